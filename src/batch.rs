@@ -0,0 +1,76 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// A note that could not be deleted, for the end-of-run report
+#[derive(Debug, Serialize)]
+pub struct DeleteFailure {
+    pub note_id: String,
+    pub error: String,
+}
+
+/// What `notes delete-many` did, printed with `--output json`
+#[derive(Debug, Serialize)]
+pub struct DeleteSummary {
+    pub total: usize,
+    pub deleted: usize,
+    pub failures: Vec<DeleteFailure>,
+}
+
+/// Note IDs from `args` followed by those in `path`, without repeats
+pub fn collect_note_ids(
+    args: Vec<String>,
+    path: Option<&Path>,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut ids = args;
+    if let Some(path) = path {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        ids.extend(parse_note_ids(&text));
+    }
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    if ids.is_empty() {
+        return Err("No note IDs given.".into());
+    }
+    Ok(ids)
+}
+
+/// One ID per line; blank lines and lines starting with `#` are skipped
+pub fn parse_note_ids(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_note_ids_skips_blanks_and_comments() {
+        let text = "note_1\n\n  note_2  \r\n# old notes\nnote_3\n";
+        assert_eq!(parse_note_ids(text), ["note_1", "note_2", "note_3"]);
+    }
+
+    #[test]
+    fn test_collect_note_ids_merges_args_and_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ids.txt");
+        fs::write(&path, "note_2\nnote_3\n").unwrap();
+
+        let ids = collect_note_ids(
+            vec!["note_1".to_string(), "note_2".to_string()],
+            Some(&path),
+        )
+        .unwrap();
+        assert_eq!(ids, ["note_1", "note_2", "note_3"]);
+
+        assert!(collect_note_ids(Vec::new(), None).is_err());
+        assert!(collect_note_ids(Vec::new(), Some(&dir.path().join("missing"))).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use crate::models::Note;
+
+/// The path segment Attio's web app uses for an object's records, which is
+/// singular for the standard objects
+fn object_path(object: &str) -> &str {
+    match object {
+        "people" => "person",
+        "companies" => "company",
+        other => other,
+    }
+}
+
+/// Link to `note` in Attio's web app: its parent record with the note open
+pub fn note_browser_url(slug: &str, note: &Note) -> String {
+    format!(
+        "https://app.attio.com/{}/{}/{}/notes?modal=note&id={}",
+        slug,
+        object_path(&note.parent_object),
+        note.parent_record_id,
+        note.id.note_id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note_on(parent_object: &str) -> Note {
+        Note::test("note_1").with_parent(parent_object, "rec_1")
+    }
+
+    #[test]
+    fn test_note_browser_url_uses_singular_standard_objects() {
+        assert_eq!(
+            note_browser_url("acme", &note_on("people")),
+            "https://app.attio.com/acme/person/rec_1/notes?modal=note&id=note_1"
+        );
+        assert_eq!(
+            note_browser_url("acme", &note_on("companies")),
+            "https://app.attio.com/acme/company/rec_1/notes?modal=note&id=note_1"
+        );
+    }
+
+    #[test]
+    fn test_note_browser_url_keeps_other_objects() {
+        assert_eq!(
+            note_browser_url("acme", &note_on("deals")),
+            "https://app.attio.com/acme/deals/rec_1/notes?modal=note&id=note_1"
+        );
+    }
+}
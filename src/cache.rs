@@ -1,4 +1,138 @@
+use crate::client::AttioClient;
+use crate::fsutil;
 use crate::models::Note;
+use crate::models::config::token_fingerprint;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Minimum number of fetched pages between two auto-saves
+const AUTOSAVE_EVERY_PAGES: u32 = 5;
+/// Minimum time between two auto-saves
+const AUTOSAVE_MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// On-disk layout of the notes cache
+#[derive(Serialize)]
+struct DiskCache<'a> {
+    /// Unix timestamp (seconds) of the write
+    saved_at: u64,
+    /// Notes refreshed individually since `saved_at`, by note ID
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    fetched_at: HashMap<&'a str, u64>,
+    notes: Vec<&'a Note>,
+}
+
+impl<'a> DiskCache<'a> {
+    /// The notes to write, without the least recently fetched ones that
+    /// don't fit in `limit` bytes as measured by `estimate_note_size`. Notes
+    /// without their own fetch time count as fetched at `saved_at`; among
+    /// equals, earlier notes go first.
+    fn within_limit(
+        saved_at: u64,
+        fetched_at: &'a HashMap<String, u64>,
+        notes: &'a [Note],
+        limit: usize,
+    ) -> Self {
+        let fetched = |note: &Note| {
+            fetched_at
+                .get(&note.id.note_id)
+                .copied()
+                .unwrap_or(saved_at)
+        };
+        let mut keep = vec![true; notes.len()];
+        let mut size: usize = notes.iter().map(estimate_note_size).sum();
+        if size > limit {
+            let mut oldest_first: Vec<usize> = (0..notes.len()).collect();
+            oldest_first.sort_by_key(|&i| fetched(&notes[i]));
+            for i in oldest_first {
+                if size <= limit {
+                    break;
+                }
+                keep[i] = false;
+                size -= estimate_note_size(&notes[i]);
+            }
+        }
+        let notes: Vec<&Note> = notes
+            .iter()
+            .zip(keep)
+            .filter_map(|(note, keep)| keep.then_some(note))
+            .collect();
+        let fetched_at = notes
+            .iter()
+            .filter_map(|note| {
+                let id = note.id.note_id.as_str();
+                fetched_at.get(id).map(|&at| (id, at))
+            })
+            .collect();
+        Self {
+            saved_at,
+            fetched_at,
+            notes,
+        }
+    }
+}
+
+/// The notes cache as read back from disk
+#[derive(Debug, Deserialize)]
+pub struct CachedNotes {
+    pub saved_at: u64,
+    #[serde(default)]
+    pub fetched_at: HashMap<String, u64>,
+    pub notes: Vec<Note>,
+}
+
+impl CachedNotes {
+    pub fn find(&self, note_id: &str) -> Option<&Note> {
+        self.notes.iter().find(|note| note.id.note_id == note_id)
+    }
+
+    /// How long ago the note was fetched, if it is cached
+    pub fn age_of(&self, note_id: &str, now: u64) -> Option<Duration> {
+        self.find(note_id)?;
+        let fetched_at = self
+            .fetched_at
+            .get(note_id)
+            .copied()
+            .unwrap_or(self.saved_at);
+        Some(Duration::from_secs(now.saturating_sub(fetched_at)))
+    }
+
+    /// Adds or replaces a note fetched at `now`
+    pub fn upsert(&mut self, note: Note, now: u64) {
+        self.fetched_at.insert(note.id.note_id.clone(), now);
+        match self
+            .notes
+            .iter_mut()
+            .find(|cached| cached.id.note_id == note.id.note_id)
+        {
+            Some(cached) => *cached = note,
+            None => self.notes.push(note),
+        }
+    }
+}
+
+/// Human-readable cache age, e.g. `42s`, `5m`, `3h`, `2d`
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3_600 => format!("{}m", secs / 60),
+        3_600..86_400 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+/// Current time as a Unix timestamp in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
 
 /// Estimates the memory size of a note in bytes.
 ///
@@ -16,25 +150,351 @@ pub fn estimate_note_size(note: &Note) -> usize {
         + note.created_at.capacity()
 }
 
+/// When each cached note was last viewed or fetched, for LRU eviction
+#[derive(Debug, Default)]
+pub struct AccessLog {
+    clock: u64,
+    last_access: HashMap<String, u64>,
+}
+
+impl AccessLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks a note as the most recently used
+    pub fn touch(&mut self, note_id: &str) {
+        self.clock += 1;
+        self.last_access.insert(note_id.to_string(), self.clock);
+    }
+
+    /// Notes that were never touched sort before every touched note
+    fn last_access(&self, note_id: &str) -> u64 {
+        self.last_access.get(note_id).copied().unwrap_or(0)
+    }
+}
+
+/// Evicts the least-recently-used notes from `cache` until `incoming` more
+/// bytes fit within `limit`. Returns how many notes were evicted.
+///
+/// Nothing is evicted when `incoming` alone exceeds `limit`, since no amount
+/// of eviction would make room for it.
+pub fn evict_lru(
+    cache: &mut Vec<Note>,
+    cache_size: &mut usize,
+    incoming: usize,
+    limit: usize,
+    access: &mut AccessLog,
+) -> usize {
+    if incoming > limit || *cache_size + incoming <= limit {
+        return 0;
+    }
+
+    let mut order: Vec<usize> = (0..cache.len()).collect();
+    order.sort_by_key(|&i| (access.last_access(&cache[i].id.note_id), i));
+
+    let mut evict = vec![false; cache.len()];
+    let mut evicted = 0;
+    for i in order {
+        if *cache_size + incoming <= limit {
+            break;
+        }
+        *cache_size = cache_size.saturating_sub(estimate_note_size(&cache[i]));
+        evict[i] = true;
+        evicted += 1;
+    }
+
+    let mut index = 0;
+    cache.retain(|note| {
+        let keep = !evict[index];
+        index += 1;
+        if !keep {
+            access.last_access.remove(&note.id.note_id);
+        }
+        keep
+    });
+    evicted
+}
+
+/// Outcome of adding a page of notes to the in-memory cache
+#[derive(Debug, Default, PartialEq)]
+pub struct Admission {
+    pub added: usize,
+    /// Notes whose ID was already cached
+    pub duplicates: usize,
+    /// Older notes evicted to make room
+    pub evicted: usize,
+    /// A note was skipped because it is larger than the whole limit
+    pub limit_reached: bool,
+}
+
+/// Adds `new_notes` to `cache`, evicting the least-recently-used notes to
+/// stay within `limit` bytes. Admitted notes count as just used.
+///
+/// Notes already in the cache are counted as duplicates and skipped when
+/// `dedupe` is set; otherwise they are admitted again, which is useful for
+/// spotting unstable offset paging in the API.
+pub fn admit_notes(
+    cache: &mut Vec<Note>,
+    cache_size: &mut usize,
+    new_notes: Vec<Note>,
+    limit: usize,
+    dedupe: bool,
+    access: &mut AccessLog,
+) -> Admission {
+    let mut admission = Admission::default();
+    for note in new_notes {
+        if cache.iter().any(|n| n.id.note_id == note.id.note_id) {
+            admission.duplicates += 1;
+            if dedupe {
+                continue;
+            }
+        }
+        let note_size = estimate_note_size(&note);
+        if note_size > limit {
+            admission.limit_reached = true;
+            continue;
+        }
+        admission.evicted += evict_lru(cache, cache_size, note_size, limit, access);
+        *cache_size += note_size;
+        access.touch(&note.id.note_id);
+        cache.push(note);
+        admission.added += 1;
+    }
+    admission
+}
+
+/// Location of the persisted notes cache for `token`, e.g.
+/// `~/.cache/attio/notes-<fingerprint>.json`. Each token has its own file, so
+/// switching profiles or projects never serves another workspace's notes.
+pub fn disk_cache_path(token: &str) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("attio").join(disk_cache_file_name(token)))
+}
+
+fn disk_cache_file_name(token: &str) -> String {
+    format!("notes-{}.json", token_fingerprint(token.trim()))
+}
+
+/// Writes `notes` to `path` atomically, leaving out the earliest ones that
+/// don't fit in `limit` bytes.
+///
+/// The data goes to a sibling temp file first and is then renamed over the
+/// target, so an interrupted write never leaves a truncated cache behind.
+/// The file holds note content, so it is only readable by the current user.
+pub fn save_notes(path: &Path, notes: &[Note], limit: usize) -> io::Result<()> {
+    let fetched_at = HashMap::new();
+    write_disk_cache(
+        path,
+        &DiskCache::within_limit(unix_now(), &fetched_at, notes, limit),
+    )
+}
+
+/// Writes back a cache read with `load_notes`, keeping per-note fetch times
+/// and leaving out the least recently fetched notes that don't fit in
+/// `limit` bytes
+pub fn save_cached(path: &Path, cached: &CachedNotes, limit: usize) -> io::Result<()> {
+    write_disk_cache(
+        path,
+        &DiskCache::within_limit(cached.saved_at, &cached.fetched_at, &cached.notes, limit),
+    )
+}
+
+/// Reads the notes cache; `Ok(None)` when there is no cache file yet
+pub fn load_notes(path: &Path) -> io::Result<Option<CachedNotes>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// What `cache info` reports about the notes cache file
+#[derive(Debug, Serialize)]
+pub struct DiskCacheInfo {
+    pub path: PathBuf,
+    /// Size of the file; 0 when there is none
+    pub size_bytes: u64,
+    /// Notes in the file; `None` when it can't be read
+    pub notes: Option<usize>,
+    /// Unix timestamp of the last full save
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_at: Option<u64>,
+    /// The most bytes of notes a write keeps
+    pub limit_bytes: usize,
+}
+
+/// Size and contents of the notes cache at `path`
+pub fn disk_cache_info(path: &Path, limit: usize) -> io::Result<DiskCacheInfo> {
+    let size_bytes = match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(e),
+    };
+    let cached = load_notes(path);
+    Ok(DiskCacheInfo {
+        path: path.to_path_buf(),
+        size_bytes,
+        notes: match &cached {
+            Ok(cached) => Some(cached.as_ref().map_or(0, |cached| cached.notes.len())),
+            Err(_) => None,
+        },
+        saved_at: cached.ok().flatten().map(|cached| cached.saved_at),
+        limit_bytes: limit,
+    })
+}
+
+/// Deletes the notes cache at `path` along with any temp file an
+/// interrupted write left behind. Returns false when there was no cache.
+pub fn clear_disk_cache(path: &Path) -> io::Result<bool> {
+    let _ = fs::remove_file(path.with_extension("json.tmp"));
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn write_disk_cache(path: &Path, cache: &DiskCache) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_vec(cache)?;
+
+    let tmp_path = path.with_extension("json.tmp");
+    fsutil::write_private(&tmp_path, &json)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Loads the notes cache of `token` for `notes list --cache-only`, erroring
+/// when there is nothing to list
+pub fn load_for_listing(token: &str) -> Result<CachedNotes, Box<dyn Error>> {
+    let path = disk_cache_path(token).ok_or("Could not determine the cache directory.")?;
+    let cached = load_notes(&path)
+        .map_err(|e| format!("Could not read the notes cache {}: {}", path.display(), e))?;
+    match cached {
+        Some(cached) if !cached.notes.is_empty() => Ok(cached),
+        _ => Err(
+            "The notes cache is empty. Open `attio notes list` and press Ctrl+A to fill it.".into(),
+        ),
+    }
+}
+
+/// Fetches a note, serving it from the disk cache at `path` when it was
+/// fetched less than `ttl` ago (unless `refresh` is set) and caching network
+/// results in a cache of at most `limit` bytes.
+///
+/// Returns the note and, when it came from the cache, its age. Cache read or
+/// write failures never fail the lookup; they just fall back to the network.
+pub async fn get_note(
+    client: &AttioClient,
+    path: Option<PathBuf>,
+    note_id: &str,
+    ttl: Duration,
+    refresh: bool,
+    limit: usize,
+) -> Result<(Note, Option<Duration>), Box<dyn Error>> {
+    let mut cached = path
+        .as_deref()
+        .and_then(|path| load_notes(path).ok().flatten());
+    let now = unix_now();
+
+    if !refresh
+        && let Some(cached) = &cached
+        && let Some(age) = cached.age_of(note_id, now)
+        && age < ttl
+        && let Some(note) = cached.find(note_id)
+    {
+        return Ok((note.clone(), Some(age)));
+    }
+
+    let note = client.get_note(note_id).await?.data;
+
+    // Saving rewrites the whole file, so an unchanged note is left as it was.
+    // Its fetch time isn't renewed either; it's fetched again next time.
+    let unchanged = cached
+        .as_ref()
+        .and_then(|cached| cached.find(note_id))
+        .is_some_and(|cached| *cached == note);
+    if let Some(path) = path.filter(|_| !unchanged) {
+        let cache = cached.get_or_insert_with(|| CachedNotes {
+            saved_at: now,
+            fetched_at: HashMap::new(),
+            notes: Vec::new(),
+        });
+        cache.upsert(note.clone(), now);
+        let _ = save_cached(&path, cache, limit);
+    }
+
+    Ok((note, None))
+}
+
+/// Flushes the cache to disk periodically during long fetches.
+///
+/// A save happens at most every `AUTOSAVE_EVERY_PAGES` pages and never more
+/// often than `AUTOSAVE_MIN_INTERVAL`, to avoid rewriting a large file on
+/// every page.
+pub struct AutoSaver {
+    path: PathBuf,
+    /// Most bytes of notes the file may hold
+    limit: usize,
+    pages_since_save: u32,
+    last_save: Option<Instant>,
+}
+
+impl AutoSaver {
+    pub fn new(path: PathBuf, limit: usize) -> Self {
+        Self {
+            path,
+            limit,
+            pages_since_save: 0,
+            last_save: None,
+        }
+    }
+
+    /// Records a fetched page, saving `notes` if the throttle allows it.
+    /// Returns whether a save happened.
+    pub fn page_fetched(&mut self, notes: &[Note]) -> io::Result<bool> {
+        self.pages_since_save += 1;
+        if !self.is_due(Instant::now()) {
+            return Ok(false);
+        }
+        self.flush(notes)?;
+        Ok(true)
+    }
+
+    /// Saves `notes` unconditionally, e.g. when a fetch finishes
+    pub fn flush(&mut self, notes: &[Note]) -> io::Result<()> {
+        save_notes(&self.path, notes, self.limit)?;
+        self.pages_since_save = 0;
+        self.last_save = Some(Instant::now());
+        Ok(())
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        self.pages_since_save >= AUTOSAVE_EVERY_PAGES
+            && self
+                .last_save
+                .is_none_or(|last| now.duration_since(last) >= AUTOSAVE_MIN_INTERVAL)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Note, NoteId};
+    use crate::models::Note;
+
+    const NO_LIMIT: usize = usize::MAX;
+
+    fn note(id: &str) -> Note {
+        Note::test(id)
+    }
 
     #[test]
     fn test_estimate_note_size() {
-        let note = Note {
-            id: NoteId {
-                workspace_id: "ws_123".to_string(),
-                note_id: "note_456".to_string(),
-            },
-            parent_object: "people".to_string(),
-            parent_record_id: "00000000-0000-0000-0000-000000000000".to_string(),
-            title: "Test Note".to_string(),
-            content_plaintext: "Hello world".to_string(),
-            content_markdown: "Hello **world**".to_string(),
-            created_at: "2023-01-01T00:00:00Z".to_string(),
-        };
+        let note = Note::test("note_456")
+            .with_plaintext("Hello world")
+            .with_markdown("Hello **world**");
 
         let size = estimate_note_size(&note);
         // Size should be at least the size of the struct itself
@@ -42,4 +502,400 @@ mod tests {
         // And should include some string data
         assert!(size > std::mem::size_of::<Note>());
     }
+
+    #[test]
+    fn test_admit_notes_skips_duplicates() {
+        let mut cache = vec![note("a")];
+        let mut size = 0;
+
+        let admission = admit_notes(
+            &mut cache,
+            &mut size,
+            vec![note("a"), note("b")],
+            1 << 20,
+            true,
+            &mut AccessLog::new(),
+        );
+
+        assert_eq!(
+            admission,
+            Admission {
+                added: 1,
+                duplicates: 1,
+                evicted: 0,
+                limit_reached: false
+            }
+        );
+        assert_eq!(cache.len(), 2);
+        assert_eq!(size, estimate_note_size(&cache[1]));
+    }
+
+    #[test]
+    fn test_admit_notes_without_dedupe_keeps_duplicates() {
+        let mut cache = vec![note("a")];
+        let mut size = 0;
+
+        let admission = admit_notes(
+            &mut cache,
+            &mut size,
+            vec![note("a"), note("a")],
+            1 << 20,
+            false,
+            &mut AccessLog::new(),
+        );
+
+        assert_eq!(admission.added, 2);
+        assert_eq!(admission.duplicates, 2);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_admit_notes_evicts_at_limit() {
+        let mut cache = Vec::new();
+        let mut size = 0;
+        let limit = estimate_note_size(&note("a")) + 1;
+
+        let admission = admit_notes(
+            &mut cache,
+            &mut size,
+            vec![note("a"), note("b")],
+            limit,
+            true,
+            &mut AccessLog::new(),
+        );
+
+        assert_eq!(admission.added, 2);
+        assert_eq!(admission.evicted, 1);
+        assert!(!admission.limit_reached);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache[0].id.note_id, "b");
+        assert!(size <= limit);
+    }
+
+    #[test]
+    fn test_evict_lru_removes_least_recently_used() {
+        let mut cache = vec![note("a"), note("b"), note("c")];
+        let mut size: usize = cache.iter().map(estimate_note_size).sum();
+        let one = estimate_note_size(&note("a"));
+        let mut access = AccessLog::new();
+        access.touch("b");
+        access.touch("a");
+        access.touch("c");
+        access.touch("b");
+
+        // Room for one more note means two of the three must go
+        let evicted = evict_lru(&mut cache, &mut size, one, 2 * one, &mut access);
+
+        assert_eq!(evicted, 2);
+        let ids: Vec<&str> = cache.iter().map(|n| n.id.note_id.as_str()).collect();
+        assert_eq!(ids, ["b"]);
+        assert_eq!(size, one);
+    }
+
+    #[test]
+    fn test_evict_lru_prefers_untouched_notes() {
+        let mut cache = vec![note("a"), note("b")];
+        let one = estimate_note_size(&note("a"));
+        let mut size = 2 * one;
+        let mut access = AccessLog::new();
+        access.touch("a");
+
+        assert_eq!(
+            evict_lru(&mut cache, &mut size, one, 2 * one, &mut access),
+            1
+        );
+        assert_eq!(cache[0].id.note_id, "a");
+    }
+
+    #[test]
+    fn test_note_larger_than_limit_evicts_nothing() {
+        let mut cache = vec![note("a")];
+        let one = estimate_note_size(&note("a"));
+        let mut size = one;
+        let mut access = AccessLog::new();
+
+        assert_eq!(
+            evict_lru(&mut cache, &mut size, 3 * one, 2 * one, &mut access),
+            0
+        );
+        assert_eq!(cache.len(), 1);
+
+        let mut huge = note("huge");
+        huge.content_markdown = "x".repeat(4 * one);
+        let admission = admit_notes(
+            &mut cache,
+            &mut size,
+            vec![huge, note("b")],
+            2 * one,
+            true,
+            &mut access,
+        );
+
+        // The oversized note is skipped; the cache keeps what it had
+        assert!(admission.limit_reached);
+        assert_eq!(admission.added, 1);
+        assert_eq!(admission.evicted, 0);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(0)), "0s");
+        assert_eq!(format_age(Duration::from_secs(59)), "59s");
+        assert_eq!(format_age(Duration::from_secs(150)), "2m");
+        assert_eq!(format_age(Duration::from_secs(7_200)), "2h");
+        assert_eq!(format_age(Duration::from_secs(172_800)), "2d");
+    }
+
+    #[test]
+    fn test_load_notes_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        assert!(load_notes(&path).unwrap().is_none());
+
+        save_notes(&path, &[note("a"), note("b")], NO_LIMIT).unwrap();
+        let cached = load_notes(&path).unwrap().unwrap();
+
+        assert_eq!(cached.notes.len(), 2);
+        assert!(cached.find("b").is_some());
+        assert!(cached.find("z").is_none());
+    }
+
+    #[test]
+    fn test_load_notes_rejects_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(load_notes(&path).is_err());
+    }
+
+    #[test]
+    fn test_upsert_tracks_per_note_age() {
+        let mut cached = CachedNotes {
+            saved_at: 1_000,
+            fetched_at: HashMap::new(),
+            notes: vec![note("a")],
+        };
+
+        assert_eq!(cached.age_of("a", 1_100), Some(Duration::from_secs(100)));
+        assert_eq!(cached.age_of("b", 1_100), None);
+
+        cached.upsert(note("a"), 1_090);
+        cached.upsert(note("b"), 1_095);
+
+        assert_eq!(cached.notes.len(), 2);
+        assert_eq!(cached.age_of("a", 1_100), Some(Duration::from_secs(10)));
+        assert_eq!(cached.age_of("b", 1_100), Some(Duration::from_secs(5)));
+    }
+
+    #[tokio::test]
+    async fn test_get_note_only_saves_changed_notes() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("notes.json");
+        let cached = CachedNotes {
+            saved_at: 1_000,
+            fetched_at: HashMap::new(),
+            notes: vec![note("a")],
+        };
+        save_cached(&cache_path, &cached, NO_LIMIT).unwrap();
+        let before = fs::read(&cache_path).unwrap();
+        let client = AttioClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&server.uri());
+        let get = || {
+            get_note(
+                &client,
+                Some(cache_path.clone()),
+                "a",
+                Duration::ZERO,
+                false,
+                NO_LIMIT,
+            )
+        };
+
+        let response = |note: Note| {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": note }))
+        };
+        Mock::given(method("GET"))
+            .and(path("/notes/a"))
+            .respond_with(response(note("a")))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        get().await.unwrap();
+        assert_eq!(fs::read(&cache_path).unwrap(), before);
+
+        Mock::given(method("GET"))
+            .and(path("/notes/a"))
+            .respond_with(response(note("a").with_title("Renamed")))
+            .mount(&server)
+            .await;
+        get().await.unwrap();
+        let saved = load_notes(&cache_path).unwrap().unwrap();
+        assert_eq!(saved.find("a").unwrap().title, "Renamed");
+    }
+
+    #[test]
+    fn test_save_cached_keeps_fetch_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let mut cached = CachedNotes {
+            saved_at: 1_000,
+            fetched_at: HashMap::new(),
+            notes: Vec::new(),
+        };
+        cached.upsert(note("a"), 2_000);
+
+        save_cached(&path, &cached, NO_LIMIT).unwrap();
+        let loaded = load_notes(&path).unwrap().unwrap();
+
+        assert_eq!(loaded.saved_at, 1_000);
+        assert_eq!(loaded.fetched_at.get("a"), Some(&2_000));
+    }
+
+    #[test]
+    fn test_save_cached_drops_least_recently_fetched_over_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let mut cached = CachedNotes {
+            saved_at: 1_000,
+            fetched_at: HashMap::new(),
+            notes: vec![note("a"), note("b"), note("c")],
+        };
+        // "a" was refreshed since the save, so "b" is now the oldest
+        cached.upsert(note("a"), 2_000);
+        let two_notes = estimate_note_size(&note("a")) * 2;
+
+        save_cached(&path, &cached, two_notes).unwrap();
+        let loaded = load_notes(&path).unwrap().unwrap();
+
+        let ids: Vec<&str> = loaded.notes.iter().map(|n| n.id.note_id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(loaded.fetched_at.get("a"), Some(&2_000));
+    }
+
+    #[test]
+    fn test_save_notes_drops_earliest_over_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let one_note = estimate_note_size(&note("a"));
+
+        save_notes(&path, &[note("a"), note("b"), note("c")], one_note).unwrap();
+        let loaded = load_notes(&path).unwrap().unwrap();
+        assert_eq!(loaded.notes.len(), 1);
+        assert!(loaded.find("c").is_some());
+
+        save_notes(&path, &[note("a")], 0).unwrap();
+        assert!(load_notes(&path).unwrap().unwrap().notes.is_empty());
+    }
+
+    #[test]
+    fn test_each_token_has_its_own_cache_file() {
+        assert_eq!(
+            disk_cache_file_name("tok_a"),
+            disk_cache_file_name(" tok_a\n")
+        );
+        assert_ne!(disk_cache_file_name("tok_a"), disk_cache_file_name("tok_b"));
+        assert!(!disk_cache_file_name("tok_a").contains("tok_a"));
+    }
+
+    #[test]
+    fn test_disk_cache_info_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let info = disk_cache_info(&path, 10).unwrap();
+        assert_eq!(
+            (info.size_bytes, info.notes, info.saved_at),
+            (0, Some(0), None)
+        );
+        assert!(!clear_disk_cache(&path).unwrap());
+
+        save_notes(&path, &[note("a"), note("b")], NO_LIMIT).unwrap();
+        let info = disk_cache_info(&path, 10).unwrap();
+        assert_eq!(info.size_bytes, fs::metadata(&path).unwrap().len());
+        assert_eq!(info.notes, Some(2));
+        assert!(info.saved_at.is_some());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(disk_cache_info(&path, 10).unwrap().notes, None);
+
+        assert!(clear_disk_cache(&path).unwrap());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_save_notes_writes_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("attio").join("notes.json");
+
+        save_notes(&path, &[note("a"), note("b")], NO_LIMIT).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["notes"].as_array().unwrap().len(), 2);
+        assert!(saved["saved_at"].as_u64().unwrap() > 0);
+        // The temp file is renamed away, not left behind
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_notes_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+
+        save_notes(&path, &[note("a")], NO_LIMIT).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_save_notes_replaces_previous_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+
+        save_notes(&path, &[note("a"), note("b")], NO_LIMIT).unwrap();
+        save_notes(&path, &[note("c")], NO_LIMIT).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["notes"][0]["id"]["note_id"], "c");
+        assert_eq!(saved["notes"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_autosaver_waits_for_enough_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let mut saver = AutoSaver::new(path.clone(), NO_LIMIT);
+        let notes = [note("a")];
+
+        for _ in 1..AUTOSAVE_EVERY_PAGES {
+            assert!(!saver.page_fetched(&notes).unwrap());
+        }
+        assert!(!path.exists());
+
+        assert!(saver.page_fetched(&notes).unwrap());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_autosaver_throttles_by_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut saver = AutoSaver::new(dir.path().join("notes.json"), NO_LIMIT);
+        saver.flush(&[note("a")]).unwrap();
+        saver.pages_since_save = AUTOSAVE_EVERY_PAGES;
+
+        let last = saver.last_save.unwrap();
+        assert!(!saver.is_due(last));
+        assert!(saver.is_due(last + AUTOSAVE_MIN_INTERVAL));
+    }
 }
@@ -1,18 +1,220 @@
-use crate::models::ListNotesResponse;
-use reqwest::{Client, header};
+use crate::error::AttioError;
+use crate::models::{GetResponse, ListNotesResponse, ListResponse, Note};
+use crate::retry::{self, CircuitBreaker, RetryPolicy};
+use crate::trace::Tracer;
+use futures::{Stream, StreamExt, TryStreamExt};
+use reqwest::{Client, RequestBuilder, Response, header};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::time::Duration;
 
-const BASE_URL: &str = "https://api.attio.com/v2";
+pub const DEFAULT_BASE_URL: &str = "https://api.attio.com/v2";
 
+/// Checks that `url` is an absolute http(s) URL and strips any trailing slash
+pub fn normalize_base_url(url: &str) -> Result<String, Box<dyn Error>> {
+    let url = url.trim().trim_end_matches('/');
+    let parsed =
+        reqwest::Url::parse(url).map_err(|e| format!("Invalid base URL '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(format!(
+            "Invalid base URL '{}'. Expected an http(s) URL such as {}",
+            url, DEFAULT_BASE_URL
+        )
+        .into());
+    }
+    Ok(url.to_string())
+}
+
+/// Turns a non-success response into an `AttioError::Api`
+async fn api_error(response: Response) -> Box<dyn Error> {
+    let status = response.status();
+    let retry_after = retry::retry_after(response.headers());
+    match response.text().await {
+        Ok(body) => AttioError::Api {
+            status,
+            body,
+            retry_after,
+        }
+        .into(),
+        Err(e) => e.into(),
+    }
+}
+
+/// Like `api_error`, but a 404 becomes `AttioError::NotFound` naming
+/// `resource`, e.g. "Note abc"
+async fn api_error_for(response: Response, resource: String) -> Box<dyn Error> {
+    if response.status() != reqwest::StatusCode::NOT_FOUND {
+        return api_error(response).await;
+    }
+    match response.text().await {
+        Ok(body) => AttioError::NotFound { resource, body }.into(),
+        Err(e) => e.into(),
+    }
+}
+
+/// True for errors that say the API couldn't be reached, as opposed to
+/// requests that were never sendable
+fn is_outage(error: &(dyn Error + 'static)) -> bool {
+    if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+        return e.is_connect() || e.is_timeout();
+    }
+    matches!(
+        error.downcast_ref::<AttioError>(),
+        Some(AttioError::Timeout { .. })
+    )
+}
+
+/// A mutating request that was built but not sent, shown by `--dry-run`
+#[derive(Debug, Serialize)]
+pub struct DryRunRequest {
+    pub method: String,
+    pub url: String,
+    /// The JSON body, if the request has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+impl DryRunRequest {
+    /// Builds `request` without sending it. Client-wide headers such as the
+    /// token are only added at send time, so they never appear here.
+    pub fn prepare(request: RequestBuilder) -> Result<Self, Box<dyn Error>> {
+        let request = request.build()?;
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| {
+                serde_json::from_slice(bytes).unwrap_or_else(|_| {
+                    serde_json::Value::String(String::from_utf8_lossy(bytes).into_owned())
+                })
+            });
+        Ok(Self {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            body,
+        })
+    }
+}
+
+impl fmt::Display for DryRunRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        if let Some(body) = &self.body {
+            let body = serde_json::to_string_pretty(body).map_err(|_| fmt::Error)?;
+            write!(f, "\n{}", body)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the next page of notes starts
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageToken {
+    Offset(u32),
+    /// Cursor returned by the API, which is stable while notes are being created
+    Cursor(String),
+}
+
+impl PageToken {
+    /// The token for the page after one that returned `fetched` notes, or
+    /// `None` when there are no more pages.
+    ///
+    /// A cursor from the API is always preferred; once paging by cursor, a
+    /// response without one means the cursor is exhausted. An empty page, or
+    /// a cursor pointing back at the same page, also ends paging, so a
+    /// misbehaving API can't keep a fetch going forever.
+    pub fn advance(&self, fetched: usize, limit: u32, next_cursor: Option<String>) -> Option<Self> {
+        if fetched == 0 {
+            return None;
+        }
+        match (next_cursor, self) {
+            (Some(cursor), Self::Cursor(current)) if cursor == *current => None,
+            (Some(cursor), _) => Some(Self::Cursor(cursor)),
+            (None, Self::Cursor(_)) => None,
+            (None, Self::Offset(_)) if fetched < limit as usize => None,
+            (None, Self::Offset(offset)) => Some(Self::Offset(offset + fetched as u32)),
+        }
+    }
+}
+
+/// A page of notes from `note_pages`
+#[derive(Debug)]
+pub struct NotesPage {
+    pub notes: Vec<Note>,
+    /// Total number of notes, when the API reports one
+    pub total_count: Option<usize>,
+    /// Where the page after this one starts, or `None` after the last page
+    pub next: Option<PageToken>,
+}
+
+/// What a conditional request returned
+#[derive(Debug)]
+pub enum Conditional<T> {
+    /// Nothing matched the ETag sent (or none was sent); `etag` identifies
+    /// this version
+    Modified { value: T, etag: Option<String> },
+    /// The resource still matches the ETag sent
+    NotModified,
+}
+
+/// Connection settings fixed when the underlying HTTP client is built
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Request gzip/brotli/deflate-compressed responses. Compression shrinks
+    /// large note lists considerably; turning it off trades bandwidth for CPU
+    /// on constrained machines.
+    pub compression: bool,
+    /// Limit for connecting and for each whole request; `None` waits forever
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            compression: true,
+            timeout: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct AttioClient {
     client: Client,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    breaker: CircuitBreaker,
+    timeout: Option<Duration>,
+    /// Parent object and record every notes listing is narrowed to
+    note_parent: Option<(String, String)>,
+    /// Where every request and response is recorded, when tracing
+    trace: Option<Tracer>,
+    /// Headers `client` adds to every request, so traces show them
+    default_headers: header::HeaderMap,
+}
+
+/// How `send_guarded` treats a request
+#[derive(Debug, Clone, Copy)]
+struct SendMode {
+    /// Safe to send again after a 5xx or a timeout
+    idempotent: bool,
+    /// The caller streams the body, so a trace records only a summary
+    streamed: bool,
 }
 
 impl AttioClient {
-    pub fn new(token: String) -> Self {
+    #[cfg(test)]
+    pub fn new(token: String) -> Result<Self, AttioError> {
+        Self::with_options(token, ClientOptions::default())
+    }
+
+    /// Fails if `token` isn't a valid header value or the HTTP client can't be built
+    pub fn with_options(token: String, options: ClientOptions) -> Result<Self, AttioError> {
         let mut headers = header::HeaderMap::new();
 
-        let mut auth_value = header::HeaderValue::from_str(&format!("Bearer {}", token)).unwrap();
+        let mut auth_value = header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| AttioError::InvalidToken)?;
         auth_value.set_sensitive(true);
         headers.insert(header::AUTHORIZATION, auth_value);
         headers.insert(
@@ -20,22 +222,209 @@ impl AttioClient {
             header::HeaderValue::from_static("attio-cli/0.1.0"),
         );
 
-        let client = Client::builder().default_headers(headers).build().unwrap();
+        let mut builder = Client::builder()
+            .default_headers(headers.clone())
+            .gzip(options.compression)
+            .brotli(options.compression)
+            .deflate(options.compression);
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout).connect_timeout(timeout);
+        }
+        let client = builder.build().map_err(AttioError::ClientSetup)?;
+
+        Ok(Self {
+            default_headers: headers,
+            ..Self::from_http_client(client, options.timeout)
+        })
+    }
+
+    /// Wraps an already-built HTTP client, e.g. one with extra headers or a
+    /// proxy. It must send the `Authorization` header itself; `timeout` is
+    /// only used to report timeouts.
+    pub fn from_http_client(client: Client, timeout: Option<Duration>) -> Self {
+        Self {
+            client,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            breaker: CircuitBreaker::default(),
+            timeout,
+            note_parent: None,
+            trace: None,
+            default_headers: header::HeaderMap::new(),
+        }
+    }
+
+    /// Replaces the retry policy (e.g. one built from `Config`, or a fast one in tests)
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Stops requests for a while after repeated failures; clones of this
+    /// client share the breaker
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Narrows `list_notes` and everything built on it to the notes of one
+    /// parent record
+    pub fn with_note_parent(mut self, parent_object: &str, parent_record_id: &str) -> Self {
+        self.note_parent = Some((parent_object.to_string(), parent_record_id.to_string()));
+        self
+    }
+
+    /// Records every request attempt and its response with `tracer`
+    pub fn with_trace(mut self, tracer: Tracer) -> Self {
+        self.trace = Some(tracer);
+        self
+    }
+
+    /// Points the client at a different API root, e.g. a proxy or a mock server
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Sends a request, retrying transient failures according to the retry policy.
+    ///
+    /// Retries statuses in `RetryPolicy::retry_statuses` (honoring `Retry-After`)
+    /// as well as connection errors and timeouts. Other responses, including
+    /// non-429 4xx errors, are returned immediately for the caller to handle.
+    /// Non-idempotent requests such as POST are only retried on 429 and on
+    /// connection errors, when the server can't have acted on them.
+    /// Fails without sending anything while the circuit breaker is open.
+    async fn send_with_retry(&self, request: RequestBuilder) -> Result<Response, Box<dyn Error>> {
+        let idempotent = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .is_some_and(|request| request.method().is_idempotent());
+        let mode = SendMode {
+            idempotent,
+            streamed: false,
+        };
+        self.send_guarded(request, mode).await
+    }
+
+    /// `send_with_retry` for POSTs that only read, such as record queries,
+    /// which are as safe to repeat as a GET
+    async fn send_query_with_retry(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Response, Box<dyn Error>> {
+        let mode = SendMode {
+            idempotent: true,
+            streamed: false,
+        };
+        self.send_guarded(request, mode).await
+    }
+
+    async fn send_guarded(
+        &self,
+        request: RequestBuilder,
+        mode: SendMode,
+    ) -> Result<Response, Box<dyn Error>> {
+        if let Err((failures, retry_in)) = self.breaker.check() {
+            return Err(AttioError::CircuitOpen { failures, retry_in }.into());
+        }
+        let result = self.send_with_backoff(request, mode).await;
+        match &result {
+            Ok(response) if response.status().is_server_error() => self.breaker.record_failure(),
+            Ok(_) => self.breaker.record_success(),
+            Err(e) if is_outage(e.as_ref()) => self.breaker.record_failure(),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Sends a single attempt, through the tracer when tracing
+    async fn send_once(
+        &self,
+        request: RequestBuilder,
+        mode: SendMode,
+    ) -> reqwest::Result<Response> {
+        match &self.trace {
+            Some(tracer) if mode.streamed => tracer.send_summary(request).await,
+            Some(tracer) => tracer.send(request, &self.default_headers).await,
+            None => request.send().await,
+        }
+    }
+
+    async fn send_with_backoff(
+        &self,
+        request: RequestBuilder,
+        mode: SendMode,
+    ) -> Result<Response, Box<dyn Error>> {
+        let idempotent = mode.idempotent;
+        let mut retry = 0;
+        loop {
+            let attempt = request
+                .try_clone()
+                .ok_or("Request cannot be retried because its body is a stream")?;
+            let can_retry = retry + 1 < self.retry_policy.max_attempts;
 
-        Self { client }
+            match self.send_once(attempt, mode).await {
+                Ok(response)
+                    if can_retry
+                        && self.retry_policy.should_retry(response.status())
+                        && (idempotent
+                            || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS) =>
+                {
+                    let delay = self
+                        .retry_policy
+                        .delay_for(retry, retry::retry_after(response.headers()));
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) if can_retry && (e.is_connect() || (idempotent && e.is_timeout())) => {
+                    tokio::time::sleep(self.retry_policy.delay_for(retry, None)).await;
+                }
+                Err(e) if e.is_timeout() => {
+                    return Err(AttioError::Timeout {
+                        secs: self.timeout.map_or(0, |timeout| timeout.as_secs()),
+                    }
+                    .into());
+                }
+                result => return Ok(result?),
+            }
+            retry += 1;
+        }
     }
 
     pub async fn identify(&self) -> Result<crate::models::IdentifyResponse, Box<dyn Error>> {
-        let response = self.client.get(format!("{}/self", BASE_URL)).send().await?;
+        match self.identify_if_none_match(None).await? {
+            Conditional::Modified { value, .. } => Ok(value),
+            Conditional::NotModified => {
+                Err("Attio answered 304 to an unconditional request".into())
+            }
+        }
+    }
+
+    /// `identify`, sending `etag` as `If-None-Match` so an unchanged identity
+    /// comes back as a 304 without a body
+    pub async fn identify_if_none_match(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Conditional<crate::models::IdentifyResponse>, Box<dyn Error>> {
+        let mut request = self.client.get(format!("{}/self", self.base_url));
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        let response = self.send_with_retry(request).await?;
 
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Conditional::NotModified);
+        }
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(format!("API Error ({}): {}", status, body).into());
+            return Err(api_error(response).await);
         }
 
-        let response_data = response.json::<crate::models::IdentifyResponse>().await?;
-        Ok(response_data)
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let value = response.json::<crate::models::IdentifyResponse>().await?;
+        Ok(Conditional::Modified { value, etag })
     }
 
     pub async fn list_notes(
@@ -43,27 +432,22 @@ impl AttioClient {
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> Result<ListNotesResponse, Box<dyn Error>> {
-        let mut url = format!("{}/notes", BASE_URL);
-        let mut query_params = Vec::new();
-
-        if let Some(limit) = limit {
-            query_params.push(format!("limit={}", limit));
-        }
-        if let Some(offset) = offset {
-            query_params.push(format!("offset={}", offset));
-        }
-
-        if !query_params.is_empty() {
-            url.push('?');
-            url.push_str(&query_params.join("&"));
+        let mut url = reqwest::Url::parse(&format!("{}/notes", self.base_url))?;
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(limit) = limit {
+                query.append_pair("limit", &limit.to_string());
+            }
+            if let Some(offset) = offset {
+                query.append_pair("offset", &offset.to_string());
+            }
+            query.extend_pairs(self.note_parent_query());
         }
 
-        let response = self.client.get(url).send().await?;
+        let response = self.send_with_retry(self.client.get(url)).await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(format!("API Error ({}): {}", status, body).into());
+            return Err(api_error(response).await);
         }
 
         let body = response.text().await?;
@@ -71,121 +455,2098 @@ impl AttioClient {
         Ok(response_data)
     }
 
+    /// Whether `with_note_parent` narrowed the listings to one record
+    pub fn has_note_parent(&self) -> bool {
+        self.note_parent.is_some()
+    }
+
+    /// The `with_note_parent` filter as query pairs, if set
+    fn note_parent_query(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.note_parent
+            .iter()
+            .flat_map(|(parent_object, parent_record_id)| {
+                [
+                    ("parent_object", parent_object.as_str()),
+                    ("parent_record_id", parent_record_id.as_str()),
+                ]
+            })
+    }
+
+    /// Lists a page of notes starting at `cursor` (the first page when `None`)
+    pub async fn list_notes_cursor(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<ListNotesResponse, Box<dyn Error>> {
+        let mut url = reqwest::Url::parse(&format!("{}/notes", self.base_url))?;
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("limit", &limit.to_string());
+            if let Some(cursor) = cursor {
+                query.append_pair("cursor", cursor);
+            }
+            query.extend_pairs(self.note_parent_query());
+        }
+        let response = self.send_with_retry(self.client.get(url)).await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let response_data = response.json::<ListNotesResponse>().await?;
+        Ok(response_data)
+    }
+
+    /// Fetches the page of notes identified by `token`
+    pub async fn list_notes_page(
+        &self,
+        token: &PageToken,
+        limit: u32,
+    ) -> Result<ListNotesResponse, Box<dyn Error>> {
+        match token {
+            PageToken::Offset(offset) => self.list_notes(Some(limit), Some(*offset)).await,
+            PageToken::Cursor(cursor) => self.list_notes_cursor(Some(cursor), limit).await,
+        }
+    }
+
+    /// Pages of notes from `start`, fetched only as the stream is read and
+    /// waiting `delay` before each request after the first. Once paging by
+    /// offset, up to `concurrency` pages are in flight at a time but still
+    /// yielded in order. Ends after the last page or the first error, so a
+    /// caller can resume from the last page's `next`.
+    pub fn note_pages(
+        &self,
+        start: PageToken,
+        page_size: u32,
+        concurrency: usize,
+        delay: Duration,
+    ) -> impl Stream<Item = Result<NotesPage, Box<dyn Error>>> + '_ {
+        async_stream::try_stream! {
+            let mut token = start;
+            let mut first = true;
+            loop {
+                if !first {
+                    tokio::time::sleep(delay).await;
+                }
+                first = false;
+                // A cursor is only known once the page before it arrives, so
+                // pages are fetched one at a time until offsets are in use
+                let page = self.list_notes_page(&token, page_size).await?;
+                let total = page.total_count;
+                let next = token.advance(page.data.len(), page_size, page.next_cursor);
+                yield NotesPage {
+                    notes: page.data,
+                    total_count: total,
+                    next: next.clone(),
+                };
+                token = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let PageToken::Offset(offset) = token else {
+                    continue;
+                };
+                if concurrency <= 1 {
+                    continue;
+                }
+
+                // Offset pages don't depend on each other. A known total keeps
+                // requests from going past the end; otherwise the first short
+                // page ends the fetch and drops the requests still in flight.
+                let end = total.map_or(u32::MAX, |total| u32::try_from(total).unwrap_or(u32::MAX));
+                let offsets = (offset..end).step_by(page_size.max(1) as usize);
+                let mut pages = std::pin::pin!(
+                    futures::stream::iter(offsets)
+                        .map(|offset| async move {
+                            tokio::time::sleep(delay).await;
+                            let token = PageToken::Offset(offset);
+                            let page = self.list_notes_page(&token, page_size).await;
+                            (token, page)
+                        })
+                        .buffered(concurrency)
+                );
+                // Past the total, check for notes created since sequentially
+                let mut resume = Some(token.clone());
+                while let Some((current, page)) = pages.next().await {
+                    let page = page?;
+                    let next = current.advance(page.data.len(), page_size, page.next_cursor);
+                    yield NotesPage {
+                        notes: page.data,
+                        total_count: page.total_count.or(total),
+                        next: next.clone(),
+                    };
+                    resume = next;
+                    // A short page, or a cursor to follow instead
+                    if !matches!(resume, Some(PageToken::Offset(_))) {
+                        break;
+                    }
+                }
+                token = match resume {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+        }
+    }
+
+    /// Every note in the workspace, fetching pages of `page_size` only as
+    /// the stream is read. Pages by cursor when the API provides one and by
+    /// offset otherwise, and ends after the last page or the first error.
+    pub fn notes_stream(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Note, Box<dyn Error>>> + '_ {
+        self.note_pages(PageToken::Offset(0), page_size, 1, Duration::ZERO)
+            .map_ok(|page| futures::stream::iter(page.notes.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Collects `notes_stream`. `on_progress` is called with the number of
+    /// notes fetched so far after each one.
+    pub async fn list_all_notes(
+        &self,
+        page_size: u32,
+        mut on_progress: impl FnMut(usize),
+    ) -> Result<Vec<Note>, Box<dyn Error>> {
+        let mut notes = Vec::new();
+        let mut stream = std::pin::pin!(self.notes_stream(page_size));
+        while let Some(note) = stream.next().await {
+            notes.push(note?);
+            on_progress(notes.len());
+        }
+        Ok(notes)
+    }
+
     pub async fn get_note(
         &self,
         note_id: &str,
     ) -> Result<crate::models::GetNoteResponse, Box<dyn Error>> {
         let response = self
-            .client
-            .get(format!("{}/notes/{}", BASE_URL, note_id))
-            .send()
+            .send_with_retry(
+                self.client
+                    .get(format!("{}/notes/{}", self.base_url, note_id)),
+            )
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(format!("API Error ({}): {}", status, body).into());
+            return Err(api_error_for(response, format!("Note {}", note_id)).await);
         }
 
         let response_data = response.json::<crate::models::GetNoteResponse>().await?;
         Ok(response_data)
     }
 
+    /// Files uploaded to a note
+    pub async fn list_note_attachments(
+        &self,
+        note_id: &str,
+    ) -> Result<crate::models::ListAttachmentsResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(
+                self.client
+                    .get(format!("{}/notes/{}/attachments", self.base_url, note_id)),
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response
+            .json::<crate::models::ListAttachmentsResponse>()
+            .await?)
+    }
+
+    /// Whether `url` points into the API at `base_url`: same scheme, host and
+    /// port, and a path under the base path. A string prefix isn't enough,
+    /// e.g. `https://proxy.corp.evil.com` starts with `https://proxy.corp`.
+    fn is_api_url(&self, url: &str) -> bool {
+        let (Ok(url), Ok(base)) = (
+            reqwest::Url::parse(url),
+            reqwest::Url::parse(&self.base_url),
+        ) else {
+            return false;
+        };
+        let base_path = base.path().trim_end_matches('/');
+        url.scheme() == base.scheme()
+            && url.host_str() == base.host_str()
+            && url.port_or_known_default() == base.port_or_known_default()
+            && (url.path() == base_path || url.path().starts_with(&format!("{}/", base_path)))
+    }
+
+    /// Streams the file at `url` into `out` chunk by chunk, returning the
+    /// number of bytes written. The API token is only sent to the API itself,
+    /// not to other hosts such as presigned storage links.
+    pub async fn download(&self, url: &str, out: &mut impl Write) -> Result<u64, Box<dyn Error>> {
+        let api_host = self.is_api_url(url);
+        // A client from `from_http_client` sends the token itself, and its
+        // timeouts are the caller's to choose
+        let request = if api_host && self.default_headers.is_empty() {
+            self.client.get(url)
+        } else {
+            // A large file can take longer than the request timeout to
+            // arrive, so only connecting and each read are limited
+            let mut builder = Client::builder();
+            if let Some(timeout) = self.timeout {
+                builder = builder.connect_timeout(timeout).read_timeout(timeout);
+            }
+            let request = builder.build().map_err(AttioError::ClientSetup)?.get(url);
+            if api_host {
+                request.headers(self.default_headers.clone())
+            } else {
+                request
+            }
+        };
+        let mode = SendMode {
+            idempotent: true,
+            streamed: true,
+        };
+        let mut response = self.send_guarded(request, mode).await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let mut written = 0;
+        while let Some(chunk) = response.chunk().await? {
+            out.write_all(&chunk)?;
+            written += chunk.len() as u64;
+        }
+        Ok(written)
+    }
+
+    /// The request `create_note` sends
+    pub fn create_note_request(&self, data: &crate::models::CreateNoteRequest) -> RequestBuilder {
+        self.client
+            .post(format!("{}/notes", self.base_url))
+            .json(data)
+    }
+
     pub async fn create_note(
         &self,
         data: crate::models::CreateNoteRequest,
     ) -> Result<crate::models::GetNoteResponse, Box<dyn Error>> {
         let response = self
-            .client
-            .post(format!("{}/notes", BASE_URL))
-            .json(&data)
-            .send()
+            .send_with_retry(self.create_note_request(&data))
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(format!("API Error ({}): {}", status, body).into());
+            return Err(api_error(response).await);
         }
 
         let response_data = response.json::<crate::models::GetNoteResponse>().await?;
         Ok(response_data)
     }
 
+    /// The request `delete_note` sends
+    pub fn delete_note_request(&self, note_id: &str) -> RequestBuilder {
+        self.client
+            .delete(format!("{}/notes/{}", self.base_url, note_id))
+    }
+
     pub async fn delete_note(&self, note_id: &str) -> Result<(), Box<dyn Error>> {
         let response = self
-            .client
-            .delete(format!("{}/notes/{}", BASE_URL, note_id))
-            .send()
+            .send_with_retry(self.delete_note_request(note_id))
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(format!("API Error ({}): {}", status, body).into());
+            return Err(api_error_for(response, format!("Note {}", note_id)).await);
         }
 
         Ok(())
     }
 
-    #[cfg(test)]
-    pub(crate) fn build_notes_url(limit: Option<u32>, offset: Option<u32>) -> String {
-        let mut url = format!("{}/notes", BASE_URL);
-        let mut query_params = Vec::new();
+    pub async fn query_records(
+        &self,
+        object: &str,
+        query: &crate::models::RecordQuery,
+    ) -> Result<crate::models::ListRecordsResponse, Box<dyn Error>> {
+        self.list_object_records(object, query).await
+    }
 
-        if let Some(limit) = limit {
-            query_params.push(format!("limit={}", limit));
+    pub async fn get_record(
+        &self,
+        object: &str,
+        record_id: &str,
+    ) -> Result<crate::models::GetRecordResponse, Box<dyn Error>> {
+        self.get_object_record(object, record_id).await
+    }
+
+    /// Queries the records of `object`, deserialized as `T`
+    async fn list_object_records<T: DeserializeOwned>(
+        &self,
+        object: &str,
+        query: &crate::models::RecordQuery,
+    ) -> Result<ListResponse<T>, Box<dyn Error>> {
+        let response = self
+            .send_query_with_retry(
+                self.client
+                    .post(format!(
+                        "{}/objects/{}/records/query",
+                        self.base_url, object
+                    ))
+                    .json(query),
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
         }
-        if let Some(offset) = offset {
-            query_params.push(format!("offset={}", offset));
+
+        Ok(response.json::<ListResponse<T>>().await?)
+    }
+
+    async fn get_object_record<T: DeserializeOwned>(
+        &self,
+        object: &str,
+        record_id: &str,
+    ) -> Result<GetResponse<T>, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.client.get(format!(
+                "{}/objects/{}/records/{}",
+                self.base_url, object, record_id
+            )))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
         }
 
-        if !query_params.is_empty() {
-            url.push('?');
-            url.push_str(&query_params.join("&"));
+        Ok(response.json::<GetResponse<T>>().await?)
+    }
+
+    pub async fn list_people(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<crate::models::ListPeopleResponse, Box<dyn Error>> {
+        let query = crate::models::RecordQuery {
+            limit,
+            offset,
+            ..Default::default()
+        };
+        self.list_object_records("people", &query).await
+    }
+
+    pub async fn get_person(
+        &self,
+        record_id: &str,
+    ) -> Result<crate::models::GetPersonResponse, Box<dyn Error>> {
+        self.get_object_record("people", record_id).await
+    }
+
+    pub async fn list_companies(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<crate::models::ListCompaniesResponse, Box<dyn Error>> {
+        let query = crate::models::RecordQuery {
+            limit,
+            offset,
+            ..Default::default()
+        };
+        self.list_object_records("companies", &query).await
+    }
+
+    pub async fn get_company(
+        &self,
+        record_id: &str,
+    ) -> Result<crate::models::GetCompanyResponse, Box<dyn Error>> {
+        self.get_object_record("companies", record_id).await
+    }
+
+    pub async fn list_tasks(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<crate::models::ListTasksResponse, Box<dyn Error>> {
+        let mut url = reqwest::Url::parse(&format!("{}/tasks", self.base_url))?;
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(limit) = limit {
+                query.append_pair("limit", &limit.to_string());
+            }
+            if let Some(offset) = offset {
+                query.append_pair("offset", &offset.to_string());
+            }
         }
+        let response = self.send_with_retry(self.client.get(url)).await?;
 
-        url
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response.json::<crate::models::ListTasksResponse>().await?)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub async fn get_task(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::models::GetTaskResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(
+                self.client
+                    .get(format!("{}/tasks/{}", self.base_url, task_id)),
+            )
+            .await?;
 
-    #[test]
-    fn test_client_creation() {
-        let client = AttioClient::new("test_token".to_string());
-        // Just verify it doesn't panic
-        assert!(std::mem::size_of_val(&client) > 0);
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response.json::<crate::models::GetTaskResponse>().await?)
     }
 
-    #[test]
-    fn test_build_notes_url_no_params() {
-        let url = AttioClient::build_notes_url(None, None);
-        assert_eq!(url, "https://api.attio.com/v2/notes");
+    /// The request `create_task` sends
+    pub fn create_task_request(&self, data: &crate::models::CreateTaskRequest) -> RequestBuilder {
+        self.client
+            .post(format!("{}/tasks", self.base_url))
+            .json(data)
     }
 
-    #[test]
-    fn test_build_notes_url_with_limit() {
-        let url = AttioClient::build_notes_url(Some(50), None);
-        assert_eq!(url, "https://api.attio.com/v2/notes?limit=50");
+    pub async fn create_task(
+        &self,
+        data: crate::models::CreateTaskRequest,
+    ) -> Result<crate::models::GetTaskResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.create_task_request(&data))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response.json::<crate::models::GetTaskResponse>().await?)
     }
 
-    #[test]
-    fn test_build_notes_url_with_offset() {
-        let url = AttioClient::build_notes_url(None, Some(100));
-        assert_eq!(url, "https://api.attio.com/v2/notes?offset=100");
+    /// The request `complete_task` sends
+    pub fn complete_task_request(&self, task_id: &str) -> RequestBuilder {
+        let request = crate::models::UpdateTaskRequest {
+            data: crate::models::UpdateTaskData {
+                is_completed: Some(true),
+            },
+        };
+        self.client
+            .patch(format!("{}/tasks/{}", self.base_url, task_id))
+            .json(&request)
     }
 
-    #[test]
-    fn test_build_notes_url_with_both_params() {
-        let url = AttioClient::build_notes_url(Some(25), Some(50));
-        assert_eq!(url, "https://api.attio.com/v2/notes?limit=25&offset=50");
+    /// Marks a task as completed
+    pub async fn complete_task(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::models::GetTaskResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.complete_task_request(task_id))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response.json::<crate::models::GetTaskResponse>().await?)
     }
 
-    #[test]
-    fn test_base_url_is_v2() {
-        assert_eq!(BASE_URL, "https://api.attio.com/v2");
+    /// Comment threads on a record, each with its comments
+    pub async fn list_comments(
+        &self,
+        object: &str,
+        record_id: &str,
+    ) -> Result<crate::models::ListThreadsResponse, Box<dyn Error>> {
+        let mut url = reqwest::Url::parse(&format!("{}/threads", self.base_url))?;
+        url.query_pairs_mut()
+            .append_pair("object", object)
+            .append_pair("record_id", record_id);
+        let response = self.send_with_retry(self.client.get(url)).await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response
+            .json::<crate::models::ListThreadsResponse>()
+            .await?)
+    }
+
+    /// The request `create_comment` sends
+    pub fn create_comment_request(
+        &self,
+        data: &crate::models::CreateCommentRequest,
+    ) -> RequestBuilder {
+        self.client
+            .post(format!("{}/comments", self.base_url))
+            .json(data)
+    }
+
+    pub async fn create_comment(
+        &self,
+        data: crate::models::CreateCommentRequest,
+    ) -> Result<crate::models::GetCommentResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.create_comment_request(&data))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(response.json::<crate::models::GetCommentResponse>().await?)
+    }
+
+    /// Checks whether a record exists; a 404 is `Ok(false)`, not an error
+    pub async fn record_exists(
+        &self,
+        object: &str,
+        record_id: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        match self
+            .get_object_record::<serde::de::IgnoredAny>(object, record_id)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<AttioError>(),
+                    Some(AttioError::Api { status, .. })
+                        if *status == reqwest::StatusCode::NOT_FOUND
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn list_objects(&self) -> Result<crate::models::ListObjectsResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.client.get(format!("{}/objects", self.base_url)))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let response_data = response
+            .json::<crate::models::ListObjectsResponse>()
+            .await?;
+        Ok(response_data)
+    }
+
+    pub async fn list_webhooks(
+        &self,
+    ) -> Result<crate::models::ListWebhooksResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.client.get(format!("{}/webhooks", self.base_url)))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let response_data = response
+            .json::<crate::models::ListWebhooksResponse>()
+            .await?;
+        Ok(response_data)
+    }
+
+    /// The request `create_webhook` sends
+    pub fn create_webhook_request(
+        &self,
+        data: &crate::models::CreateWebhookRequest,
+    ) -> RequestBuilder {
+        self.client
+            .post(format!("{}/webhooks", self.base_url))
+            .json(data)
+    }
+
+    pub async fn create_webhook(
+        &self,
+        data: crate::models::CreateWebhookRequest,
+    ) -> Result<crate::models::GetWebhookResponse, Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.create_webhook_request(&data))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let response_data = response.json::<crate::models::GetWebhookResponse>().await?;
+        Ok(response_data)
+    }
+
+    /// The request `delete_webhook` sends
+    pub fn delete_webhook_request(&self, webhook_id: &str) -> RequestBuilder {
+        self.client
+            .delete(format!("{}/webhooks/{}", self.base_url, webhook_id))
+    }
+
+    pub async fn delete_webhook(&self, webhook_id: &str) -> Result<(), Box<dyn Error>> {
+        let response = self
+            .send_with_retry(self.delete_webhook_request(webhook_id))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn build_notes_url(limit: Option<u32>, offset: Option<u32>) -> String {
+        let mut url = format!("{}/notes", DEFAULT_BASE_URL);
+        let mut query_params = Vec::new();
+
+        if let Some(limit) = limit {
+            query_params.push(format!("limit={}", limit));
+        }
+        if let Some(offset) = offset {
+            query_params.push(format!("offset={}", offset));
+        }
+
+        if !query_params.is_empty() {
+            url.push('?');
+            url.push_str(&query_params.join("&"));
+        }
+
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use wiremock::matchers::{body_json, header, header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_client_creation() {
+        let client = AttioClient::new("test_token".to_string()).unwrap();
+        // Just verify it doesn't panic
+        assert!(std::mem::size_of_val(&client) > 0);
+    }
+
+    #[test]
+    fn test_build_notes_url_no_params() {
+        let url = AttioClient::build_notes_url(None, None);
+        assert_eq!(url, "https://api.attio.com/v2/notes");
+    }
+
+    #[test]
+    fn test_build_notes_url_with_limit() {
+        let url = AttioClient::build_notes_url(Some(50), None);
+        assert_eq!(url, "https://api.attio.com/v2/notes?limit=50");
+    }
+
+    #[test]
+    fn test_build_notes_url_with_offset() {
+        let url = AttioClient::build_notes_url(None, Some(100));
+        assert_eq!(url, "https://api.attio.com/v2/notes?offset=100");
+    }
+
+    #[test]
+    fn test_build_notes_url_with_both_params() {
+        let url = AttioClient::build_notes_url(Some(25), Some(50));
+        assert_eq!(url, "https://api.attio.com/v2/notes?limit=25&offset=50");
+    }
+
+    #[test]
+    fn test_base_url_is_v2() {
+        assert_eq!(DEFAULT_BASE_URL, "https://api.attio.com/v2");
+    }
+
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(
+            normalize_base_url("https://proxy.example.com/attio/v2/").unwrap(),
+            "https://proxy.example.com/attio/v2"
+        );
+        assert_eq!(
+            normalize_base_url(" http://localhost:8080 ").unwrap(),
+            "http://localhost:8080"
+        );
+        assert!(normalize_base_url("api.attio.com/v2").is_err());
+        assert!(normalize_base_url("ftp://api.attio.com/v2").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requests_use_custom_base_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/proxy/v2/notes/note_1"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = AttioClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("{}/proxy/v2", server.uri()))
+            .with_retry_policy(fast_retry_policy(1));
+        let _ = client.get_note("note_1").await;
+
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            ..RetryPolicy::default()
+        }
+    }
+
+    async fn mock_client(server: &MockServer, policy: RetryPolicy) -> AttioClient {
+        AttioClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&server.uri())
+            .with_retry_policy(policy)
+    }
+
+    fn note_json(id: &str) -> serde_json::Value {
+        let note = Note::test(id)
+            .with_parent("people", "rec_1")
+            .with_title("Kickoff")
+            .with_plaintext("Agenda")
+            .with_markdown("# Agenda");
+        serde_json::to_value(note).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_notes_sends_bearer_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("limit", "10"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("note_1"), note_json("note_2")]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let notes = client.list_notes(Some(10), None).await.unwrap().data;
+
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[1].id.note_id, "note_2");
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.contains_key("user-agent"));
+    }
+
+    #[tokio::test]
+    async fn test_get_note_parses_note() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes/note_1"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": note_json("note_1")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let note = client.get_note("note_1").await.unwrap().data;
+
+        assert_eq!(note.title, "Kickoff");
+        assert_eq!(note.parent_record_id, "rec_1");
+        assert_eq!(note.content_markdown, "# Agenda");
+    }
+
+    #[tokio::test]
+    async fn test_trace_records_requests_and_responses() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/notes"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": note_json("n1") })),
+            )
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let trace_path = dir.path().join("trace.jsonl");
+
+        let client = mock_client(&server, fast_retry_policy(1))
+            .await
+            .with_trace(Tracer::create(&trace_path).unwrap());
+        let created = client
+            .create_note(crate::models::CreateNoteRequest {
+                data: crate::models::CreateNoteData {
+                    parent_object: "people".to_string(),
+                    parent_record_id: "rec_1".to_string(),
+                    title: "Kickoff".to_string(),
+                    format: "plaintext".to_string(),
+                    content: "Agenda".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+        // The response still reaches the caller after being recorded
+        assert_eq!(created.data.id.note_id, "n1");
+
+        let trace = std::fs::read_to_string(&trace_path).unwrap();
+        assert!(!trace.contains("test_token"));
+        let entry: serde_json::Value = serde_json::from_str(trace.trim()).unwrap();
+        assert_eq!(entry["request"]["method"], "POST");
+        assert_eq!(entry["request"]["headers"]["authorization"], "[REDACTED]");
+        assert_eq!(entry["request"]["url"], format!("{}/notes", server.uri()));
+        assert_eq!(entry["request"]["body"]["data"]["title"], "Kickoff");
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["body"]["data"]["id"]["note_id"], "n1");
+        assert!(entry["duration_ms"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn test_identify_returns_the_etag() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_json(serde_json::json!({ "active": true })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        match client.identify_if_none_match(None).await.unwrap() {
+            Conditional::Modified { value, etag } => {
+                assert!(value.active);
+                assert_eq!(etag.as_deref(), Some("\"v1\""));
+            }
+            Conditional::NotModified => panic!("expected a body"),
+        }
+        let request = &server.received_requests().await.unwrap()[0];
+        assert!(!request.headers.contains_key("if-none-match"));
+    }
+
+    #[tokio::test]
+    async fn test_identify_sends_if_none_match() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let result = client.identify_if_none_match(Some("\"v1\"")).await.unwrap();
+        assert!(matches!(result, Conditional::NotModified));
+    }
+
+    #[tokio::test]
+    async fn test_identify_parses_self() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "active": true,
+                "workspace_id": "ws_1",
+                "workspace_name": "Acme",
+                "workspace_slug": "acme",
+                "authorized_by_workspace_member_id": "member_1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let identity = client.identify().await.unwrap();
+
+        assert!(identity.active);
+        assert_eq!(identity.workspace_slug.as_deref(), Some("acme"));
+        assert_eq!(
+            identity.authorized_by_workspace_member_id.as_deref(),
+            Some("member_1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_parses_objects() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/objects"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": { "workspace_id": "ws_1", "object_id": "obj_1" },
+                    "api_slug": "deals",
+                    "singular_noun": "Deal",
+                    "plural_noun": "Deals"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let objects = client.list_objects().await.unwrap().data;
+
+        assert_eq!(objects[0].api_slug.as_deref(), Some("deals"));
+        assert_eq!(objects[0].id_label(), "obj_1");
+    }
+
+    #[tokio::test]
+    async fn test_injected_http_client_is_used() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .and(header("x-test", "injected"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"active": true})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert("x-test", header::HeaderValue::from_static("injected"));
+        let http = Client::builder().default_headers(headers).build().unwrap();
+        let client = AttioClient::from_http_client(http, Some(Duration::from_secs(5)))
+            .with_base_url(&server.uri());
+
+        assert!(client.identify().await.unwrap().active);
+    }
+
+    #[tokio::test]
+    async fn test_list_note_attachments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes/note_1/attachments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": { "workspace_id": "ws", "attachment_id": "att_1" },
+                    "filename": "deck.pdf",
+                    "content_type": "application/pdf",
+                    "size_bytes": 2048,
+                    "download_url": format!("{}/files/att_1", server.uri())
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let attachments = client.list_note_attachments("note_1").await.unwrap().data;
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].filename, "deck.pdf");
+        assert_eq!(attachments[0].size_bytes, Some(2048));
+    }
+
+    #[tokio::test]
+    async fn test_download_streams_body_without_token_off_api() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/files/att_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 100_000]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AttioClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("{}/v2", server.uri()))
+            .with_retry_policy(fast_retry_policy(1));
+        let mut out = Vec::new();
+        let written = client
+            .download(&format!("{}/files/att_1", server.uri()), &mut out)
+            .await
+            .unwrap();
+
+        assert_eq!(written, 100_000);
+        assert_eq!(out, vec![7u8; 100_000]);
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get("authorization").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_api_downloads_outlast_the_request_timeout() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Sends the body in chunks, together slower than the timeout but each
+        // well within it
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 4096];
+            let read = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 3\r\n\r\n")
+                .await
+                .unwrap();
+            for chunk in [b"a", b"b", b"c"] {
+                tokio::time::sleep(Duration::from_millis(400)).await;
+                socket.write_all(chunk).await.unwrap();
+            }
+            String::from_utf8_lossy(&request[..read]).to_lowercase()
+        });
+
+        let options = ClientOptions {
+            timeout: Some(Duration::from_secs(1)),
+            ..ClientOptions::default()
+        };
+        let client = AttioClient::with_options("test_token".to_string(), options)
+            .unwrap()
+            .with_base_url(&format!("http://{}", address))
+            .with_retry_policy(fast_retry_policy(1));
+        let mut out = Vec::new();
+        let url = format!("http://{}/files/att_1", address);
+        assert_eq!(client.download(&url, &mut out).await.unwrap(), 3);
+        assert_eq!(out, b"abc");
+        assert!(
+            server
+                .await
+                .unwrap()
+                .contains("authorization: bearer test_token")
+        );
+    }
+
+    #[test]
+    fn test_is_api_url_compares_hosts_not_prefixes() {
+        let client = AttioClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url("https://proxy.corp");
+        assert!(client.is_api_url("https://proxy.corp/files/att_1"));
+        assert!(client.is_api_url("https://proxy.corp:443/files/att_1"));
+        assert!(!client.is_api_url("https://proxy.corp.evil.com/files/att_1"));
+        assert!(!client.is_api_url("http://proxy.corp/files/att_1"));
+        assert!(!client.is_api_url("https://proxy.corp:8443/files/att_1"));
+
+        let client = client.with_base_url("https://api.attio.com/v2");
+        assert!(client.is_api_url("https://api.attio.com/v2/files/att_1"));
+        assert!(!client.is_api_url("https://api.attio.com/v2evil/files/att_1"));
+        assert!(!client.is_api_url("https://api.attio.com/files/att_1"));
+    }
+
+    #[tokio::test]
+    async fn test_download_sends_no_token_to_look_alike_hosts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/files/att_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 10]))
+            .mount(&server)
+            .await;
+        let address = server.address();
+
+        // The download URL starts with the base URL as a string, but is on
+        // another port
+        let client = AttioClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("http://{}", address.ip()))
+            .with_retry_policy(fast_retry_policy(1));
+        let mut out = Vec::new();
+        let url = format!("http://{}/files/att_1", address);
+        assert_eq!(client.download(&url, &mut out).await.unwrap(), 10);
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get("authorization").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_trace_summarizes_downloads() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/files/att_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 1000]))
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let trace_path = dir.path().join("trace.jsonl");
+
+        let client = mock_client(&server, fast_retry_policy(1))
+            .await
+            .with_trace(Tracer::create(&trace_path).unwrap());
+        let mut out = Vec::new();
+        let url = format!("{}/files/att_1?X-Amz-Signature=secret", server.uri());
+        assert_eq!(client.download(&url, &mut out).await.unwrap(), 1000);
+
+        let trace = std::fs::read_to_string(&trace_path).unwrap();
+        assert!(!trace.contains("secret"));
+        let entry: serde_json::Value = serde_json::from_str(trace.trim()).unwrap();
+        assert_eq!(
+            entry["request"]["url"],
+            format!("{}/files/att_1", server.uri())
+        );
+        assert_eq!(entry["response"]["status"], 200);
+        assert!(entry["response"].get("body").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_note_is_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(path("/notes/note_x"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(serde_json::json!({"status_code": 404, "message": "missing"})),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        for error in [
+            client.get_note("note_x").await.unwrap_err(),
+            client.delete_note("note_x").await.unwrap_err(),
+        ] {
+            assert_eq!(error.to_string(), "Note note_x not found.");
+            let api_error = error.downcast_ref::<AttioError>().unwrap();
+            assert!(api_error.body().contains("missing"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decodes_gzip_responses() {
+        let body = serde_json::to_vec(&serde_json::json!({"data": []})).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&body).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(header_exists("accept-encoding"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Encoding", "gzip")
+                    .insert_header("Content-Type", "application/json")
+                    .set_body_bytes(gzipped),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let response = client.list_notes(None, None).await.unwrap();
+
+        assert!(response.data.is_empty());
+        let requests = server.received_requests().await.unwrap();
+        let accept = requests[0].headers["accept-encoding"].to_str().unwrap();
+        assert!(accept.contains("gzip"));
+        assert!(accept.contains("br"));
+    }
+
+    #[tokio::test]
+    async fn test_decodes_deflate_responses() {
+        let body = serde_json::to_vec(&serde_json::json!({"data": []})).unwrap();
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&body).unwrap();
+        let deflated = encoder.finish().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Encoding", "deflate")
+                    .insert_header("Content-Type", "application/json")
+                    .set_body_bytes(deflated),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let response = client.list_notes(Some(10), Some(0)).await.unwrap();
+
+        assert!(response.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_compression_can_be_disabled() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"active": true})),
+            )
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions {
+            compression: false,
+            ..ClientOptions::default()
+        };
+        let client = AttioClient::with_options("test_token".to_string(), options)
+            .unwrap()
+            .with_base_url(&server.uri());
+        client.identify().await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get("accept-encoding").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_reports_configured_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(3)))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions {
+            timeout: Some(Duration::from_secs(1)),
+            ..ClientOptions::default()
+        };
+        let client = AttioClient::with_options("test_token".to_string(), options)
+            .unwrap()
+            .with_base_url(&server.uri())
+            .with_retry_policy(fast_retry_policy(1));
+        let error = client.identify().await.unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<AttioError>(),
+            Some(AttioError::Timeout { secs: 1 })
+        ));
+        assert!(error.to_string().contains("timed out after 1 seconds"));
+    }
+
+    #[tokio::test]
+    async fn test_no_timeout_waits_for_slow_responses() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"active": true}))
+                    .set_delay(Duration::from_millis(1500)),
+            )
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions {
+            timeout: None,
+            ..ClientOptions::default()
+        };
+        let client = AttioClient::with_options("test_token".to_string(), options)
+            .unwrap()
+            .with_base_url(&server.uri());
+        assert!(client.identify().await.unwrap().active);
+    }
+
+    #[tokio::test]
+    async fn test_note_parent_narrows_every_listing() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("parent_object", "deals"))
+            .and(query_param("parent_record_id", "rec 1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": []})))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1))
+            .await
+            .with_note_parent("deals", "rec 1");
+        client.list_notes(Some(10), Some(20)).await.unwrap();
+        client.list_notes_cursor(Some("next"), 10).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_returns_secret() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/webhooks"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": { "workspace_id": "ws", "webhook_id": "wh_1" },
+                    "target_url": "https://example.com/hook",
+                    "subscriptions": [{ "event_type": "note.created", "filter": null }],
+                    "status": "active",
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "secret": "shh"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let request = crate::models::CreateWebhookRequest {
+            data: crate::models::CreateWebhookData {
+                target_url: "https://example.com/hook".to_string(),
+                subscriptions: vec![crate::models::WebhookSubscription {
+                    event_type: "note.created".to_string(),
+                    filter: None,
+                }],
+            },
+        };
+        let webhook = client.create_webhook(request).await.unwrap().data;
+
+        assert_eq!(webhook.id.webhook_id, "wh_1");
+        assert_eq!(webhook.secret.as_deref(), Some("shh"));
+    }
+
+    #[tokio::test]
+    async fn test_list_people_sends_paging_in_query_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/objects/people/records/query"))
+            .and(body_json(serde_json::json!({ "limit": 10, "offset": 20 })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": { "workspace_id": "ws", "object_id": "obj", "record_id": "rec_1" },
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "values": { "name": [{ "full_name": "Jane Doe" }] }
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let people = client.list_people(Some(10), Some(20)).await.unwrap().data;
+
+        assert_eq!(people.len(), 1);
+        assert_eq!(people[0].name(), Some("Jane Doe"));
+    }
+
+    #[tokio::test]
+    async fn test_get_company_uses_companies_path() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/objects/companies/records/rec_9"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": { "workspace_id": "ws", "object_id": "obj", "record_id": "rec_9" },
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "values": { "domains": [{ "domain": "acme.com" }] }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let company = client.get_company("rec_9").await.unwrap().data;
+
+        assert_eq!(company.domain(), Some("acme.com"));
+    }
+
+    fn task_json(id: &str, completed: bool) -> serde_json::Value {
+        serde_json::json!({
+            "id": { "workspace_id": "ws", "task_id": id },
+            "content_plaintext": "Follow up",
+            "deadline_at": null,
+            "is_completed": completed,
+            "linked_records": []
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_sends_paging_in_query() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tasks"))
+            .and(query_param("limit", "5"))
+            .and(query_param("offset", "10"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": [task_json("task_1", false)] })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let tasks = client.list_tasks(Some(5), Some(10)).await.unwrap().data;
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id.task_id, "task_1");
+    }
+
+    #[tokio::test]
+    async fn test_complete_task_patches_is_completed() {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/tasks/task_1"))
+            .and(body_json(
+                serde_json::json!({ "data": { "is_completed": true } }),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": task_json("task_1", true) })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let task = client.complete_task("task_1").await.unwrap().data;
+
+        assert!(task.is_completed);
+    }
+
+    fn comment_json(id: &str, thread_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": { "workspace_id": "ws", "comment_id": id },
+            "thread_id": thread_id,
+            "content_plaintext": "Nice",
+            "author": { "type": "workspace-member", "id": "member_1" },
+            "created_at": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_comments_filters_threads_by_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/threads"))
+            .and(query_param("object", "people"))
+            .and(query_param("record_id", "rec_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": { "workspace_id": "ws", "thread_id": "thread_1" },
+                    "comments": [comment_json("comment_1", "thread_1")]
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let threads = client.list_comments("people", "rec_1").await.unwrap().data;
+
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].comments[0].id.comment_id, "comment_1");
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_replies_in_thread() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/comments"))
+            .and(body_json(serde_json::json!({
+                "data": {
+                    "format": "plaintext",
+                    "content": "Nice",
+                    "author": { "type": "workspace-member", "id": "member_1" },
+                    "thread_id": "thread_1"
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "data": comment_json("comment_2", "thread_1") }),
+            ))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let request = crate::models::CreateCommentRequest {
+            data: crate::models::CreateCommentData {
+                format: "plaintext".to_string(),
+                content: "Nice".to_string(),
+                author: crate::models::Actor::workspace_member("member_1".to_string()),
+                thread_id: Some("thread_1".to_string()),
+                record: None,
+            },
+        };
+        let comment = client.create_comment(request).await.unwrap().data;
+
+        assert_eq!(comment.id.comment_id, "comment_2");
+    }
+
+    #[test]
+    fn test_dry_run_shows_method_url_and_body() {
+        let client = AttioClient::new("secret_token".to_string()).unwrap();
+        let request = client.complete_task_request("task_1");
+
+        let prepared = DryRunRequest::prepare(request).unwrap();
+
+        assert_eq!(prepared.method, "PATCH");
+        assert_eq!(prepared.url, "https://api.attio.com/v2/tasks/task_1");
+        assert_eq!(
+            prepared.body,
+            Some(serde_json::json!({ "data": { "is_completed": true } }))
+        );
+        let shown = prepared.to_string();
+        assert!(shown.starts_with("PATCH https://api.attio.com/v2/tasks/task_1\n{"));
+        assert!(!shown.contains("secret_token"));
+    }
+
+    #[test]
+    fn test_token_with_newline_is_an_error() {
+        let result = AttioClient::new("secret\ntoken".to_string());
+
+        let error = result.err().expect("a newline can't go in a header");
+        assert!(matches!(error, AttioError::InvalidToken));
+        assert!(!error.to_string().contains("secret"));
+    }
+
+    #[test]
+    fn test_dry_run_without_body() {
+        let client = AttioClient::new("token".to_string()).unwrap();
+        let prepared = DryRunRequest::prepare(client.delete_note_request("note_1")).unwrap();
+
+        assert_eq!(
+            prepared.to_string(),
+            "DELETE https://api.attio.com/v2/notes/note_1"
+        );
+        assert_eq!(
+            serde_json::to_value(&prepared).unwrap(),
+            serde_json::json!({
+                "method": "DELETE",
+                "url": "https://api.attio.com/v2/notes/note_1"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preparing_a_request_sends_nothing() {
+        let server = MockServer::start().await;
+        let client = AttioClient::new("token".to_string())
+            .unwrap()
+            .with_base_url(&server.uri());
+
+        DryRunRequest::prepare(client.delete_webhook_request("wh_1")).unwrap();
+
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_page_token_offset_paging() {
+        let first = PageToken::Offset(0);
+        assert_eq!(first.advance(50, 50, None), Some(PageToken::Offset(50)));
+        // A short page is the last one
+        assert_eq!(PageToken::Offset(50).advance(12, 50, None), None);
+    }
+
+    #[test]
+    fn test_page_token_prefers_cursor() {
+        let first = PageToken::Offset(0);
+        assert_eq!(
+            first.advance(50, 50, Some("c1".to_string())),
+            Some(PageToken::Cursor("c1".to_string()))
+        );
+        // Even a short page continues while the API hands out a cursor
+        assert_eq!(
+            PageToken::Cursor("c1".to_string()).advance(3, 50, Some("c2".to_string())),
+            Some(PageToken::Cursor("c2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_page_token_cursor_exhausted() {
+        // A full page without a cursor ends cursor paging rather than falling back to offsets
+        assert_eq!(
+            PageToken::Cursor("c1".to_string()).advance(50, 50, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_page_token_stops_on_stuck_pages() {
+        // An empty page ends paging even when the API hands out another cursor
+        assert_eq!(
+            PageToken::Offset(0).advance(0, 50, Some("c1".to_string())),
+            None
+        );
+        // So does a cursor that points back at the page just fetched
+        assert_eq!(
+            PageToken::Cursor("c1".to_string()).advance(50, 50, Some("c1".to_string())),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notes_stream_ends_when_the_cursor_repeats() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("a")],
+                "next_cursor": "same"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let notes: Vec<Note> = client.notes_stream(1).try_collect().await.unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_notes_follows_cursor_until_exhausted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("cursor", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("b")]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("a")],
+                "next_cursor": "page2"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let mut progress = Vec::new();
+        let notes = client
+            .list_all_notes(1, |fetched| progress.push(fetched))
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = notes.iter().map(|n| n.id.note_id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(progress, [1, 2]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_notes_stream_reads_every_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("a"), note_json("b")]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("c")]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let notes: Vec<Note> = client.notes_stream(2).try_collect().await.unwrap();
+
+        let ids: Vec<&str> = notes.iter().map(|n| n.id.note_id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_notes_stream_fetches_lazily_and_stops_on_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("a")]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "1"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let mut stream = std::pin::pin!(client.notes_stream(1));
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.id.note_id, "a");
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+
+    /// Mounts an offset page of `ids`, answered after `delay`
+    async fn mount_offset_page(server: &MockServer, offset: u32, ids: &[&str], delay: Duration) {
+        let data: Vec<_> = ids.iter().map(|id| note_json(id)).collect();
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", offset.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": data }))
+                    .set_delay(delay),
+            )
+            .mount(server)
+            .await;
+    }
+
+    fn requested_offsets(requests: &[wiremock::Request]) -> Vec<String> {
+        let mut offsets: Vec<String> = requests
+            .iter()
+            .filter_map(|request| {
+                request
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == "offset")
+                    .map(|(_, value)| value.into_owned())
+            })
+            .collect();
+        offsets.sort_by_key(|offset| offset.parse::<u32>().unwrap());
+        offsets
+    }
+
+    #[tokio::test]
+    async fn test_note_pages_fetch_offset_pages_concurrently_in_order() {
+        let server = MockServer::start().await;
+        let slow = Duration::from_millis(400);
+        mount_offset_page(&server, 0, &["a", "b"], Duration::ZERO).await;
+        mount_offset_page(&server, 2, &["c", "d"], slow).await;
+        mount_offset_page(&server, 4, &["e", "f"], slow).await;
+        mount_offset_page(&server, 6, &["g"], slow).await;
+        mount_offset_page(&server, 8, &[], slow).await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let started = std::time::Instant::now();
+        let pages: Vec<NotesPage> = client
+            .note_pages(PageToken::Offset(0), 2, 3, Duration::ZERO)
+            .try_collect()
+            .await
+            .unwrap();
+
+        // One round of slow pages, not three in a row
+        assert!(started.elapsed() < slow * 2, "{:?}", started.elapsed());
+        let ids: Vec<&str> = pages
+            .iter()
+            .flat_map(|page| &page.notes)
+            .map(|note| note.id.note_id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "b", "c", "d", "e", "f", "g"]);
+        assert_eq!(pages.last().unwrap().next, None);
+        // The short page ends the fetch; only the requests already in flight
+        // (at most one fewer than the concurrency) went past it
+        let offsets = requested_offsets(&server.received_requests().await.unwrap());
+        assert_eq!(offsets[..4], ["0", "2", "4", "6"]);
+        assert!(offsets.len() <= 4 + 2, "{:?}", offsets);
+    }
+
+    #[tokio::test]
+    async fn test_note_pages_stop_requesting_at_the_total() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("a"), note_json("b")],
+                "total_count": 4
+            })))
+            .mount(&server)
+            .await;
+        mount_offset_page(&server, 2, &["c", "d"], Duration::ZERO).await;
+        mount_offset_page(&server, 4, &[], Duration::ZERO).await;
+        mount_offset_page(&server, 6, &[], Duration::ZERO).await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let notes: usize = client
+            .note_pages(PageToken::Offset(0), 2, 4, Duration::ZERO)
+            .map_ok(|page| page.notes.len())
+            .try_fold(0, |sum, count| async move { Ok(sum + count) })
+            .await
+            .unwrap();
+
+        assert_eq!(notes, 4);
+        // Offset 4 is the check for notes created since the total was counted
+        let offsets = requested_offsets(&server.received_requests().await.unwrap());
+        assert_eq!(offsets, ["0", "2", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_note_pages_end_at_the_first_error_with_a_resume_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [note_json("a")],
+                "total_count": 2
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", "1"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let mut pages =
+            std::pin::pin!(client.note_pages(PageToken::Offset(0), 1, 1, Duration::ZERO));
+        let first = pages.next().await.unwrap().unwrap();
+        assert_eq!(first.notes.len(), 1);
+        assert_eq!(first.total_count, Some(2));
+        assert_eq!(first.next, Some(PageToken::Offset(1)));
+
+        assert!(pages.next().await.unwrap().is_err());
+        assert!(pages.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_record_exists() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/objects/people/records/found"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": { "workspace_id": "ws", "object_id": "obj", "record_id": "found" },
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "values": {}
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/objects/people/records/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/objects/people/records/forbidden"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+
+        assert!(client.record_exists("people", "found").await.unwrap());
+        assert!(!client.record_exists("people", "missing").await.unwrap());
+        assert!(client.record_exists("people", "forbidden").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_page_becomes_unavailable_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_string("<html>Down for maintenance</html>"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(2)).await;
+        let error = client.list_notes(None, None).await.unwrap_err();
+
+        // Retries are exhausted first
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        let api_error = error.downcast_ref::<AttioError>().unwrap();
+        assert!(api_error.is_unavailable());
+        assert!(api_error.body().contains("maintenance"));
+        assert!(!error.to_string().contains("<html>"));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_stops_requests_during_outage() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1))
+            .await
+            .with_circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)));
+        for _ in 0..2 {
+            client.list_notes(None, None).await.unwrap_err();
+        }
+        let error = client.list_notes(None, None).await.unwrap_err();
+
+        // The third call never reaches the server
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        assert!(matches!(
+            error.downcast_ref::<AttioError>(),
+            Some(AttioError::CircuitOpen { failures: 2, .. })
+        ));
+        assert!(error.to_string().contains("appears unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limited_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": []})))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(4)).await;
+        let response = client.list_notes(None, None).await.unwrap();
+
+        assert!(response.data.is_empty());
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/self"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(3)).await;
+        let result = client.identify().await;
+
+        assert!(result.unwrap_err().to_string().contains("429"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_rate_limit_keeps_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "12"))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(1)).await;
+        let error = client.list_notes(None, None).await.unwrap_err();
+
+        let api_error = error.downcast_ref::<AttioError>().unwrap();
+        assert!(api_error.is_rate_limited());
+        assert_eq!(api_error.retry_after(), Some(Duration::from_secs(12)));
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(4)).await;
+        assert!(client.get_note("missing").await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_header_sets_delay() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/notes/note_1"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/notes/note_1"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(5),
+            ..fast_retry_policy(2)
+        };
+        let client = mock_client(&server, policy).await;
+        let started = std::time::Instant::now();
+        client.delete_note("note_1").await.unwrap();
+
+        // Retry-After (1s) wins over the 10ms computed backoff
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_creates_on_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(4)).await;
+        let request = crate::models::CreateNoteRequest {
+            data: crate::models::CreateNoteData {
+                parent_object: "people".to_string(),
+                parent_record_id: "rec_1".to_string(),
+                title: "Kickoff".to_string(),
+                format: "plaintext".to_string(),
+                content: "Agenda".to_string(),
+            },
+        };
+        assert!(client.create_note(request).await.is_err());
+        // The server may have created the note before failing, so a retry
+        // could create it twice
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limited_creates() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/notes"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": note_json("n1") })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(2)).await;
+        let request = crate::models::CreateNoteRequest {
+            data: crate::models::CreateNoteData {
+                parent_object: "people".to_string(),
+                parent_record_id: "rec_1".to_string(),
+                title: "Kickoff".to_string(),
+                format: "plaintext".to_string(),
+                content: "Agenda".to_string(),
+            },
+        };
+        client.create_note(request).await.unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retries_post_with_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/objects/people/records/query"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/objects/people/records/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": []})))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server, fast_retry_policy(2)).await;
+        let query = crate::models::RecordQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        client.query_records("people", &query).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].body, br#"{"limit":1}"#);
     }
 }
@@ -0,0 +1,127 @@
+use regex::Regex;
+use std::error::Error;
+use std::sync::LazyLock;
+
+static UUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+        .unwrap()
+});
+
+/// Matches an Attio record URL, e.g. `https://app.attio.com/acme/person/<uuid>/overview`
+static RECORD_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"app\.attio\.com/[^/]+/([a-z0-9_-]+)/([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})",
+    )
+    .unwrap()
+});
+
+/// A record reference pulled out of clipboard text
+#[derive(Debug, PartialEq)]
+pub struct ClipboardRecord {
+    pub record_id: String,
+    /// The parent object, when it could be inferred from an Attio URL
+    pub object: Option<String>,
+}
+
+/// Reads text from the system clipboard
+pub fn read_text() -> Result<String, Box<dyn Error>> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("Clipboard is unavailable: {}", e))?;
+    clipboard
+        .get_text()
+        .map_err(|e| format!("Could not read text from the clipboard: {}", e).into())
+}
+
+/// Writes text to the system clipboard.
+///
+/// The clipboard handle is kept open between copies: on X11 the copied text
+/// is only available while the handle that set it is alive.
+#[derive(Default)]
+pub struct ClipboardWriter {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl ClipboardWriter {
+    pub fn copy(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+        let clipboard = match &mut self.clipboard {
+            Some(clipboard) => clipboard,
+            None => self.clipboard.insert(
+                arboard::Clipboard::new()
+                    .map_err(|e| format!("Clipboard is unavailable: {}", e))?,
+            ),
+        };
+        clipboard
+            .set_text(text)
+            .map_err(|e| format!("Could not copy to the clipboard: {}", e).into())
+    }
+}
+
+/// Extracts a record ID from an Attio record URL or a bare UUID
+pub fn extract_record(text: &str) -> Option<ClipboardRecord> {
+    let text = text.trim();
+
+    if let Some(captures) = RECORD_URL_RE.captures(text) {
+        let object = match &captures[1] {
+            "person" => "people".to_string(),
+            "company" => "companies".to_string(),
+            other => other.to_string(),
+        };
+        return Some(ClipboardRecord {
+            record_id: captures[2].to_lowercase(),
+            object: Some(object),
+        });
+    }
+
+    UUID_RE
+        .find(text)
+        .filter(|m| m.as_str().len() == text.len())
+        .map(|m| ClipboardRecord {
+            record_id: m.as_str().to_lowercase(),
+            object: None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "12345678-1234-1234-1234-123456789abc";
+
+    #[test]
+    fn test_extract_bare_uuid() {
+        let record = extract_record(&format!("  {}\n", ID)).unwrap();
+        assert_eq!(record.record_id, ID);
+        assert_eq!(record.object, None);
+    }
+
+    #[test]
+    fn test_extract_from_person_url() {
+        let url = format!("https://app.attio.com/acme/person/{}/overview", ID);
+        let record = extract_record(&url).unwrap();
+        assert_eq!(record.record_id, ID);
+        assert_eq!(record.object, Some("people".to_string()));
+    }
+
+    #[test]
+    fn test_extract_from_company_url() {
+        let url = format!("https://app.attio.com/acme/company/{}", ID.to_uppercase());
+        let record = extract_record(&url).unwrap();
+        assert_eq!(record.record_id, ID);
+        assert_eq!(record.object, Some("companies".to_string()));
+    }
+
+    #[test]
+    fn test_extract_from_custom_object_url() {
+        let url = format!("https://app.attio.com/acme/deals/{}/activity", ID);
+        let record = extract_record(&url).unwrap();
+        assert_eq!(record.object, Some("deals".to_string()));
+    }
+
+    #[test]
+    fn test_extract_rejects_unrecognized_text() {
+        assert_eq!(extract_record("Jane Doe"), None);
+        assert_eq!(extract_record(""), None);
+        // A UUID embedded in arbitrary text is too ambiguous to trust
+        assert_eq!(extract_record(&format!("see {} please", ID)), None);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::models::Note;
+use crossterm::style::Stylize;
+use similar::TextDiff;
+
+/// Which part of a note `notes diff` compares
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum DiffField {
+    Title,
+    Content,
+}
+
+impl DiffField {
+    fn extract(self, note: &Note) -> &str {
+        match self {
+            Self::Title => &note.title,
+            Self::Content => &note.content_markdown,
+        }
+    }
+}
+
+/// Unified diff of `field` between two notes; empty when they match
+pub fn diff_notes(old: &Note, new: &Note, field: DiffField, color: bool) -> String {
+    unified_diff(
+        field.extract(old),
+        field.extract(new),
+        &old.id.note_id,
+        &new.id.note_id,
+        color,
+    )
+}
+
+/// What `notes diff --output json` prints: both note IDs and the uncolored
+/// diff, empty when the notes match
+pub fn diff_json(old: &Note, new: &Note, field: DiffField) -> serde_json::Value {
+    let diff = diff_notes(old, new, field, false);
+    serde_json::json!({
+        "old": old.id.note_id,
+        "new": new.id.note_id,
+        "identical": diff.is_empty(),
+        "diff": diff,
+    })
+}
+
+/// Line-based unified diff of two texts, optionally colored for a terminal
+fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str, color: bool) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let text = diff
+        .unified_diff()
+        .context_radius(3)
+        .missing_newline_hint(false)
+        .header(old_label, new_label)
+        .to_string();
+
+    if !color {
+        return text;
+    }
+
+    text.lines()
+        .map(|line| {
+            if line.starts_with("+++") || line.starts_with("---") {
+                line.bold().to_string()
+            } else if line.starts_with("@@") {
+                line.cyan().to_string()
+            } else if line.starts_with('+') {
+                line.green().to_string()
+            } else if line.starts_with('-') {
+                line.red().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, title: &str, content: &str) -> Note {
+        Note::test(id).with_title(title).with_content(content)
+    }
+
+    #[test]
+    fn test_diff_content() {
+        let old = note("a", "Title", "one\ntwo\nthree\n");
+        let new = note("b", "Title", "one\n2\nthree\n");
+
+        let diff = diff_notes(&old, &new, DiffField::Content, false);
+
+        assert!(diff.starts_with("--- a\n+++ b\n"));
+        assert!(diff.contains("\n-two\n"));
+        assert!(diff.contains("\n+2\n"));
+        assert!(diff.contains("\n one\n"));
+    }
+
+    #[test]
+    fn test_diff_title() {
+        let old = note("a", "Weekly sync", "same");
+        let new = note("b", "Weekly sync (copy)", "same");
+
+        let diff = diff_notes(&old, &new, DiffField::Title, false);
+
+        assert!(diff.contains("-Weekly sync\n"));
+        assert!(diff.contains("+Weekly sync (copy)\n"));
+    }
+
+    #[test]
+    fn test_identical_notes_have_empty_diff() {
+        let old = note("a", "Title", "body\n");
+        let new = note("b", "Title", "body\n");
+
+        assert!(diff_notes(&old, &new, DiffField::Content, false).is_empty());
+        assert!(diff_notes(&old, &new, DiffField::Content, true).is_empty());
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let old = note("a", "Title", "old\n");
+        let new = note("b", "Title", "new\n");
+
+        let json = diff_json(&old, &new, DiffField::Content);
+        assert_eq!(json["old"], "a");
+        assert_eq!(json["new"], "b");
+        assert_eq!(json["identical"], false);
+        assert_eq!(json["diff"], "--- a\n+++ b\n@@ -1 +1 @@\n-old\n+new\n");
+
+        let same = diff_json(&old, &old, DiffField::Content);
+        assert_eq!(same["identical"], true);
+        assert_eq!(same["diff"], "");
+    }
+
+    #[test]
+    fn test_colored_diff_uses_ansi_codes() {
+        let old = note("a", "Title", "old\n");
+        let new = note("b", "Title", "new\n");
+
+        let diff = diff_notes(&old, &new, DiffField::Content, true);
+
+        assert!(diff.contains("\u{1b}["));
+        assert!(diff.contains("old"));
+    }
+}
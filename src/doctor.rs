@@ -0,0 +1,209 @@
+use crate::models::Config;
+use crate::symbols::SymbolSet;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+use std::time::Duration;
+
+/// Outcome of one `doctor` check
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    /// Not broken, but worth knowing about
+    Warn,
+    Fail,
+}
+
+/// One line of the `doctor` report
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    /// How to fix a warning or failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl Check {
+    pub fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    pub fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Checks that the config file exists and parses. A missing file is only a
+/// warning, since the token can come from the environment instead.
+pub fn check_config_file(path: &Path) -> Check {
+    const NAME: &str = "Config file";
+    if !path.exists() {
+        return Check::warn(
+            NAME,
+            format!("{} does not exist", path.display()),
+            "Run `attio auth <token>` to create it (not needed if the token is in the environment)",
+        );
+    }
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("Can't read {}: {}", path.display(), e),
+                "Check the file's permissions",
+            );
+        }
+    };
+    match serde_json::from_str::<Config>(&content) {
+        Ok(_) => Check::pass(NAME, path.display().to_string()),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{} is not valid: {}", path.display(), e),
+            "Fix it with `attio config edit`, or remove it and run `attio auth <token>`",
+        ),
+    }
+}
+
+/// Checks that files can be created in `dir` (creating it if needed)
+pub fn check_writable(dir: &Path) -> Check {
+    const NAME: &str = "Config directory";
+    let probe = dir.join(format!(".attio-doctor-{}", std::process::id()));
+    let result = fs::create_dir_all(dir).and_then(|_| {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+            .map(drop)
+    });
+    match result {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            Check::pass(NAME, format!("{} is writable", dir.display()))
+        }
+        Err(e) => Check::fail(
+            NAME,
+            format!("Can't write to {}: {}", dir.display(), e),
+            "Fix the directory's permissions, or point ATTIO_CONFIG_DIR somewhere writable",
+        ),
+    }
+}
+
+/// Checks that `base_url` answers at all. Any HTTP response counts, since
+/// this request is sent without a token.
+pub async fn check_reachable(base_url: &str, timeout: Duration) -> Check {
+    const NAME: &str = "Network";
+    let hint = "Check your connection and proxy settings, and `attio config get base-url`";
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => return Check::fail(NAME, format!("Could not set up HTTP: {}", e), hint),
+    };
+    match client.get(base_url).send().await {
+        Ok(response) => Check::pass(
+            NAME,
+            format!("{} answered ({})", base_url, response.status()),
+        ),
+        Err(e) if e.is_timeout() => Check::fail(
+            NAME,
+            format!(
+                "{} did not answer within {} seconds",
+                base_url,
+                timeout.as_secs()
+            ),
+            hint,
+        ),
+        Err(e) => Check::fail(NAME, format!("Can't reach {}: {}", base_url, e), hint),
+    }
+}
+
+/// Prints each check with its marker, and the hint under anything not passing
+pub fn print(checks: &[Check], symbols: SymbolSet) {
+    for check in checks {
+        let marker = match check.status {
+            Status::Pass => symbols.success(),
+            Status::Warn => symbols.warning(),
+            Status::Fail => symbols.failure(),
+        };
+        println!("{} {}: {}", marker, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("    {}", hint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_config_file_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        assert_eq!(check_config_file(&path).status, Status::Warn);
+
+        fs::write(&path, r#"{"token": "abc"}"#).unwrap();
+        assert_eq!(check_config_file(&path).status, Status::Pass);
+
+        fs::write(&path, r#"{"token": "abc","#).unwrap();
+        let check = check_config_file(&path);
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.detail.contains("is not valid"));
+    }
+
+    #[test]
+    fn test_writable_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("attio");
+        assert_eq!(check_writable(&nested).status, Status::Pass);
+        // The probe file is cleaned up
+        assert_eq!(fs::read_dir(&nested).unwrap().count(), 0);
+
+        // A path below a regular file can't be created
+        let file = dir.path().join("file");
+        fs::write(&file, "").unwrap();
+        assert_eq!(check_writable(&file.join("attio")).status, Status::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_reachable_accepts_any_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let check = check_reachable(&server.uri(), Duration::from_secs(5)).await;
+        assert_eq!(check.status, Status::Pass);
+        assert!(check.detail.contains("404"));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_host_fails_with_hint() {
+        let check = check_reachable("http://127.0.0.1:1", Duration::from_secs(5)).await;
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.hint.is_some());
+    }
+}
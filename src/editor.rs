@@ -0,0 +1,56 @@
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use std::process::Command;
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set
+const FALLBACK_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// Splits the editor setting into a program and its arguments, e.g.
+/// `code --wait` becomes `("code", ["--wait"])`
+fn editor_command(visual: Option<&str>, editor: Option<&str>) -> (String, Vec<String>) {
+    let setting = [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .unwrap_or(FALLBACK_EDITOR);
+    let mut parts = setting.split_whitespace().map(str::to_string);
+    let program = parts.next().unwrap_or_else(|| FALLBACK_EDITOR.to_string());
+    (program, parts.collect())
+}
+
+/// Opens `path` in the user's editor and waits for it to exit
+pub fn open_in_editor(path: &Path) -> Result<(), Box<dyn Error>> {
+    let (program, args) = editor_command(
+        env::var("VISUAL").ok().as_deref(),
+        env::var("EDITOR").ok().as_deref(),
+    );
+    let status = Command::new(&program)
+        .args(&args)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("Failed to launch editor '{}': {}", program, e))?;
+    if !status.success() {
+        return Err(format!("Editor '{}' exited with {}", program, status).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visual_wins_over_editor() {
+        let (program, args) = editor_command(Some("code --wait"), Some("nano"));
+        assert_eq!(program, "code");
+        assert_eq!(args, vec!["--wait"]);
+    }
+
+    #[test]
+    fn test_blank_values_fall_through() {
+        assert_eq!(editor_command(Some("  "), Some("nano")).0, "nano");
+        assert_eq!(editor_command(None, None).0, FALLBACK_EDITOR);
+    }
+}
@@ -0,0 +1,414 @@
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// Errors returned by the Attio API
+#[derive(Debug)]
+pub enum AttioError {
+    /// The API answered with a non-success status (after any retries)
+    Api {
+        status: StatusCode,
+        body: String,
+        /// Wait requested by the server's `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+    /// The requested resource doesn't exist (404), e.g. `resource` "Note abc"
+    NotFound { resource: String, body: String },
+    /// No response arrived within the configured timeout
+    Timeout { secs: u64 },
+    /// Requests were stopped after `failures` failed in a row; the next one
+    /// is allowed in `retry_in`
+    CircuitOpen { failures: u32, retry_in: Duration },
+    /// The token can't be sent in an HTTP header, e.g. it contains a newline
+    InvalidToken,
+    /// No token in the config file, keyring or `env_var`
+    NotAuthenticated { env_var: String },
+    /// The HTTP client could not be set up, e.g. no TLS backend
+    ClientSetup(reqwest::Error),
+    /// A config key, value or file is invalid, or the OS keyring can't be
+    /// reached
+    Config(String),
+}
+
+impl AttioError {
+    /// True for 5xx responses, e.g. a 503 maintenance page
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            Self::Api { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// True when the API rejected the token (401)
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Self::Api { status, .. } if *status == StatusCode::UNAUTHORIZED)
+    }
+
+    /// True for 429 responses, after the client's own retries ran out
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, Self::Api { status, .. } if *status == StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// How long the server asked us to wait before trying again
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::Api { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Attio's explanation of an invalid request, with the message of each
+    /// validation error and the path it applies to
+    pub fn validation_message(&self) -> Option<String> {
+        let Self::Api { status, body, .. } = self else {
+            return None;
+        };
+        if *status != StatusCode::BAD_REQUEST {
+            return None;
+        }
+        let body: Value = serde_json::from_str(body).ok()?;
+        if body.get("type").and_then(Value::as_str) != Some("invalid_request_error") {
+            return None;
+        }
+        let mut message = body.get("message")?.as_str()?.to_string();
+        let errors = body.get("validation_errors").and_then(Value::as_array);
+        for error in errors.into_iter().flatten() {
+            let Some(detail) = error.get("message").and_then(Value::as_str) else {
+                continue;
+            };
+            let path: Vec<String> = error
+                .get("path")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|segment| match segment {
+                    Value::String(name) => name.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            if path.is_empty() {
+                message.push_str(&format!("\n  {}", detail));
+            } else {
+                message.push_str(&format!("\n  {}: {}", path.join("."), detail));
+            }
+        }
+        Some(message)
+    }
+
+    /// True when the message replaces the response body, which is then only
+    /// shown with `--verbose`
+    pub fn hides_body(&self) -> bool {
+        self.is_unavailable()
+            || matches!(self, Self::NotFound { .. })
+            || self.validation_message().is_some()
+    }
+
+    /// The raw response body, for `--verbose` output
+    pub fn body(&self) -> &str {
+        match self {
+            Self::Api { body, .. } | Self::NotFound { body, .. } => body,
+            _ => "",
+        }
+    }
+}
+
+impl fmt::Display for AttioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // 5xx bodies are often HTML error pages; don't dump them
+            Self::Api { status, .. } if self.is_unavailable() => write!(
+                f,
+                "Attio appears to be temporarily unavailable ({}); please retry shortly.",
+                status
+            ),
+            Self::Api { status, body, .. } => match self.validation_message() {
+                Some(message) => write!(f, "Attio rejected the request: {}", message),
+                None => write!(f, "API Error ({}): {}", status, body),
+            },
+            Self::NotFound { resource, .. } => write!(f, "{} not found.", resource),
+            Self::CircuitOpen { failures, retry_in } => write!(
+                f,
+                "The Attio API appears unavailable: the last {} requests failed. Not sending more for {} seconds; try again then.",
+                failures,
+                retry_in.as_secs().max(1)
+            ),
+            Self::Timeout { secs } => write!(
+                f,
+                "Request timed out after {} seconds. Raise it with `--timeout <secs>` or `attio config set timeout-secs <secs>`.",
+                secs
+            ),
+            Self::NotAuthenticated { env_var } => write!(
+                f,
+                "Not authenticated. Please run `attio auth <token>` or set {}.",
+                env_var
+            ),
+            // Never echo the token itself
+            Self::InvalidToken => write!(
+                f,
+                "The API token contains characters that can't be sent in a header (such as a newline). Check for stray whitespace or quotes."
+            ),
+            Self::ClientSetup(e) => write!(f, "Could not set up the HTTP client: {}", e),
+            Self::Config(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for AttioError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ClientSetup(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// What went wrong, as the process exit status. Scripts can branch on these;
+/// clap already uses 2 for invalid arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCategory {
+    Generic = 1,
+    /// The token is missing, malformed or rejected
+    Auth = 3,
+    NotFound = 4,
+    /// 429 responses outlasted the client's retries
+    RateLimited = 5,
+    /// Connection errors, timeouts, 5xx responses and an open circuit breaker
+    Network = 6,
+    /// A bad config key, value or file
+    Config = 7,
+}
+
+impl ExitCategory {
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl AttioError {
+    pub fn exit_category(&self) -> ExitCategory {
+        match self {
+            Self::Api { status, .. } if *status == StatusCode::NOT_FOUND => ExitCategory::NotFound,
+            _ if self.is_unauthorized() => ExitCategory::Auth,
+            _ if self.is_rate_limited() => ExitCategory::RateLimited,
+            _ if self.is_unavailable() => ExitCategory::Network,
+            Self::NotFound { .. } => ExitCategory::NotFound,
+            Self::Timeout { .. } | Self::CircuitOpen { .. } => ExitCategory::Network,
+            Self::InvalidToken | Self::NotAuthenticated { .. } => ExitCategory::Auth,
+            Self::Config(_) => ExitCategory::Config,
+            Self::Api { .. } | Self::ClientSetup(_) => ExitCategory::Generic,
+        }
+    }
+}
+
+/// The category of `error` or the first error in its source chain that has one
+pub fn exit_category(error: &(dyn Error + 'static)) -> ExitCategory {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(api_error) = error.downcast_ref::<AttioError>() {
+            return api_error.exit_category();
+        }
+        if let Some(http_error) = error.downcast_ref::<reqwest::Error>()
+            && (http_error.is_connect() || http_error.is_timeout())
+        {
+            return ExitCategory::Network;
+        }
+        current = error.source();
+    }
+    ExitCategory::Generic
+}
+
+/// Prints an error for the user; with `verbose`, also the raw API response
+/// that a friendly message may have replaced
+pub fn report(error: &(dyn Error + 'static), verbose: bool) {
+    eprintln!("Error: {}", error);
+    if verbose
+        && let Some(api_error) = error.downcast_ref::<AttioError>()
+        && api_error.hides_body()
+    {
+        eprintln!("Response body:\n{}", api_error.body());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAINTENANCE_PAGE: &str = "<html><body><h1>Down for maintenance</h1></body></html>";
+
+    #[test]
+    fn test_service_unavailable_hides_html_body() {
+        let error = AttioError::Api {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            body: MAINTENANCE_PAGE.to_string(),
+            retry_after: None,
+        };
+
+        let message = error.to_string();
+
+        assert!(error.is_unavailable());
+        assert!(message.contains("temporarily unavailable"));
+        assert!(message.contains("503"));
+        assert!(!message.contains("<html>"));
+        assert_eq!(error.body(), MAINTENANCE_PAGE);
+    }
+
+    #[test]
+    fn test_client_errors_keep_body() {
+        let error = AttioError::Api {
+            status: StatusCode::BAD_REQUEST,
+            body: r#"{"message":"bad"}"#.to_string(),
+            retry_after: None,
+        };
+
+        assert!(!error.is_unavailable());
+        assert!(!error.is_unauthorized());
+        assert_eq!(
+            error.to_string(),
+            r#"API Error (400 Bad Request): {"message":"bad"}"#
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_are_summarized() {
+        let error = AttioError::Api {
+            status: StatusCode::BAD_REQUEST,
+            body: r#"{
+                "status_code": 400,
+                "type": "invalid_request_error",
+                "code": "validation_type",
+                "message": "Body payload validation error.",
+                "validation_errors": [
+                    {"code": "invalid_type", "path": ["filter", "employees", "$gt"], "message": "Expected number, received string"}
+                ]
+            }"#
+            .to_string(),
+            retry_after: None,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Attio rejected the request: Body payload validation error.\n  filter.employees.$gt: Expected number, received string"
+        );
+        assert!(error.hides_body());
+    }
+
+    #[test]
+    fn test_unauthorized() {
+        let error = AttioError::Api {
+            status: StatusCode::UNAUTHORIZED,
+            body: String::new(),
+            retry_after: None,
+        };
+        assert!(error.is_unauthorized());
+        assert!(!AttioError::Timeout { secs: 30 }.is_unauthorized());
+    }
+
+    #[test]
+    fn test_rate_limited_keeps_retry_after() {
+        let error = AttioError::Api {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            body: String::new(),
+            retry_after: Some(Duration::from_secs(7)),
+        };
+        assert!(error.is_rate_limited());
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(7)));
+        assert!(!AttioError::Timeout { secs: 30 }.is_rate_limited());
+    }
+
+    #[test]
+    fn test_not_found_hides_body() {
+        let error = AttioError::NotFound {
+            resource: "Note note_1".to_string(),
+            body: r#"{"status_code":404,"message":"Note not found"}"#.to_string(),
+        };
+        assert_eq!(error.to_string(), "Note note_1 not found.");
+        assert!(error.hides_body());
+        assert!(!error.is_unavailable());
+        assert!(error.body().contains("status_code"));
+    }
+
+    fn api_error(status: StatusCode) -> AttioError {
+        AttioError::Api {
+            status,
+            body: String::new(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_exit_category_for_each_variant() {
+        let cases = [
+            (api_error(StatusCode::UNAUTHORIZED), ExitCategory::Auth),
+            (api_error(StatusCode::NOT_FOUND), ExitCategory::NotFound),
+            (
+                api_error(StatusCode::TOO_MANY_REQUESTS),
+                ExitCategory::RateLimited,
+            ),
+            (
+                api_error(StatusCode::SERVICE_UNAVAILABLE),
+                ExitCategory::Network,
+            ),
+            (api_error(StatusCode::BAD_REQUEST), ExitCategory::Generic),
+            (
+                AttioError::NotFound {
+                    resource: "Note note_1".to_string(),
+                    body: String::new(),
+                },
+                ExitCategory::NotFound,
+            ),
+            (AttioError::Timeout { secs: 30 }, ExitCategory::Network),
+            (
+                AttioError::CircuitOpen {
+                    failures: 5,
+                    retry_in: Duration::from_secs(30),
+                },
+                ExitCategory::Network,
+            ),
+            (AttioError::InvalidToken, ExitCategory::Auth),
+            (
+                AttioError::NotAuthenticated {
+                    env_var: "ATTIO_API_TOKEN".to_string(),
+                },
+                ExitCategory::Auth,
+            ),
+            (
+                AttioError::Config("Invalid config: expected value".to_string()),
+                ExitCategory::Config,
+            ),
+        ];
+        for (error, category) in cases {
+            assert_eq!(error.exit_category(), category, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct_from_clap_usage_errors() {
+        let codes = [
+            ExitCategory::Generic,
+            ExitCategory::Auth,
+            ExitCategory::NotFound,
+            ExitCategory::RateLimited,
+            ExitCategory::Network,
+            ExitCategory::Config,
+        ]
+        .map(ExitCategory::code);
+        assert_eq!(codes, [1, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_exit_category_of_boxed_errors() {
+        let boxed: Box<dyn Error> = Box::new(AttioError::Timeout { secs: 30 });
+        assert_eq!(exit_category(boxed.as_ref()), ExitCategory::Network);
+
+        let plain: Box<dyn Error> = "Something else".into();
+        assert_eq!(exit_category(plain.as_ref()), ExitCategory::Generic);
+    }
+
+    #[test]
+    fn test_timeout_message() {
+        let message = AttioError::Timeout { secs: 30 }.to_string();
+        assert!(message.starts_with("Request timed out after 30 seconds"));
+    }
+}
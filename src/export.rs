@@ -0,0 +1,188 @@
+use crate::models::Note;
+
+/// File format written by `notes export`
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// One `##` section per note
+    #[value(alias = "markdown")]
+    Md,
+    /// One row per note
+    Csv,
+    /// A single array of note objects
+    Json,
+}
+
+/// Newline style used for note content in exported files
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    /// CRLF on Windows, LF elsewhere
+    #[default]
+    Native,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+            Self::Native if cfg!(windows) => "\r\n",
+            Self::Native => "\n",
+        }
+    }
+
+    /// Rewrites every newline in `text` (`\r\n`, lone `\r` or `\n`) to this ending
+    pub fn normalize(self, text: &str) -> String {
+        let unified = text.replace("\r\n", "\n").replace('\r', "\n");
+        match self.as_str() {
+            "\n" => unified,
+            ending => unified.replace('\n', ending),
+        }
+    }
+}
+
+/// Renders notes in `format`, with every line ending written as `ending`
+pub fn render(notes: &[Note], format: ExportFormat, ending: LineEnding) -> String {
+    match format {
+        ExportFormat::Md => render_markdown(notes, ending),
+        ExportFormat::Csv => render_csv(notes, ending),
+        ExportFormat::Json => render_json(notes, ending),
+    }
+}
+
+fn render_json(notes: &[Note], ending: LineEnding) -> String {
+    // Notes are plain strings, so serialization cannot fail
+    let json = serde_json::to_string_pretty(notes).expect("notes serialize to JSON");
+    ending.normalize(&(json + "\n"))
+}
+
+fn render_markdown(notes: &[Note], ending: LineEnding) -> String {
+    let mut out = String::new();
+    for note in notes {
+        out.push_str(&format!("## {}\n\n", note.title));
+        out.push_str(&format!(
+            "- ID: {}\n- Parent: {} {}\n- Created: {}\n\n",
+            note.id.note_id, note.parent_object, note.parent_record_id, note.created_at
+        ));
+        let content = note.content_markdown.trim_end();
+        if !content.is_empty() {
+            out.push_str(content);
+            out.push_str("\n\n");
+        }
+    }
+    ending.normalize(&out)
+}
+
+const CSV_HEADER: [&str; 6] = [
+    "id",
+    "title",
+    "parent_object",
+    "parent_record_id",
+    "created_at",
+    "content_plaintext",
+];
+
+fn render_csv(notes: &[Note], ending: LineEnding) -> String {
+    let newline = ending.as_str();
+    let mut out = CSV_HEADER.join(",");
+    out.push_str(newline);
+    for note in notes {
+        let row = [
+            note.id.note_id.as_str(),
+            &note.title,
+            &note.parent_object,
+            &note.parent_record_id,
+            &note.created_at,
+            &note.content_plaintext,
+        ]
+        .map(|field| csv_field(&ending.normalize(field)));
+        out.push_str(&row.join(","));
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(title: &str, content: &str) -> Note {
+        Note::test("note_1")
+            .with_parent("people", "rec_1")
+            .with_title(title)
+            .with_content(content)
+    }
+
+    #[test]
+    fn test_normalize_mixed_line_endings() {
+        let mixed = "a\r\nb\nc\rd";
+        assert_eq!(LineEnding::Lf.normalize(mixed), "a\nb\nc\nd");
+        assert_eq!(LineEnding::Crlf.normalize(mixed), "a\r\nb\r\nc\r\nd");
+        // Already-CRLF content isn't doubled up
+        assert_eq!(LineEnding::Crlf.normalize("a\r\nb"), "a\r\nb");
+    }
+
+    #[test]
+    fn test_native_matches_platform() {
+        let expected = if cfg!(windows) { "\r\n" } else { "\n" };
+        assert_eq!(LineEnding::Native.as_str(), expected);
+    }
+
+    #[test]
+    fn test_csv_quotes_special_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn test_csv_uses_line_ending_throughout() {
+        let csv = render(
+            &[note("Call", "one\rtwo\n")],
+            ExportFormat::Csv,
+            LineEnding::Crlf,
+        );
+        assert_eq!(
+            csv,
+            "id,title,parent_object,parent_record_id,created_at,content_plaintext\r\n\
+             note_1,Call,people,rec_1,2024-01-01T00:00:00Z,\"one\r\ntwo\r\n\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trips() {
+        let json = render(
+            &[note("Call", "line one\nline two")],
+            ExportFormat::Json,
+            LineEnding::Crlf,
+        );
+        let parsed: Vec<Note> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 1);
+        // Newlines inside content stay escaped; only the layout uses CRLF
+        assert_eq!(parsed[0].content_markdown, "line one\nline two");
+        assert!(json.ends_with("]\r\n"));
+    }
+
+    #[test]
+    fn test_markdown_has_no_stray_carriage_returns() {
+        let md = render(
+            &[note("Call", "one\r\ntwo\rthree")],
+            ExportFormat::Md,
+            LineEnding::Lf,
+        );
+        assert!(md.starts_with("## Call\n\n"));
+        assert!(md.contains("one\ntwo\nthree\n"));
+        assert!(!md.contains('\r'));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::models::Note;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+
+/// A `Note` field that `--fields` can select
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoteField {
+    Id,
+    ParentObject,
+    ParentRecordId,
+    Title,
+    ContentPlaintext,
+    ContentMarkdown,
+    CreatedAt,
+}
+
+impl NoteField {
+    pub const ALL: [Self; 7] = [
+        Self::Id,
+        Self::ParentObject,
+        Self::ParentRecordId,
+        Self::Title,
+        Self::ContentPlaintext,
+        Self::ContentMarkdown,
+        Self::CreatedAt,
+    ];
+
+    /// Columns shown when `--fields` isn't given
+    pub const DEFAULT: [Self; 3] = [Self::Id, Self::Title, Self::ContentPlaintext];
+
+    /// The field's name in `Note` and its JSON
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::ParentObject => "parent_object",
+            Self::ParentRecordId => "parent_record_id",
+            Self::Title => "title",
+            Self::ContentPlaintext => "content_plaintext",
+            Self::ContentMarkdown => "content_markdown",
+            Self::CreatedAt => "created_at",
+        }
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Id => "ID",
+            Self::ParentObject => "Parent Object",
+            Self::ParentRecordId => "Parent Record ID",
+            Self::Title => "Title",
+            Self::ContentPlaintext => "Content",
+            Self::ContentMarkdown => "Markdown",
+            Self::CreatedAt => "Created",
+        }
+    }
+
+    /// The table cell for `note`; `id` shows just the note ID
+    pub fn value(self, note: &Note) -> String {
+        match self {
+            Self::Id => note.id.note_id.clone(),
+            Self::ParentObject => note.parent_object.clone(),
+            Self::ParentRecordId => note.parent_record_id.clone(),
+            Self::Title => note.title.clone(),
+            Self::ContentPlaintext => note.content_plaintext.clone(),
+            Self::ContentMarkdown => note.content_markdown.clone(),
+            Self::CreatedAt => note.created_at.clone(),
+        }
+    }
+}
+
+/// Parses one `--fields` entry, listing the valid names when it's unknown
+pub fn parse_field(name: &str) -> Result<NoteField, String> {
+    let name = name.trim();
+    NoteField::ALL
+        .into_iter()
+        .find(|field| field.name() == name)
+        .ok_or_else(|| {
+            let valid: Vec<&str> = NoteField::ALL.iter().map(|field| field.name()).collect();
+            format!(
+                "unknown field '{}'. Valid fields: {}",
+                name,
+                valid.join(", ")
+            )
+        })
+}
+
+/// A note cut down to some fields, serialized as an object with the keys in
+/// the order they were asked for
+#[derive(Debug)]
+pub struct Projection(Vec<(&'static str, Value)>);
+
+impl Serialize for Projection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// `note` with only `fields`, in that order
+pub fn project(note: &Note, fields: &[NoteField]) -> Result<Projection, serde_json::Error> {
+    let mut full = serde_json::to_value(note)?;
+    Ok(Projection(
+        fields
+            .iter()
+            .map(|field| (field.name(), full[field.name()].take()))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note() -> Note {
+        Note::test("note_1")
+            .with_parent("people", "rec_1")
+            .with_title("Kickoff")
+            .with_plaintext("Agenda")
+            .with_markdown("# Agenda")
+    }
+
+    #[test]
+    fn test_every_field_is_a_note_key() {
+        let json = serde_json::to_value(note()).unwrap();
+        for field in NoteField::ALL {
+            assert!(json.get(field.name()).is_some(), "{}", field.name());
+        }
+    }
+
+    #[test]
+    fn test_parse_field_rejects_unknown_names() {
+        assert_eq!(parse_field(" created_at"), Ok(NoteField::CreatedAt));
+        let error = parse_field("body").unwrap_err();
+        assert!(error.starts_with("unknown field 'body'. Valid fields: id, parent_object"));
+    }
+
+    #[test]
+    fn test_project_keeps_requested_order() {
+        let fields = [NoteField::CreatedAt, NoteField::Id];
+        let projected = project(&note(), &fields).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&projected).unwrap(),
+            r#"{"created_at":"2024-01-01T00:00:00Z","id":{"note_id":"note_1","workspace_id":"ws"}}"#
+        );
+        assert_eq!(NoteField::Id.value(&note()), "note_1");
+    }
+}
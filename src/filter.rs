@@ -0,0 +1,292 @@
+use crate::models::Note;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Filters notes by whether they have any content
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ContentFilter {
+    #[default]
+    All,
+    Empty,
+    NonEmpty,
+}
+
+impl ContentFilter {
+    pub fn from_flags(only_empty: bool, only_nonempty: bool) -> Self {
+        match (only_empty, only_nonempty) {
+            (true, _) => Self::Empty,
+            (_, true) => Self::NonEmpty,
+            _ => Self::All,
+        }
+    }
+
+    pub fn matches(&self, note: &Note) -> bool {
+        match self {
+            Self::All => true,
+            Self::Empty => note.is_empty_content(),
+            Self::NonEmpty => !note.is_empty_content(),
+        }
+    }
+
+    /// Cycles All → Empty → NonEmpty → All (used by the TUI toggle)
+    pub fn next(&self) -> Self {
+        match self {
+            Self::All => Self::Empty,
+            Self::Empty => Self::NonEmpty,
+            Self::NonEmpty => Self::All,
+        }
+    }
+
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            Self::All => None,
+            Self::Empty => Some("empty only"),
+            Self::NonEmpty => Some("non-empty only"),
+        }
+    }
+}
+
+/// Which note fields a search query is matched against
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchScope {
+    #[default]
+    All,
+    Title,
+    Content,
+}
+
+impl SearchScope {
+    /// Cycles All → Title → Content → All (used by the TUI toggle)
+    pub fn next(&self) -> Self {
+        match self {
+            Self::All => Self::Title,
+            Self::Title => Self::Content,
+            Self::Content => Self::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::All => "title + content",
+            Self::Title => "title",
+            Self::Content => "content",
+        }
+    }
+}
+
+/// Lowercases `text` and strips diacritics, so "Café" folds to "cafe"
+fn fold(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// True when `query` appears in the fields of `note` covered by `scope`.
+///
+/// Matching ignores case and diacritics; an empty query matches every note.
+pub fn note_matches(note: &Note, query: &str, scope: SearchScope) -> bool {
+    let query = fold(query);
+    let in_title = || fold(&note.title).contains(&query);
+    let in_content = || fold(&note.plaintext_content()).contains(&query);
+    match scope {
+        SearchScope::All => in_title() || in_content(),
+        SearchScope::Title => in_title(),
+        SearchScope::Content => in_content(),
+    }
+}
+
+/// Parses an RFC 3339 timestamp, or a `YYYY-MM-DD` date meaning midnight UTC
+pub fn parse_date(input: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(input) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc())
+        .ok_or_else(|| {
+            format!(
+                "invalid date '{}': expected YYYY-MM-DD or RFC 3339 (e.g. 2024-01-31T09:00:00Z)",
+                input
+            )
+        })
+}
+
+/// Window of note creation times; `since` is inclusive, `until` exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DateRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub fn new(since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>) -> Result<Self, String> {
+        if let (Some(since), Some(until)) = (since, until)
+            && since >= until
+        {
+            return Err(format!(
+                "--since ({}) must be before --until ({})",
+                since.to_rfc3339(),
+                until.to_rfc3339()
+            ));
+        }
+        Ok(Self { since, until })
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+
+    /// Notes whose `created_at` can't be parsed only match an open range
+    pub fn matches(&self, note: &Note) -> bool {
+        if !self.is_set() {
+            return true;
+        }
+        let Ok(created) = note.created_at_datetime() else {
+            return false;
+        };
+        self.since.is_none_or(|since| created >= since)
+            && self.until.is_none_or(|until| created < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(content: &str) -> Note {
+        titled("Title", content)
+    }
+
+    fn titled(title: &str, content: &str) -> Note {
+        Note::test("note").with_title(title).with_content(content)
+    }
+
+    #[test]
+    fn test_content_filter_matches() {
+        let empty = note("  \n ");
+        let full = note("Agenda");
+
+        assert!(ContentFilter::All.matches(&empty) && ContentFilter::All.matches(&full));
+        assert!(ContentFilter::Empty.matches(&empty));
+        assert!(!ContentFilter::Empty.matches(&full));
+        assert!(ContentFilter::NonEmpty.matches(&full));
+        assert!(!ContentFilter::NonEmpty.matches(&empty));
+    }
+
+    #[test]
+    fn test_content_filter_cycles() {
+        let filter = ContentFilter::All;
+        assert_eq!(filter.next(), ContentFilter::Empty);
+        assert_eq!(filter.next().next(), ContentFilter::NonEmpty);
+        assert_eq!(filter.next().next().next(), ContentFilter::All);
+    }
+
+    #[test]
+    fn test_content_filter_from_flags() {
+        assert_eq!(ContentFilter::from_flags(false, false), ContentFilter::All);
+        assert_eq!(ContentFilter::from_flags(true, false), ContentFilter::Empty);
+        assert_eq!(
+            ContentFilter::from_flags(false, true),
+            ContentFilter::NonEmpty
+        );
+    }
+
+    #[test]
+    fn test_note_matches_ignores_case() {
+        let note = titled("Quarterly Review", "Discussed PRICING");
+        assert!(note_matches(&note, "quarterly", SearchScope::All));
+        assert!(note_matches(&note, "pricing", SearchScope::All));
+        assert!(!note_matches(&note, "renewal", SearchScope::All));
+    }
+
+    #[test]
+    fn test_note_matches_respects_scope() {
+        let note = titled("Quarterly Review", "Discussed pricing");
+        assert!(note_matches(&note, "review", SearchScope::Title));
+        assert!(!note_matches(&note, "pricing", SearchScope::Title));
+        assert!(note_matches(&note, "pricing", SearchScope::Content));
+        assert!(!note_matches(&note, "review", SearchScope::Content));
+    }
+
+    #[test]
+    fn test_note_matches_ignores_diacritics() {
+        let note = titled("Café with Zoë", "Meeting in São Paulo");
+        assert!(note_matches(&note, "cafe", SearchScope::All));
+        assert!(note_matches(&note, "ZOE", SearchScope::Title));
+        assert!(note_matches(&note, "sao paulo", SearchScope::Content));
+        // Accented queries still match accented text
+        assert!(note_matches(&note, "São", SearchScope::Content));
+    }
+
+    #[test]
+    fn test_note_matches_decomposed_text() {
+        // "é" written as "e" + combining acute accent
+        let note = titled("Cafe\u{301}", "");
+        assert!(note_matches(&note, "café", SearchScope::Title));
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        assert!(note_matches(&note(""), "", SearchScope::Content));
+    }
+
+    fn created(at: &str) -> Note {
+        note("").with_created_at(at)
+    }
+
+    #[test]
+    fn test_parse_date_formats() {
+        assert_eq!(
+            parse_date("2024-01-31").unwrap().to_rfc3339(),
+            "2024-01-31T00:00:00+00:00"
+        );
+        // Offsets are converted to UTC
+        assert_eq!(
+            parse_date("2024-01-31T09:00:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2024-01-31T07:00:00+00:00"
+        );
+        assert!(parse_date("31/01/2024").is_err());
+        assert!(parse_date("2024-02-30").is_err());
+    }
+
+    #[test]
+    fn test_date_range_boundaries() {
+        let range =
+            DateRange::new(parse_date("2024-01-01").ok(), parse_date("2024-02-01").ok()).unwrap();
+        // since is inclusive
+        assert!(range.matches(&created("2024-01-01T00:00:00Z")));
+        assert!(!range.matches(&created("2023-12-31T23:59:59Z")));
+        // until is exclusive
+        assert!(range.matches(&created("2024-01-31T23:59:59.999Z")));
+        assert!(!range.matches(&created("2024-02-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_date_range_unparseable_created_at() {
+        assert!(DateRange::default().matches(&created("garbage")));
+        let range = DateRange::new(parse_date("2024-01-01").ok(), None).unwrap();
+        assert!(!range.matches(&created("garbage")));
+    }
+
+    #[test]
+    fn test_date_range_rejects_inverted_bounds() {
+        let day = parse_date("2024-01-01").ok();
+        assert!(DateRange::new(day, day).is_err());
+        assert!(DateRange::new(day, None).is_ok());
+    }
+
+    #[test]
+    fn test_search_scope_cycles() {
+        let scope = SearchScope::All;
+        assert_eq!(scope.next(), SearchScope::Title);
+        assert_eq!(scope.next().next(), SearchScope::Content);
+        assert_eq!(scope.next().next().next(), SearchScope::All);
+    }
+}
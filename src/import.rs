@@ -0,0 +1,241 @@
+use crate::models::CreateNoteData;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Columns a CSV import file must have; `format` is optional
+const REQUIRED_COLUMNS: [&str; 4] = ["parent_object", "parent_record_id", "title", "content"];
+
+/// One note to create, as read from an import file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImportRow {
+    pub parent_object: String,
+    pub parent_record_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+    /// "plaintext" or "markdown" [default: plaintext]
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// A row that could not be imported, for the end-of-run report
+#[derive(Debug, Serialize)]
+pub struct ImportFailure {
+    /// 1-based position of the note in the file
+    pub row: usize,
+    pub title: String,
+    pub error: String,
+}
+
+/// What `notes import` did, printed with `--output json`
+#[derive(Debug, Serialize)]
+pub struct ImportSummary {
+    pub total: usize,
+    pub created: usize,
+    pub failures: Vec<ImportFailure>,
+}
+
+impl ImportRow {
+    /// Checks the row and turns it into a create request body
+    pub fn into_data(self) -> Result<CreateNoteData, String> {
+        for (field, value) in [
+            ("parent_object", &self.parent_object),
+            ("parent_record_id", &self.parent_record_id),
+            ("title", &self.title),
+        ] {
+            if value.trim().is_empty() {
+                return Err(format!("{} is empty", field));
+            }
+        }
+        let format = match self.format.as_deref().map(str::trim) {
+            None | Some("") => "plaintext".to_string(),
+            Some(format) => CreateNoteData::parse_format(format)?,
+        };
+        Ok(CreateNoteData {
+            parent_object: self.parent_object,
+            parent_record_id: self.parent_record_id,
+            title: self.title,
+            format,
+            content: self.content,
+        })
+    }
+}
+
+/// Reads note rows from a `.csv` or `.json` file
+pub fn read_rows(path: &Path) -> Result<Vec<ImportRow>, Box<dyn Error>> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("csv") => parse_csv(&text),
+        Some("json") => parse_json(&text),
+        _ => Err(format!(
+            "Can't tell the format of {}. Use a .csv or .json file.",
+            path.display()
+        )
+        .into()),
+    }
+}
+
+/// Parses a JSON array of note objects
+pub fn parse_json(text: &str) -> Result<Vec<ImportRow>, Box<dyn Error>> {
+    serde_json::from_str(text).map_err(|e| format!("Invalid import JSON: {}", e).into())
+}
+
+/// Parses CSV with a header row naming the columns, in any order
+pub fn parse_csv(text: &str) -> Result<Vec<ImportRow>, Box<dyn Error>> {
+    let mut records = split_records(text)?.into_iter();
+    let header = records.next().ok_or("The import file is empty")?;
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+
+    let missing: Vec<&str> = REQUIRED_COLUMNS
+        .iter()
+        .copied()
+        .filter(|name| column(name).is_none())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("The CSV header is missing: {}", missing.join(", ")).into());
+    }
+    let [object, record_id, title, content] = REQUIRED_COLUMNS.map(|name| column(name).unwrap());
+    let format = column("format");
+
+    let field = |record: &[String], index: usize| record.get(index).cloned().unwrap_or_default();
+    Ok(records
+        .map(|record| ImportRow {
+            parent_object: field(&record, object),
+            parent_record_id: field(&record, record_id),
+            title: field(&record, title),
+            content: field(&record, content),
+            format: format.map(|index| field(&record, index)),
+        })
+        .collect())
+}
+
+/// Splits CSV text into records, honoring quoted fields (which may contain
+/// commas, `""` escapes and newlines). Blank lines are skipped.
+fn split_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                if record.len() == 1 && record[0].is_empty() {
+                    record.clear();
+                } else {
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("The CSV file ends inside a quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_records_handles_quotes() {
+        let records =
+            split_records("a,b\r\n\"x, y\",\"say \"\"hi\"\"\"\n\n\"multi\nline\",z").unwrap();
+        assert_eq!(
+            records,
+            [
+                vec!["a", "b"],
+                vec!["x, y", "say \"hi\""],
+                vec!["multi\nline", "z"]
+            ]
+        );
+        assert!(split_records("\"open").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_maps_columns_by_header() {
+        let csv = "title,content,parent_record_id,parent_object\nKickoff,Agenda,rec_1,people\n";
+        let rows = parse_csv(csv).unwrap();
+        assert_eq!(
+            rows,
+            [ImportRow {
+                parent_object: "people".to_string(),
+                parent_record_id: "rec_1".to_string(),
+                title: "Kickoff".to_string(),
+                content: "Agenda".to_string(),
+                format: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_requires_columns() {
+        let error = parse_csv("title,content\nKickoff,Agenda").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The CSV header is missing: parent_object, parent_record_id"
+        );
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let rows = parse_json(
+            r#"[{"parent_object": "deals", "parent_record_id": "rec_2", "title": "Call", "format": "markdown"}]"#,
+        )
+        .unwrap();
+        assert_eq!(rows[0].content, "");
+        assert_eq!(rows[0].format.as_deref(), Some("markdown"));
+    }
+
+    #[test]
+    fn test_into_data_validates_row() {
+        let row = ImportRow {
+            parent_object: "people".to_string(),
+            parent_record_id: "rec_1".to_string(),
+            title: "Kickoff".to_string(),
+            content: String::new(),
+            format: Some(String::new()),
+        };
+        assert_eq!(row.clone().into_data().unwrap().format, "plaintext");
+
+        let untitled = ImportRow {
+            title: " ".to_string(),
+            ..row.clone()
+        };
+        assert_eq!(untitled.into_data().unwrap_err(), "title is empty");
+
+        let html = ImportRow {
+            format: Some("html".to_string()),
+            ..row
+        };
+        assert!(
+            html.into_data()
+                .unwrap_err()
+                .contains("unknown format 'html'")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use chrono::{DateTime, SecondsFormat};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Log level for `-v` given `verbosity` times: warnings and errors by
+/// default, then info, debug and trace
+pub fn level_for(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Where logs go while the TUI is running, unless `log_file` is configured
+pub fn default_log_file() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("attio")
+        .join("attio-cli.log")
+}
+
+/// Writes log lines to stderr, or to a file while the terminal is taken over
+/// by the TUI (where stderr output would corrupt the screen)
+struct Logger {
+    path: PathBuf,
+    to_file: AtomicBool,
+    /// Opened on the first line written to it
+    file: Mutex<Option<File>>,
+}
+
+impl Logger {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            to_file: AtomicBool::new(false),
+            file: Mutex::new(None),
+        }
+    }
+
+    fn open(path: &Path) -> Option<File> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).ok()?;
+        }
+        OpenOptions::new().create(true).append(true).open(path).ok()
+    }
+
+    fn write_to_file(&self, line: &str) {
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+        if file.is_none() {
+            *file = Self::open(&self.path);
+        }
+        if let Some(file) = file.as_mut() {
+            let _ = writeln!(file, "{}", line);
+            let _ = file.flush();
+        }
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if self.to_file.load(Ordering::SeqCst) {
+            self.write_to_file(&format!(
+                "{} {:<5} {}",
+                timestamp(),
+                record.level(),
+                record.args()
+            ));
+        } else {
+            eprintln!("{}: {}", label(record.level()), record.args());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock()
+            && let Some(file) = file.as_mut()
+        {
+            let _ = file.flush();
+        }
+    }
+}
+
+fn label(level: Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warn => "warning",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    }
+}
+
+/// Current time as an RFC 3339 UTC timestamp, for log file lines
+fn timestamp() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    DateTime::from_timestamp(now.as_secs() as i64, now.subsec_nanos())
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+/// Installs the logger at the level for `verbosity`. `log_file` overrides
+/// `default_log_file()`. Only the first call has any effect.
+pub fn init(verbosity: u8, log_file: Option<PathBuf>) {
+    let logger = LOGGER.get_or_init(|| Logger::new(log_file.unwrap_or_else(default_log_file)));
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(level_for(verbosity));
+    }
+}
+
+/// Sends log lines to the log file instead of stderr, e.g. while the TUI is
+/// drawing on the terminal
+pub fn redirect_to_file(enabled: bool) {
+    if let Some(logger) = LOGGER.get() {
+        logger.to_file.store(enabled, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_raises_the_level() {
+        assert_eq!(level_for(0), LevelFilter::Warn);
+        assert_eq!(level_for(1), LevelFilter::Info);
+        assert_eq!(level_for(2), LevelFilter::Debug);
+        assert_eq!(level_for(9), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn test_lines_are_appended_to_the_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        // The parent directory is created on demand
+        let path = dir.path().join("logs").join("attio-cli.log");
+        let logger = Logger::new(path.clone());
+
+        logger.write_to_file("ERROR CRITICAL PANIC: boom");
+        logger.write_to_file("WARN  second");
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(written, "ERROR CRITICAL PANIC: boom\nWARN  second\n");
+    }
+
+    #[test]
+    fn test_log_file_is_not_created_until_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("attio-cli.log");
+        let logger = Logger::new(path.clone());
+
+        logger.flush();
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_timestamp_is_rfc3339_utc() {
+        let stamp = timestamp();
+        assert!(DateTime::parse_from_rfc3339(&stamp).is_ok());
+        assert!(stamp.ends_with('Z'));
+    }
+}
@@ -1,22 +1,87 @@
+mod batch;
+mod browser;
 mod cache;
 mod client;
+mod clipboard;
+mod diff;
+mod doctor;
+mod editor;
+mod error;
+mod export;
+mod fields;
+mod filter;
+mod fsutil;
+mod import;
+mod logging;
+mod markdown;
 mod models;
+mod notify;
+mod output;
+mod progress;
+mod report;
+mod resolve;
+mod retry;
+mod secrets;
+mod symbols;
+mod template;
+mod timings;
+mod trace;
 mod tui;
+mod update;
+mod watch;
 
 use clap::{Parser, Subcommand};
-use client::AttioClient;
+use client::{AttioClient, DryRunRequest};
 use dotenvy::dotenv;
 use std::env;
 use std::error::Error;
 
 use std::fs;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use symbols::SymbolSet;
 
 #[derive(Parser)]
 #[command(name = "attio", author, version, about = "A CLI tool for Attio CRM", long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Show more detail, e.g. the raw response body behind a friendly API
+    /// error. Repeat for more log output: -v info, -vv debug, -vvv trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Leave out success banners and progress messages, printing only the
+    /// requested data, warnings and errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Print request timings to stderr when the command finishes
+    #[arg(long, global = true)]
+    timings: bool,
+    /// Symbols used in status output [default: detected from the terminal]
+    #[arg(long, global = true, value_enum)]
+    symbols: Option<SymbolSet>,
+    /// Signal when long operations finish [default: off, or the notify config key]
+    #[arg(long, global = true, value_enum)]
+    notify: Option<notify::NotifyMode>,
+    /// Output format for note commands; `json` replaces tables with the API response
+    #[arg(long, global = true, value_enum, default_value = "table")]
+    output: output::OutputFormat,
+    /// Print the request a mutating command would send, without sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// Fetch the workspace details again instead of using the cached ones
+    #[arg(long, global = true)]
+    refresh_identity: bool,
+    /// Read and write this config file instead of the default one
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Request timeout in seconds for this run, overriding timeout-secs; 0 waits forever
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+    /// Append every API request and response to this file as JSON lines, with the token redacted
+    #[arg(long, global = true, value_name = "PATH")]
+    trace: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -24,7 +89,17 @@ enum Commands {
     /// Authenticate with Attio
     Auth {
         /// Your Attio API Token
-        token: String,
+        #[arg(required_unless_present = "migrate_to_keyring")]
+        token: Option<String>,
+        /// Save the token without checking it against the API (for offline setups)
+        #[arg(long)]
+        force: bool,
+        /// Save the token in the project config (.attio/config.json) instead
+        #[arg(long)]
+        local: bool,
+        /// Move the tokens in the config file to the OS keyring and set token-storage to keyring
+        #[arg(long, conflicts_with_all = ["token", "force", "local"])]
+        migrate_to_keyring: bool,
     },
     /// Note related actions
     Notes {
@@ -36,6 +111,248 @@ enum Commands {
         #[command(subcommand)]
         action: ConfigCommands,
     },
+    /// The on-disk notes cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommands,
+    },
+    /// Webhook subscription management
+    Webhooks {
+        #[command(subcommand)]
+        action: WebhookCommands,
+    },
+    /// Objects (people, companies and custom objects) in the workspace
+    Objects {
+        #[command(subcommand)]
+        action: ObjectCommands,
+    },
+    /// Records of any object, e.g. `attio records companies list`
+    Records {
+        /// Object slug, e.g. "companies" (see `attio objects list`)
+        object: String,
+        #[command(subcommand)]
+        action: RecordCommands,
+    },
+    /// People records
+    People {
+        #[command(subcommand)]
+        action: PeopleCommands,
+    },
+    /// Company records
+    Companies {
+        #[command(subcommand)]
+        action: CompanyCommands,
+    },
+    /// Tasks
+    Tasks {
+        #[command(subcommand)]
+        action: TaskCommands,
+    },
+    /// Comments on records
+    Comments {
+        #[command(subcommand)]
+        action: CommentCommands,
+    },
+    /// Show which workspace the current token belongs to
+    Whoami,
+    /// Check the config file, token, permissions and network, with hints for anything wrong
+    Doctor,
+    /// Print the installed version
+    Version {
+        /// Also ask GitHub whether a newer release is out
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TaskCommands {
+    /// List tasks
+    List {
+        /// Maximum number of tasks to return
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Number of tasks to skip
+        #[arg(long)]
+        offset: Option<u32>,
+    },
+    /// Get a task by ID
+    Get {
+        /// The ID of the task
+        task_id: String,
+    },
+    /// Create a task
+    Create {
+        /// What needs doing
+        #[arg(long)]
+        content: String,
+        /// When the task is due (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", value_parser = filter::parse_date)]
+        deadline: Option<chrono::DateTime<chrono::Utc>>,
+        /// Record to link the task to, e.g. "companies:Acme" or "people:<record id>" (repeatable)
+        #[arg(long, value_name = "OBJECT:RECORD")]
+        link: Vec<String>,
+    },
+    /// Mark a task as completed
+    Complete {
+        /// The ID of the task
+        task_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommentCommands {
+    /// List the comment threads on a record
+    List {
+        /// The object the record belongs to (e.g., "people")
+        #[arg(long)]
+        parent_object: String,
+        /// The ID of the record
+        #[arg(long)]
+        parent_record_id: String,
+    },
+    /// Start a comment thread on a record, or reply in an existing one
+    Create {
+        /// The comment text
+        #[arg(long)]
+        content: String,
+        /// The object the record belongs to (e.g., "people")
+        #[arg(
+            long,
+            required_unless_present = "thread_id",
+            requires = "parent_record_id"
+        )]
+        parent_object: Option<String>,
+        /// The ID of the record to comment on
+        #[arg(
+            long,
+            required_unless_present = "thread_id",
+            requires = "parent_object"
+        )]
+        parent_record_id: Option<String>,
+        /// Reply in this thread instead of starting a new one
+        #[arg(long, conflicts_with_all = ["parent_object", "parent_record_id"])]
+        thread_id: Option<String>,
+        /// Workspace member to post as [default: the member who created the token]
+        #[arg(long, value_name = "MEMBER_ID")]
+        author: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CompanyCommands {
+    /// List companies
+    List {
+        /// Maximum number of companies to return
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Number of companies to skip
+        #[arg(long)]
+        offset: Option<u32>,
+    },
+    /// Get a company by record ID
+    Get {
+        /// The record ID of the company
+        record_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum PeopleCommands {
+    /// List people
+    List {
+        /// Maximum number of people to return
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Number of people to skip
+        #[arg(long)]
+        offset: Option<u32>,
+    },
+    /// Get a person by record ID
+    Get {
+        /// The record ID of the person
+        record_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RecordCommands {
+    /// List the object's records
+    List {
+        /// Maximum number of records to return
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Number of records to skip
+        #[arg(long)]
+        offset: Option<u32>,
+        /// Comma-separated attribute slugs to show as columns, e.g. "name,domains"
+        #[arg(long, value_delimiter = ',')]
+        attributes: Vec<String>,
+    },
+    /// Get a record by ID
+    Get {
+        /// The record ID
+        record_id: String,
+        /// Comma-separated attribute slugs to show [default: every attribute with a value]
+        #[arg(long, value_delimiter = ',')]
+        attributes: Vec<String>,
+    },
+    /// Query the object's records, filtered and in a chosen order
+    Query {
+        /// Raw Attio filter JSON, e.g. '{"name": {"$contains": "Acme"}}'
+        #[arg(long, value_parser = models::filter::parse_filter_json)]
+        filter: Option<serde_json::Value>,
+        /// A condition such as "employees gt 50" (operators: eq, contains, gt,
+        /// lt); repeat to require several
+        #[arg(long = "where", value_name = "CONDITION", value_parser = models::Condition::parse)]
+        conditions: Vec<models::Condition>,
+        /// Sort by an attribute, optionally with ":asc" or ":desc"; repeat to break ties
+        #[arg(long = "sort", value_parser = models::RecordSort::parse)]
+        sorts: Vec<models::RecordSort>,
+        /// Maximum number of records to return
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Number of records to skip
+        #[arg(long)]
+        offset: Option<u32>,
+        /// Comma-separated attribute slugs to show as columns, e.g. "name,domains"
+        #[arg(long, value_delimiter = ',')]
+        attributes: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ObjectCommands {
+    /// List the objects records can belong to
+    List,
+}
+
+#[derive(Subcommand)]
+enum WebhookCommands {
+    /// List webhook subscriptions
+    List,
+    /// Subscribe a URL to workspace events
+    Create {
+        /// The HTTPS URL events are delivered to
+        #[arg(long)]
+        url: String,
+        /// Comma-separated event types, e.g. "note.created,note.deleted"
+        #[arg(long, value_delimiter = ',', required = true)]
+        events: Vec<String>,
+    },
+    /// Delete a webhook by ID
+    Delete {
+        /// The ID of the webhook to delete
+        webhook_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Show where the notes cache is, its size and how many notes it holds
+    Info,
+    /// Delete the notes cache
+    Clear,
 }
 
 #[derive(Subcommand)]
@@ -46,14 +363,55 @@ enum ConfigCommands {
         key: String,
         /// Configuration value
         value: String,
+        /// Set it in the project config (.attio/config.json) instead
+        #[arg(long)]
+        local: bool,
     },
     /// Get a configuration value
     Get {
         /// Configuration key (e.g., cache-limit-mb)
         key: String,
     },
+    /// Reset a configuration value to its default
+    Unset {
+        /// Configuration key (e.g., cache-limit-mb)
+        key: String,
+    },
+    /// Reset every configuration value to its default, keeping the token and profiles
+    Reset,
     /// List all configuration values
     List,
+    /// Open the config file in $VISUAL / $EDITOR
+    Edit,
+    /// Print where the config file is read from and written to
+    Path,
+    /// Manage named workspace profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommands {
+    /// Add a profile for another workspace
+    Add {
+        /// Profile name, e.g. "work"
+        name: String,
+        /// API token for the workspace [default: the current token]
+        #[arg(long)]
+        token: Option<String>,
+        /// Cache limit for this profile [default: the top-level cache-limit-mb]
+        #[arg(long)]
+        cache_limit_mb: Option<u64>,
+    },
+    /// Switch to a profile
+    Use {
+        /// Profile name
+        name: String,
+    },
+    /// List profiles
+    List,
 }
 
 #[derive(Subcommand)]
@@ -63,6 +421,90 @@ enum NoteCommands {
         /// Show notes in plain text mode (non-interactive)
         #[arg(long)]
         plain: bool,
+        /// Summarize which records have notes instead of listing every note
+        #[arg(long)]
+        distinct_parents: bool,
+        /// Look up each parent record's name (one extra request per record)
+        #[arg(long, requires = "distinct_parents")]
+        resolve_names: bool,
+        /// Only show notes whose content is blank
+        #[arg(long, conflicts_with = "only_nonempty")]
+        only_empty: bool,
+        /// Only show notes that have content
+        #[arg(long)]
+        only_nonempty: bool,
+        /// Only show notes containing this text (ignores case and accents)
+        #[arg(long, value_name = "TEXT", conflicts_with = "watch_record")]
+        contains: Option<String>,
+        /// Fields --contains searches
+        #[arg(long, value_enum, default_value = "all", requires = "contains")]
+        scope: filter::SearchScope,
+        /// Only show notes created at or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", value_parser = filter::parse_date, conflicts_with = "watch_record")]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        /// Only show notes created before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", value_parser = filter::parse_date, conflicts_with = "watch_record")]
+        until: Option<chrono::DateTime<chrono::Utc>>,
+        /// Only list notes attached to records of this object, e.g. "deals" (needs --parent-record-id)
+        #[arg(long, requires = "parent_record_id", conflicts_with = "watch_record")]
+        parent_object: Option<String>,
+        /// Only list notes attached to this record (needs --parent-object)
+        #[arg(long, requires = "parent_object")]
+        parent_record_id: Option<String>,
+        /// Poll the notes of one record, e.g. "deals:<record id>" or "companies:Acme", until Ctrl+C
+        #[arg(long, value_name = "OBJECT:RECORD", conflicts_with_all = ["plain", "distinct_parents"])]
+        watch_record: Option<String>,
+        /// Seconds between polls in --watch-record mode
+        #[arg(long, default_value_t = 10, requires = "watch_record", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Stream notes as newline-delimited JSON, one note per line, as pages arrive
+        #[arg(long, conflicts_with_all = ["plain", "distinct_parents", "watch_record", "cache_only"])]
+        json_lines: bool,
+        /// Comma-separated note fields to show, in order, e.g. "id,title,created_at" (implies --plain)
+        #[arg(
+            long,
+            value_name = "FIELDS",
+            value_delimiter = ',',
+            value_parser = fields::parse_field,
+            conflicts_with_all = ["distinct_parents", "watch_record", "json_lines"]
+        )]
+        fields: Vec<fields::NoteField>,
+        /// Notes per page in plain mode [default: 25]; in --json-lines mode, stop after this many notes
+        #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        limit: Option<usize>,
+        /// Page of notes to show in plain mode, starting at 1 (implies --plain)
+        #[arg(
+            long,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+            conflicts_with_all = ["json_lines", "watch_record", "distinct_parents"]
+        )]
+        page: Option<usize>,
+        /// List notes from the disk cache only, without touching the network
+        #[arg(long, conflicts_with_all = ["watch_record", "resolve_names", "no_dedupe"])]
+        cache_only: bool,
+        /// Open the TUI on the first page, ignoring the page, search and sort it was left with
+        #[arg(long)]
+        fresh: bool,
+        /// Keep duplicate notes returned by the API and count them (for diagnosing paging)
+        #[arg(long, hide = true)]
+        no_dedupe: bool,
+    },
+    /// Browse notes and print the ID of the one picked with Enter
+    Pick {
+        /// Start with this search (ignores case and accents)
+        #[arg(long, value_name = "TEXT")]
+        contains: Option<String>,
+        /// Fields --contains searches
+        #[arg(long, value_enum, default_value = "all", requires = "contains")]
+        scope: filter::SearchScope,
+    },
+    /// Search all notes for text in their title or content
+    Search {
+        /// Text to look for (ignores case and accents)
+        query: String,
+        /// Fields to search
+        #[arg(long, value_enum, default_value = "all")]
+        scope: filter::SearchScope,
     },
     /// Get a specific note by ID
     Get {
@@ -71,298 +513,3162 @@ enum NoteCommands {
         /// Open the note in your default browser
         #[arg(long)]
         open_in_browser: bool,
+        /// Skip the disk cache and fetch the note from the API
+        #[arg(long)]
+        refresh: bool,
     },
     /// Create a new note
     Create {
         /// The object the note belongs to (e.g., "people")
         #[arg(long)]
-        parent_object: String,
+        parent_object: Option<String>,
         /// The ID of the record the note is associated with
         #[arg(long)]
-        parent_record_id: String,
+        parent_record_id: Option<String>,
+        /// The parent record as <object>:<record name>, e.g. "people:Jane Doe"
+        #[arg(long, conflicts_with_all = ["parent_object", "parent_record_id"])]
+        parent: Option<String>,
+        /// Read the parent record ID (or Attio record URL) from the clipboard
+        #[arg(long, conflicts_with_all = ["parent", "parent_record_id"])]
+        parent_from_clipboard: bool,
         /// The title of the note
         #[arg(long)]
-        title: String,
+        title: Option<String>,
         /// The content of the note
         #[arg(long)]
-        content: String,
-        /// The format of the content ("plaintext" or "markdown")
-        #[arg(long, default_value = "plaintext")]
-        format: String,
+        content: Option<String>,
+        /// Read the content of the note from a file
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["content", "content_stdin"])]
+        content_file: Option<PathBuf>,
+        /// Read the content of the note from piped stdin
+        #[arg(long, conflicts_with = "content")]
+        content_stdin: bool,
+        /// The format of the content ("plaintext" or "markdown") [default: plaintext]
+        #[arg(long, value_parser = models::CreateNoteData::parse_format)]
+        format: Option<String>,
+        /// Read note fields from a markdown file with YAML frontmatter (flags override it)
+        #[arg(long, value_name = "PATH")]
+        from_template_file: Option<PathBuf>,
+        /// Allow creating a note with empty or whitespace-only content
+        #[arg(long)]
+        allow_empty: bool,
+        /// Check that the parent record exists before creating the note
+        #[arg(long)]
+        verify_parent: bool,
         /// Open the note in your default browser after creating it
         #[arg(long)]
         open_in_browser: bool,
     },
+    /// List the files attached to a note
+    Attachments {
+        /// The ID of the note
+        note_id: String,
+        /// Download every attachment into this directory
+        #[arg(long, value_name = "DIR")]
+        download: Option<PathBuf>,
+    },
+    /// Open a note in your default browser
+    Open {
+        /// The ID of the note to open
+        note_id: String,
+    },
     /// Delete a note by ID
     Delete {
         /// The ID of the note to delete
         note_id: String,
     },
+    /// Delete several notes, reporting each one and continuing past failures
+    DeleteMany {
+        /// IDs of the notes to delete
+        #[arg(required_unless_present = "from_file")]
+        note_ids: Vec<String>,
+        /// Also delete the IDs in this file, one per line
+        #[arg(long, value_name = "PATH")]
+        from_file: Option<PathBuf>,
+        /// Delete without asking first
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Show a unified diff between two notes
+    Diff {
+        /// The ID of the original note
+        note_id: String,
+        /// The ID of the note to compare against
+        other_note_id: String,
+        /// Which field to compare
+        #[arg(long, value_enum, default_value = "content")]
+        field: diff::DiffField,
+    },
+    /// Export all notes to a file
+    Export {
+        /// Destination file
+        path: PathBuf,
+        /// Output format
+        #[arg(long, value_enum, default_value = "md")]
+        format: export::ExportFormat,
+        /// Newline style for note content
+        #[arg(long, value_enum, default_value = "native")]
+        line_ending: export::LineEnding,
+    },
+    /// Create notes from a CSV or JSON file
+    ///
+    /// Each record needs parent_object, parent_record_id, title and content,
+    /// and may set format ("plaintext" or "markdown"). CSV files name these
+    /// columns in a header row.
+    Import {
+        /// A .csv or .json file of notes
+        path: PathBuf,
+        /// Keep going after a note fails, and report all failures at the end
+        #[arg(long)]
+        continue_on_error: bool,
+    },
 }
 
-fn get_config_path() -> PathBuf {
-    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push("attio");
-    path.push("config.json");
-    path
+/// Keys accepted by `config set` / `config get` / `config unset`
+const CONFIG_KEYS: &[&str] = &[
+    "base-url",
+    "cache-limit-mb",
+    "cache-enabled",
+    "cache-ttl-secs",
+    "token-source-priority",
+    "token-env-var",
+    "token-storage",
+    "symbols",
+    "notify",
+    "compression",
+    "mouse",
+    "theme",
+    "theme-colors",
+    "default-parent-object",
+    "default-parent-record-id",
+    "max-retries",
+    "retry-base-delay-ms",
+    "breaker-threshold",
+    "breaker-cooldown-secs",
+    "timeout-secs",
+    "fetch-delay-ms",
+    "refresh-interval-secs",
+    "fetch-concurrency",
+    "fetch-all-max",
+    "log-file",
+];
+
+/// Upper bound for `fetch-concurrency`, to stay well inside Attio's rate limit
+const MAX_FETCH_CONCURRENCY: usize = 8;
+
+fn unknown_config_key(key: &str) -> Box<dyn Error> {
+    config_error(format!(
+        "Unknown config key: {}. Available keys: {}",
+        key,
+        CONFIG_KEYS.join(", ")
+    ))
+}
+
+/// A bad config key, value or file, which exits with the config code
+fn config_error(message: impl Into<String>) -> Box<dyn Error> {
+    error::AttioError::Config(message.into()).into()
+}
+
+/// `Ok(None)` when there's no config file yet; an invalid one is still an error
+fn existing_config(
+    result: Result<models::Config, Box<dyn Error>>,
+) -> Result<Option<models::Config>, Box<dyn Error>> {
+    match result {
+        Ok(config) => Ok(Some(config)),
+        Err(e) if error::exit_category(e.as_ref()) == error::ExitCategory::Config => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, Box<dyn Error>> {
+    match value {
+        "true" | "on" | "yes" | "1" => Ok(true),
+        "false" | "off" | "no" | "0" => Ok(false),
+        _ => Err(config_error(format!(
+            "Invalid value. {} must be true or false.",
+            key
+        ))),
+    }
+}
+
+/// The config file to use: `--config`, then `ATTIO_CONFIG_DIR`, then the OS default
+fn get_config_path(flag: Option<&Path>) -> PathBuf {
+    models::config::resolve_config_path(flag, dirs::config_dir(), |name| env::var(name).ok())
+}
+
+/// Parses edited config JSON, rejecting anything `read_config` couldn't load
+fn parse_config(content: &str) -> Result<models::Config, Box<dyn Error>> {
+    serde_json::from_str(content).map_err(|e| config_error(format!("Invalid config: {}", e)))
+}
+
+/// Asks a yes/no question on stderr. An empty answer means `default`; no
+/// answer at all (end of input) means no.
+fn confirm(prompt: &str, default: bool) -> Result<bool, Box<dyn Error>> {
+    eprint!("{} {} ", prompt, if default { "[Y/n]" } else { "[y/N]" });
+    std::io::Write::flush(&mut std::io::stderr())?;
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer)? == 0 {
+        return Ok(false);
+    }
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
+
+/// Edits the config in a scratch copy so an invalid edit never replaces the
+/// real file
+fn edit_config(config_path: &Path, symbols: SymbolSet) -> Result<(), Box<dyn Error>> {
+    let original = match fs::read_to_string(config_path) {
+        Ok(content) => content,
+        Err(_) => serde_json::to_string_pretty(&models::Config::new(String::new()))?,
+    };
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let scratch_path = config_path.with_extension("json.edit");
+    fsutil::write_private(&scratch_path, original.as_bytes())?;
+
+    let result = loop {
+        if let Err(e) = editor::open_in_editor(&scratch_path) {
+            break Err(e);
+        }
+        let edited = fs::read_to_string(&scratch_path)?;
+        match parse_config(&edited) {
+            Ok(_) if edited == original => break Ok(false),
+            Ok(config) => break write_config(&config, config_path).map(|_| true),
+            Err(e) => {
+                eprintln!("{} {}", symbols.warning(), e);
+                if !confirm("Reopen the editor?", true)? {
+                    break Err("Config left unchanged.".into());
+                }
+            }
+        }
+    };
+    let _ = fs::remove_file(&scratch_path);
+
+    if result? {
+        if !output::is_quiet() {
+            println!("{} Saved {}", symbols.success(), config_path.display());
+        }
+    } else if !output::is_quiet() {
+        println!("No changes made.");
+    }
+    Ok(())
+}
+
+/// Reads the global config, with the nearest project config (if any) on top
+fn read_config(config_path: &Path) -> Result<models::Config, Box<dyn Error>> {
+    let Some(local_path) = local_config_path() else {
+        return read_config_file(config_path);
+    };
+    let local = read_local_config(&local_path)?;
+    warn_ignored_project_keys(&local_path, &local);
+    let global = existing_config(read_config_file(config_path))?
+        .unwrap_or_else(|| models::Config::new(String::new()));
+    let merged = models::config::overlay(serde_json::to_value(&global)?, &local);
+    let mut config: models::Config = serde_json::from_value(merged).map_err(|e| {
+        config_error(format!(
+            "Invalid project config {}: {}",
+            local_path.display(),
+            e
+        ))
+    })?;
+    config.project_token = local["token"]
+        .as_str()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(str::to_string);
+    Ok(config)
+}
+
+/// Warns, once per run, about project config keys only the global config may set
+fn warn_ignored_project_keys(local_path: &Path, local: &serde_json::Value) {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    let ignored = models::config::ignored_project_keys(local);
+    if ignored.is_empty() {
+        return;
+    }
+    WARNED.call_once(|| {
+        eprintln!(
+            "{} Ignoring {} in the project config {}: only the global config can set {}",
+            SymbolSet::detect().warning(),
+            ignored.join(", "),
+            local_path.display(),
+            if ignored.len() == 1 { "it" } else { "them" }
+        );
+    });
+}
+
+/// The nearest `.attio/config.json` from the current directory up
+fn local_config_path() -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    models::config::find_local_config(&cwd)
+}
+
+/// Where `--local` writes: the nearest project config, or a new one in the
+/// current directory
+fn local_config_target() -> Result<PathBuf, Box<dyn Error>> {
+    match local_config_path() {
+        Some(path) => Ok(path),
+        None => Ok(env::current_dir()?
+            .join(models::config::LOCAL_CONFIG_DIR)
+            .join("config.json")),
+    }
+}
+
+/// The keys a project config sets, as JSON
+fn read_local_config(path: &Path) -> Result<serde_json::Value, Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+    match serde_json::from_str(&content) {
+        Ok(value @ serde_json::Value::Object(_)) => Ok(value),
+        Ok(_) => Err(config_error(format!(
+            "Invalid project config {}: expected an object",
+            path.display()
+        ))),
+        Err(e) => Err(config_error(format!(
+            "Invalid project config {}: {}",
+            path.display(),
+            e
+        ))),
+    }
 }
 
-fn read_config() -> Result<models::Config, Box<dyn Error>> {
-    let config_path = get_config_path();
+/// Reads just the config file at `config_path`
+fn read_config_file(config_path: &Path) -> Result<models::Config, Box<dyn Error>> {
     if config_path.exists() {
-        let content = fs::read_to_string(&config_path)?;
+        let content = fs::read_to_string(config_path)?;
         // Try to parse as new Config format
-        if let Ok(config) = serde_json::from_str::<models::Config>(&content) {
-            return Ok(config);
-        }
+        let parse_error = match serde_json::from_str::<models::Config>(&content) {
+            Ok(config) => return Ok(config),
+            Err(e) => e,
+        };
         // Fallback: try old format (just token as string or in object)
         if let Ok(data) = serde_json::from_str::<serde_json::Value>(&content)
             && let Some(token) = data["token"].as_str()
         {
             return Ok(models::Config::new(token.to_string()));
         }
+        return Err(config_error(format!(
+            "Invalid config {}: {}",
+            config_path.display(),
+            parse_error
+        )));
     }
     Err("Config file not found".into())
 }
 
-fn write_config(config: &models::Config) -> Result<(), Box<dyn Error>> {
-    let config_path = get_config_path();
+/// Saves `config` to the global file. Values that came from a project config
+/// stay out of it.
+fn write_config(config: &models::Config, config_path: &Path) -> Result<(), Box<dyn Error>> {
+    let content = match local_config_path() {
+        Some(local_path) => {
+            let local = read_local_config(&local_path)?;
+            let global = fs::read_to_string(config_path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_else(|| serde_json::json!({}));
+            let value =
+                models::config::without_overrides(serde_json::to_value(config)?, &global, &local);
+            serde_json::to_string_pretty(&value)?
+        }
+        None => serde_json::to_string_pretty(&config)?,
+    };
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent)?;
     }
-    fs::write(config_path, serde_json::to_string_pretty(&config)?)?;
+    fsutil::write_private(config_path, content.as_bytes())?;
     Ok(())
 }
 
-fn get_token() -> Result<String, Box<dyn Error>> {
-    // 1. Check config file first
-    if let Ok(config) = read_config() {
-        let token = config.token.trim();
-        if !token.is_empty() {
-            return Ok(token.to_string());
-        }
+/// Saves the parts of `config` that differ from the global config to the
+/// project config at `local_path`
+fn write_local_config(
+    config: &models::Config,
+    config_path: &Path,
+    local_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let local = if local_path.exists() {
+        read_local_config(local_path)?
+    } else {
+        serde_json::json!({})
+    };
+    let global =
+        read_config_file(config_path).unwrap_or_else(|_| models::Config::new(String::new()));
+    let overrides = models::config::local_overrides(
+        &serde_json::to_value(config)?,
+        &serde_json::to_value(&global)?,
+        &local,
+    );
+    if let Some(parent) = local_path.parent() {
+        fs::create_dir_all(parent)?;
     }
+    fsutil::write_private(
+        local_path,
+        serde_json::to_string_pretty(&overrides)?.as_bytes(),
+    )?;
+    Ok(())
+}
 
-    // 2. Fallback to environment variable
-    if let Ok(token) = env::var("ATTIO_API_TOKEN") {
-        let token = token.trim();
-        if !token.is_empty() {
-            return Ok(token.to_string());
+fn run_profile_command(
+    action: ProfileCommands,
+    config_path: &Path,
+    symbols: SymbolSet,
+) -> Result<(), Box<dyn Error>> {
+    let mut config =
+        read_config(config_path).unwrap_or_else(|_| models::Config::new(String::new()));
+    match action {
+        ProfileCommands::Add {
+            name,
+            token,
+            cache_limit_mb,
+        } => {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err("Profile name cannot be empty.".into());
+            }
+            if config.profiles.contains_key(&name) {
+                return Err(format!("Profile '{}' already exists.", name).into());
+            }
+            let mut token = match token {
+                Some(token) => token,
+                None => stored_token(&config)?.0,
+            }
+            .trim()
+            .to_string();
+            if token.is_empty() {
+                return Err("No token to store. Pass one with --token <token>.".into());
+            }
+            if config.token_storage == models::TokenStorage::Keyring {
+                secrets::store_token(&secrets::account(Some(&name)), &token)?;
+                token.clear();
+            }
+            config.profiles.insert(
+                name.clone(),
+                models::Profile {
+                    token,
+                    cache_limit_mb,
+                },
+            );
+            write_config(&config, config_path)?;
+            if !output::is_quiet() {
+                println!(
+                    "{} Added profile '{}'. Switch to it with `attio config profile use {}`.",
+                    symbols.success(),
+                    name,
+                    name
+                );
+            }
+        }
+        ProfileCommands::Use { name } => {
+            config.use_profile(&name)?;
+            write_config(&config, config_path)?;
+            if !output::is_quiet() {
+                println!("{} Now using profile '{}'", symbols.success(), name);
+            }
+        }
+        ProfileCommands::List => {
+            if config.profiles.is_empty() {
+                println!("No profiles configured. Add one with `attio config profile add <name>`.");
+                return Ok(());
+            }
+            let mut table = comfy_table::Table::new();
+            table
+                .set_header(vec!["", "Profile", "Cache limit (MB)"])
+                .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+            for (name, profile) in &config.profiles {
+                let active = config.active_profile.as_deref() == Some(name.as_str());
+                table.add_row(vec![
+                    if active { "*" } else { "" }.to_string(),
+                    name.clone(),
+                    profile
+                        .cache_limit_mb
+                        .unwrap_or(config.cache_limit_mb)
+                        .to_string(),
+                ]);
+            }
+            println!("{table}");
         }
     }
+    Ok(())
+}
 
-    Err("Not authenticated. Please run `attio auth <token>`.".into())
+/// Builds an API client honoring the base URL, compression, timeout, retry and
+/// circuit breaker settings
+/// `timeout` is the `--timeout` flag, which wins over `timeout-secs`
+fn build_client(
+    token: String,
+    config: &models::Config,
+    timeout: Option<u64>,
+) -> Result<AttioClient, Box<dyn Error>> {
+    let options = client::ClientOptions {
+        compression: config.compression,
+        timeout: request_timeout(timeout, config),
+    };
+    let client = AttioClient::with_options(token, options)?
+        .with_base_url(&config.base_url)
+        .with_retry_policy(retry::RetryPolicy::from_config(config))
+        .with_circuit_breaker(retry::CircuitBreaker::from_config(config));
+    Ok(match trace::global() {
+        Some(tracer) => client.with_trace(tracer),
+        None => client,
+    })
 }
 
-fn get_config() -> Result<models::Config, Box<dyn Error>> {
-    read_config()
+/// The request timeout: `--timeout` if given, else `timeout-secs`. Zero means
+/// no limit.
+fn request_timeout(flag: Option<u64>, config: &models::Config) -> Option<std::time::Duration> {
+    let secs = flag.unwrap_or(config.timeout_secs);
+    (secs > 0).then(|| std::time::Duration::from_secs(secs))
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
-    dotenv().ok();
-    let cli = Cli::parse();
+/// TUI settings from the config, showing every note. `fresh` skips
+/// reopening where the last session left off.
+fn tui_options(
+    config: &models::Config,
+    config_path: &Path,
+    token: &str,
+    notify_mode: notify::NotifyMode,
+    dry_run: bool,
+    fresh: bool,
+) -> Result<tui::ListOptions, Box<dyn Error>> {
+    let state_path = tui::state_path(config_path);
+    Ok(tui::ListOptions {
+        cache_limit_mb: config.effective_cache_limit_mb(),
+        cache_enabled: config.tui_cache_enabled(),
+        content_filter: filter::ContentFilter::All,
+        initial_search: None,
+        dedupe: true,
+        notify_mode,
+        fetch_delay: std::time::Duration::from_millis(config.fetch_delay_ms),
+        fetch_concurrency: config.fetch_concurrency,
+        fetch_all_max: (config.fetch_all_max > 0).then_some(config.fetch_all_max),
+        rate_limit_max_wait: std::time::Duration::from_millis(config.retry_max_delay_ms),
+        dry_run,
+        create_defaults: template::CreateDefaults {
+            parent_object: config.default_parent_object.clone(),
+            parent_record_id: config.default_parent_record_id.clone(),
+        },
+        mouse: config.mouse,
+        theme: tui::theme::Theme::with_overrides(config.theme, &config.theme_colors)?,
+        refresh_interval: (config.refresh_interval_secs > 0)
+            .then(|| std::time::Duration::from_secs(config.refresh_interval_secs)),
+        saved_state: (!fresh).then(|| tui::SavedState::load(&state_path)),
+        state_path,
+        cache_path: cache::disk_cache_path(token),
+    })
+}
 
-    match cli.command {
-        Commands::Auth { token } => {
-            let trimmed_token = token.trim().to_string();
-            let config = if let Ok(mut existing_config) = read_config() {
-                existing_config.token = trimmed_token;
-                existing_config
-            } else {
-                models::Config::new(trimmed_token)
-            };
-            write_config(&config)?;
-            println!(
-                "✅ Successfully authenticated! Token saved to {:?}",
-                get_config_path()
-            );
+/// Asks before `notes delete-many` deletes `count` notes. Without a
+/// terminal to ask on, `--yes` is required.
+fn confirm_delete_many(count: usize) -> Result<(), Box<dyn Error>> {
+    if !std::io::stdin().is_terminal() {
+        return Err(format!(
+            "Refusing to delete {} notes without confirmation. Pass --yes to go ahead.",
+            count
+        )
+        .into());
+    }
+    if !confirm(&format!("Delete {} notes?", count), false)? {
+        return Err("Cancelled, nothing was deleted.".into());
+    }
+    Ok(())
+}
+
+/// Deletes each of `note_ids`, `delay` apart, printing a line per note and
+/// continuing past failures
+async fn delete_notes(
+    client: &AttioClient,
+    note_ids: Vec<String>,
+    delay: std::time::Duration,
+    symbols: SymbolSet,
+) -> batch::DeleteSummary {
+    let total = note_ids.len();
+    let mut deleted = 0;
+    let mut failures = Vec::new();
+    let bar = progress::bar(total as u64, "Deleting");
+    for (index, note_id) in note_ids.into_iter().enumerate() {
+        // Space requests out like paged fetches do, to stay under rate limits
+        if index > 0 {
+            tokio::time::sleep(delay).await;
         }
-        Commands::Config { action } => match action {
-            ConfigCommands::Set { key, value } => {
-                let mut config = read_config().unwrap_or_else(|_| {
-                    eprintln!("⚠️  No config found. Creating new config...");
-                    models::Config::new(String::new())
+        match client.delete_note(&note_id).await {
+            Ok(()) => {
+                deleted += 1;
+                bar.suspend(|| {
+                    eprintln!(
+                        "[{}/{}] {} Deleted {}",
+                        index + 1,
+                        total,
+                        symbols.success(),
+                        note_id
+                    )
                 });
-
-                match key.as_str() {
-                    "cache-limit-mb" => {
-                        let limit: u64 = value.parse().map_err(
-                            |_| "Invalid value. cache-limit-mb must be a positive number.",
-                        )?;
-                        config.cache_limit_mb = limit;
-                        write_config(&config)?;
-                        println!("✅ Set cache-limit-mb to {}", limit);
-                    }
-                    _ => {
-                        return Err(format!(
-                            "Unknown config key: {}. Available keys: cache-limit-mb",
-                            key
-                        )
-                        .into());
-                    }
-                }
             }
-            ConfigCommands::Get { key } => {
-                let config = get_config()?;
-                match key.as_str() {
-                    "cache-limit-mb" => {
-                        println!("{}", config.cache_limit_mb);
-                    }
-                    _ => {
-                        return Err(format!(
-                            "Unknown config key: {}. Available keys: cache-limit-mb",
-                            key
-                        )
-                        .into());
-                    }
-                }
+            Err(e) => {
+                bar.suspend(|| {
+                    eprintln!(
+                        "[{}/{}] {} {}: {}",
+                        index + 1,
+                        total,
+                        symbols.warning(),
+                        note_id,
+                        e
+                    )
+                });
+                failures.push(batch::DeleteFailure {
+                    note_id,
+                    error: e.to_string(),
+                });
             }
-            ConfigCommands::List => {
-                let config = get_config()?;
-                let mut table = comfy_table::Table::new();
-                table
-                    .set_header(vec!["Key", "Value"])
-                    .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
-                    .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+        }
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+    batch::DeleteSummary {
+        total,
+        deleted,
+        failures,
+    }
+}
 
-                table.add_row(vec!["token", &config.token]);
-                table.add_row(vec!["cache-limit-mb", &config.cache_limit_mb.to_string()]);
+/// Creates one imported note, returning its ID, or `None` when `dry_run`
+/// only printed the request
+async fn import_note(
+    client: &AttioClient,
+    row: import::ImportRow,
+    dry_run: bool,
+    format: output::OutputFormat,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let request = models::CreateNoteRequest {
+        data: row.into_data()?,
+    };
+    if dry_run {
+        let prepared = DryRunRequest::prepare(client.create_note_request(&request))?;
+        output::print_dry_run(&prepared, format)?;
+        return Ok(None);
+    }
+    let response = client.create_note(request).await?;
+    Ok(Some(response.data.id.note_id))
+}
 
-                println!("{table}");
-            }
-        },
-        Commands::Notes { action } => {
-            let token = get_token()?;
-            let config = get_config().unwrap_or_else(|_| models::Config::new(token.clone()));
-            let client = AttioClient::new(token);
-            match action {
-                NoteCommands::List { plain } => {
-                    if plain {
-                        let response = client.list_notes(None, None).await?;
+/// Prints records as a table: the ID, then `attributes` as columns, or the
+/// name and creation time when none are given
+fn print_records(records: &[models::Record], attributes: &[String]) {
+    if records.is_empty() {
+        println!("No records found.");
+        return;
+    }
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+    if attributes.is_empty() {
+        table.set_header(vec!["ID", "Name", "Created"]);
+        for record in records {
+            table.add_row(vec![
+                record.id.record_id.clone(),
+                record.display_name().unwrap_or_else(|| "-".to_string()),
+                record.created_at.clone(),
+            ]);
+        }
+    } else {
+        let mut header = vec!["ID"];
+        header.extend(attributes.iter().map(String::as_str));
+        table.set_header(header);
+        for record in records {
+            let mut row = vec![record.id.record_id.clone()];
+            row.extend(attributes.iter().map(|attribute| {
+                record
+                    .attribute_text(attribute)
+                    .unwrap_or_else(|| "-".to_string())
+            }));
+            table.add_row(row);
+        }
+    }
+    println!("{table}");
+}
 
-                        let mut table = comfy_table::Table::new();
-                        table
-                            .set_header(vec!["#", "ID", "Title", "Content"])
-                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
-                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+/// Prints one task as an attribute/value table
+fn print_task(task: &models::Task) {
+    let mut table = comfy_table::Table::new();
+    table
+        .set_header(vec!["Attribute", "Value"])
+        .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
 
-                        for (i, note) in response.data.into_iter().enumerate() {
-                            table.add_row(vec![
-                                (i + 1).to_string(),
-                                note.id.note_id,
-                                note.title,
-                                note.content_plaintext,
+    table.add_row(vec!["ID", &task.id.task_id]);
+    table.add_row(vec!["Done", task.checkbox()]);
+    table.add_row(vec!["Content", &task.content_plaintext]);
+    table.add_row(vec!["Deadline", task.deadline_at.as_deref().unwrap_or("-")]);
+    for link in &task.linked_records {
+        table.add_row(vec![
+            "Linked",
+            &format!("{} {}", link.target_object_id, link.target_record_id),
+        ]);
+    }
+    if let Some(created_at) = &task.created_at {
+        table.add_row(vec!["Created", created_at]);
+    }
+
+    println!("{table}");
+}
+
+/// Checks `token` against the API before it is saved, returning the
+/// workspace name
+async fn validate_token(
+    token: String,
+    config: &models::Config,
+    timeout: Option<u64>,
+) -> Result<Option<String>, Box<dyn Error>> {
+    match build_client(token, config, timeout)?.identify().await {
+        Ok(identity) if identity.active => Ok(identity.workspace_name),
+        Ok(_) => Err("This token is no longer active. Nothing was saved.".into()),
+        Err(e)
+            if e.downcast_ref::<error::AttioError>()
+                .is_some_and(|e| e.is_unauthorized()) =>
+        {
+            Err("Attio rejected this token (401 Unauthorized). Nothing was saved.".into())
+        }
+        Err(e) => Err(format!(
+            "Could not validate the token: {}\nNothing was saved. Use --force to save it without checking.",
+            e
+        )
+        .into()),
+    }
+}
+
+/// The workspace slug for `token`. Uses the identity cached in `config` when
+/// it was fetched with the same token; otherwise calls identify and saves the
+/// result (only when a config file exists at `config_path`).
+async fn get_or_fetch_slug(
+    client: &AttioClient,
+    config: &mut models::Config,
+    config_path: &Path,
+    token: &str,
+    refresh: bool,
+) -> Result<Option<String>, Box<dyn Error>> {
+    if !refresh && let Some(identity) = config.cached_identity(token) {
+        return Ok(identity.workspace_slug.clone());
+    }
+    let identity = identify_cached(client, config, config_path, token).await?;
+    Ok(identity.workspace_slug)
+}
+
+/// Calls identify for `token`, sending the ETag of the identity cached in
+/// `config` so an unchanged one comes back as an empty 304 and the cached
+/// response is reused. A new response is saved (only when a config file
+/// exists at `config_path`).
+async fn identify_cached(
+    client: &AttioClient,
+    config: &mut models::Config,
+    config_path: &Path,
+    token: &str,
+) -> Result<models::IdentifyResponse, Box<dyn Error>> {
+    let cached = config
+        .cached_identity(token)
+        .and_then(|cached| Some((cached.etag.clone()?, cached.response.clone()?)));
+    let etag = cached.as_ref().map(|(etag, _)| etag.as_str());
+    match (client.identify_if_none_match(etag).await?, cached) {
+        (client::Conditional::NotModified, Some((_, identity))) => {
+            log::debug!("Identity unchanged (304), using the cached response");
+            Ok(identity)
+        }
+        (client::Conditional::NotModified, None) => {
+            Err("Attio answered 304 to an unconditional request".into())
+        }
+        (client::Conditional::Modified { value, etag }, _) => {
+            config.cache_identity(token, &value, etag);
+            if config_path.exists() {
+                write_config(config, config_path)?;
+            }
+            Ok(value)
+        }
+    }
+}
+
+/// Opens `note` in the browser and returns its link, or `None` when the
+/// workspace slug the link needs is unknown
+async fn open_note(
+    client: &AttioClient,
+    config: &mut models::Config,
+    config_path: &Path,
+    token: &str,
+    refresh: bool,
+    note: &models::Note,
+    symbols: SymbolSet,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let Some(slug) = get_or_fetch_slug(client, config, config_path, token, refresh).await? else {
+        return Ok(None);
+    };
+    let url = browser::note_browser_url(&slug, note);
+    if !output::is_quiet() {
+        eprintln!("{} Opening note in browser...", symbols.link());
+    }
+    if let Err(e) = webbrowser::open(&url) {
+        eprintln!("Failed to open browser: {}", e);
+    }
+    Ok(Some(url))
+}
+
+/// Pages through every note with a spinner counting what has arrived
+async fn fetch_all_notes(client: &AttioClient) -> Result<Vec<models::Note>, Box<dyn Error>> {
+    let spinner = progress::spinner("Fetching notes...");
+    let notes = client
+        .list_all_notes(50, |fetched| {
+            spinner.set_message(format!("Fetching notes... {} so far", fetched))
+        })
+        .await;
+    spinner.finish_and_clear();
+    notes
+}
+
+/// Checks that `object` is an object slug in the workspace
+async fn validate_object(
+    object: &str,
+    config: &models::Config,
+    config_path: &Path,
+    timeout: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    let client = build_client(get_token(config_path)?, config, timeout)?;
+    let slugs: Vec<String> = client
+        .list_objects()
+        .await?
+        .data
+        .into_iter()
+        .filter_map(|o| o.api_slug)
+        .collect();
+    if slugs.iter().any(|slug| slug == object) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown object '{}'. Objects in this workspace: {}",
+            object,
+            slugs.join(", ")
+        )
+        .into())
+    }
+}
+
+fn get_token(config_path: &Path) -> Result<String, Box<dyn Error>> {
+    get_token_with_source(config_path).map(|(token, _)| token)
+}
+
+/// The token to use and where it was found
+fn get_token_with_source(
+    config_path: &Path,
+) -> Result<(String, secrets::TokenSource), Box<dyn Error>> {
+    let config = existing_config(read_config(config_path))?;
+    let priority = config
+        .as_ref()
+        .map(|c| c.token_source_priority)
+        .unwrap_or_default();
+    let env_var = config
+        .as_ref()
+        .map_or(models::config::DEFAULT_TOKEN_ENV_VAR, |c| {
+            c.token_env_var.as_str()
+        });
+    let env_token = env::var(env_var).ok();
+
+    // An unreachable keyring still leaves the file and environment tokens
+    let (config_token, keyring_error) = match config.as_ref().map(stored_token) {
+        Some(Ok(token)) => (Some(token), None),
+        Some(Err(e)) => (
+            config
+                .as_ref()
+                .map(|c| (c.active_token().to_string(), secrets::TokenSource::File)),
+            Some(e),
+        ),
+        None => (None, None),
+    };
+
+    let token = priority
+        .resolve(
+            config_token.as_ref().map(|(token, _)| token.as_str()),
+            env_token.as_deref(),
+        )
+        .ok_or_else(|| match keyring_error {
+            Some(e) => config_error(format!(
+                "{}. Set {} to use a token without the keyring.",
+                e, env_var
+            )),
+            None => error::AttioError::NotAuthenticated {
+                env_var: env_var.to_string(),
+            }
+            .into(),
+        })?;
+    // The config's token only loses to an identical one in the environment
+    // when the environment goes first
+    let env_won = priority == models::TokenSourcePriority::EnvFirst
+        && env_token.as_deref().map(str::trim) == Some(token.as_str());
+    let source = match config_token {
+        Some((config_token, source)) if !env_won && config_token.trim() == token => source,
+        _ => secrets::TokenSource::Env,
+    };
+    Ok((token, source))
+}
+
+/// The active token: the project config's, then the keyring's with
+/// `token-storage keyring`, then the one in the config file, along with
+/// where it was found
+fn stored_token(config: &models::Config) -> Result<(String, secrets::TokenSource), Box<dyn Error>> {
+    let keyring = || match config.token_storage {
+        models::TokenStorage::Keyring => {
+            secrets::read_token(&secrets::account(config.active_profile_name()))
+        }
+        models::TokenStorage::File => Ok(None),
+    };
+    secrets::resolve_token(
+        config.project_token.as_deref(),
+        keyring,
+        config.active_token(),
+    )
+}
+
+/// Moves every token in the global config file into the OS keyring and
+/// switches `token-storage` to keyring
+fn migrate_to_keyring(config_path: &Path, symbols: SymbolSet) -> Result<(), Box<dyn Error>> {
+    let mut config = read_config_file(config_path)
+        .map_err(|_| "No config file to migrate. Run `attio auth <token>` first.")?;
+    let mut moved = 0;
+    if !config.token.trim().is_empty() {
+        secrets::store_token(&secrets::account(None), config.token.trim())?;
+        config.token.clear();
+        moved += 1;
+    }
+    for (name, profile) in config.profiles.iter_mut() {
+        if !profile.token.trim().is_empty() {
+            secrets::store_token(&secrets::account(Some(name)), profile.token.trim())?;
+            profile.token.clear();
+            moved += 1;
+        }
+    }
+    config.token_storage = models::TokenStorage::Keyring;
+    write_config(&config, config_path)?;
+    if !output::is_quiet() {
+        println!(
+            "{} Moved {} token(s) from {} to the OS keyring",
+            symbols.success(),
+            moved,
+            config_path.display()
+        );
+    }
+    Ok(())
+}
+
+fn get_config(config_path: &Path) -> Result<models::Config, Box<dyn Error>> {
+    read_config(config_path)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenv().ok();
+    let cli = Cli::parse();
+    let verbose = cli.verbose > 0;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error::report(e.as_ref(), verbose);
+            ExitCode::from(error::exit_category(e.as_ref()).code())
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let config_path = get_config_path(cli.config.as_deref());
+    let startup_config = read_config(&config_path).ok();
+    logging::init(
+        cli.verbose,
+        startup_config.as_ref().and_then(|c| c.log_file.clone()),
+    );
+    let symbols = cli
+        .symbols
+        .or_else(|| startup_config.as_ref().and_then(|c| c.symbols))
+        .unwrap_or_else(SymbolSet::detect);
+    let notify_mode = cli
+        .notify
+        .or_else(|| startup_config.as_ref().map(|c| c.notify))
+        .unwrap_or_default();
+    let mut timings = timings::Timings::new(cli.timings, symbols);
+    output::set_quiet(cli.quiet);
+    if let Some(path) = &cli.trace {
+        trace::start(path).map_err(|e| format!("Could not open the trace file {}", e))?;
+    }
+
+    match cli.command {
+        Commands::Auth {
+            migrate_to_keyring: true,
+            ..
+        } => migrate_to_keyring(&config_path, symbols)?,
+        Commands::Auth {
+            token,
+            force,
+            local,
+            migrate_to_keyring: false,
+        } => {
+            let trimmed_token = token.unwrap_or_default().trim().to_string();
+            let mut config = if let Ok(mut existing_config) = read_config(&config_path) {
+                existing_config.set_token(trimmed_token.clone());
+                existing_config
+            } else {
+                models::Config::new(trimmed_token.clone())
+            };
+            if !force {
+                let workspace = validate_token(trimmed_token.clone(), &config, cli.timeout).await?;
+                if !output::is_quiet() {
+                    println!(
+                        "{} Authenticated to workspace {}",
+                        symbols.success(),
+                        workspace.as_deref().unwrap_or("(unnamed)")
+                    );
+                }
+            }
+            let saved_to = if local {
+                let local_path = local_config_target()?;
+                write_local_config(&config, &config_path, &local_path)?;
+                format!("{:?}", local_path)
+            } else if config.token_storage == models::TokenStorage::Keyring {
+                let account = secrets::account(config.active_profile_name());
+                secrets::store_token(&account, &trimmed_token)?;
+                // The settings still go to the file, without the token
+                config.set_token(String::new());
+                write_config(&config, &config_path)?;
+                "the OS keyring".to_string()
+            } else {
+                write_config(&config, &config_path)?;
+                format!("{:?}", config_path)
+            };
+            if !output::is_quiet() {
+                println!(
+                    "{} Successfully authenticated! Token saved to {}",
+                    symbols.success(),
+                    saved_to
+                );
+            }
+        }
+        Commands::Config { action } => match action {
+            ConfigCommands::Set { key, value, local } => {
+                if local && !models::config::PROJECT_KEYS.contains(&key.replace('-', "_").as_str())
+                {
+                    return Err(config_error(format!(
+                        "{} can only be set in the global config. Project configs can set: {}",
+                        key,
+                        models::config::PROJECT_KEYS
+                            .iter()
+                            .map(|key| key.replace('_', "-"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )));
+                }
+                let local_path = local.then(local_config_target).transpose()?;
+                let save = |config: &models::Config| match &local_path {
+                    Some(local_path) => write_local_config(config, &config_path, local_path),
+                    None => write_config(config, &config_path),
+                };
+                let mut config = read_config(&config_path).unwrap_or_else(|_| {
+                    eprintln!(
+                        "{} No config found. Creating new config...",
+                        symbols.warning()
+                    );
+                    models::Config::new(String::new())
+                });
+
+                match key.as_str() {
+                    "base-url" => {
+                        config.base_url = client::normalize_base_url(&value)
+                            .map_err(|e| config_error(e.to_string()))?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set base-url to {}", symbols.success(), config.base_url);
+                        }
+                    }
+                    "cache-limit-mb" => {
+                        let limit: u64 = value.parse().map_err(
+                            |_| config_error("Invalid value. cache-limit-mb must be a number (0 turns the cache off)."),
+                        )?;
+                        config.set_cache_limit_mb(limit);
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set cache-limit-mb to {}", symbols.success(), limit);
+                        }
+                    }
+                    "cache-enabled" => {
+                        config.cache_enabled = parse_bool(&key, &value)?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set cache-enabled to {}",
+                                symbols.success(),
+                                config.cache_enabled
+                            );
+                        }
+                    }
+                    "cache-ttl-secs" => {
+                        config.cache_ttl_secs = value.parse().map_err(|_| {
+                            config_error(
+                                "Invalid value. cache-ttl-secs must be a number of seconds.",
+                            )
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set cache-ttl-secs to {}",
+                                symbols.success(),
+                                config.cache_ttl_secs
+                            );
+                        }
+                    }
+                    "max-retries" => {
+                        config.max_retries = value.parse().map_err(|_| {
+                            config_error("Invalid value. max-retries must be a whole number.")
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set max-retries to {}",
+                                symbols.success(),
+                                config.max_retries
+                            );
+                        }
+                    }
+                    "breaker-threshold" => {
+                        config.breaker_threshold = value.parse().map_err(
+                            |_| config_error("Invalid value. breaker-threshold must be a whole number (0 turns the breaker off)."),
+                        )?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set breaker-threshold to {}",
+                                symbols.success(),
+                                config.breaker_threshold
+                            );
+                        }
+                    }
+                    "breaker-cooldown-secs" => {
+                        config.breaker_cooldown_secs = value.parse().map_err(|_| {
+                            config_error(
+                                "Invalid value. breaker-cooldown-secs must be a number of seconds.",
+                            )
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set breaker-cooldown-secs to {}",
+                                symbols.success(),
+                                config.breaker_cooldown_secs
+                            );
+                        }
+                    }
+                    "timeout-secs" => {
+                        config.timeout_secs = value.parse().ok().filter(|secs| *secs > 0).ok_or_else(|| config_error("Invalid value. timeout-secs must be a positive number of seconds."))?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set timeout-secs to {}",
+                                symbols.success(),
+                                config.timeout_secs
+                            );
+                        }
+                    }
+                    "retry-base-delay-ms" => {
+                        config.retry_base_delay_ms = value.parse().map_err(
+                            |_| config_error("Invalid value. retry-base-delay-ms must be a number of milliseconds."),
+                        )?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set retry-base-delay-ms to {}",
+                                symbols.success(),
+                                config.retry_base_delay_ms
+                            );
+                        }
+                    }
+                    "fetch-delay-ms" => {
+                        config.fetch_delay_ms = value.parse().map_err(|_| {
+                            config_error(
+                                "Invalid value. fetch-delay-ms must be a number of milliseconds.",
+                            )
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set fetch-delay-ms to {}",
+                                symbols.success(),
+                                config.fetch_delay_ms
+                            );
+                        }
+                    }
+                    "refresh-interval-secs" => {
+                        config.refresh_interval_secs = value.parse().map_err(
+                            |_| config_error("Invalid value. refresh-interval-secs must be a number of seconds (0 to turn auto-refresh off)."),
+                        )?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set refresh-interval-secs to {}",
+                                symbols.success(),
+                                config.refresh_interval_secs
+                            );
+                        }
+                    }
+                    "fetch-all-max" => {
+                        config.fetch_all_max = value.parse().map_err(
+                            |_| config_error("Invalid value. fetch-all-max must be a number of notes (0 for no cap)."),
+                        )?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set fetch-all-max to {}",
+                                symbols.success(),
+                                config.fetch_all_max
+                            );
+                        }
+                    }
+                    "fetch-concurrency" => {
+                        config.fetch_concurrency = value
+                            .parse()
+                            .ok()
+                            .filter(|n| (1..=MAX_FETCH_CONCURRENCY).contains(n))
+                            .ok_or_else(|| {
+                                config_error(format!(
+                                    "Invalid value. fetch-concurrency must be a number from 1 to {}.",
+                                    MAX_FETCH_CONCURRENCY
+                                ))
+                            })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set fetch-concurrency to {}",
+                                symbols.success(),
+                                config.fetch_concurrency
+                            );
+                        }
+                    }
+                    "token-source-priority" => {
+                        config.token_source_priority = models::TokenSourcePriority::parse(&value)
+                            .ok_or_else(|| {
+                            config_error(format!(
+                                "Invalid value. token-source-priority must be one of: {}",
+                                models::TokenSourcePriority::VARIANTS.join(", ")
+                            ))
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set token-source-priority to {}",
+                                symbols.success(),
+                                value
+                            );
+                        }
+                    }
+                    "token-storage" => {
+                        config.token_storage =
+                            models::TokenStorage::parse(&value).ok_or_else(|| {
+                                config_error(format!(
+                                    "Invalid value. token-storage must be one of: {}",
+                                    models::TokenStorage::VARIANTS.join(", ")
+                                ))
+                            })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set token-storage to {}", symbols.success(), value);
+                            if config.token_storage == models::TokenStorage::Keyring
+                                && !config.active_token().is_empty()
+                            {
+                                eprintln!(
+                                    "{} The token is still in the config file. Move it with `attio auth --migrate-to-keyring`.",
+                                    symbols.warning()
+                                );
+                            }
+                        }
+                    }
+                    "token-env-var" => {
+                        let name = value.trim();
+                        if name.is_empty() || name.contains('=') {
+                            return Err(config_error(
+                                "Invalid value. token-env-var must be an environment variable name.",
+                            ));
+                        }
+                        config.token_env_var = name.to_string();
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set token-env-var to {}", symbols.success(), name);
+                        }
+                    }
+                    "symbols" => {
+                        config.symbols = match value.as_str() {
+                            "auto" => None,
+                            other => Some(SymbolSet::parse(other).ok_or_else(|| {
+                                config_error(format!(
+                                    "Invalid value. symbols must be one of: auto, {}",
+                                    SymbolSet::VARIANTS.join(", ")
+                                ))
+                            })?),
+                        };
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set symbols to {}", symbols.success(), value);
+                        }
+                    }
+                    "notify" => {
+                        config.notify = notify::NotifyMode::parse(&value).ok_or_else(|| {
+                            config_error(format!(
+                                "Invalid value. notify must be one of: {}",
+                                notify::NotifyMode::VARIANTS.join(", ")
+                            ))
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set notify to {}", symbols.success(), value);
+                        }
+                    }
+                    "compression" => {
+                        config.compression = parse_bool(&key, &value)?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set compression to {}",
+                                symbols.success(),
+                                config.compression
+                            );
+                        }
+                    }
+                    "mouse" => {
+                        config.mouse = parse_bool(&key, &value)?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set mouse to {}", symbols.success(), config.mouse);
+                        }
+                    }
+                    "theme" => {
+                        config.theme = models::ThemeName::parse(&value).ok_or_else(|| {
+                            config_error(format!(
+                                "Invalid value. theme must be one of: {}",
+                                models::ThemeName::VARIANTS.join(", ")
+                            ))
+                        })?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!("{} Set theme to {}", symbols.success(), value);
+                        }
+                    }
+                    "theme-colors" => {
+                        config.theme_colors =
+                            tui::theme::parse_overrides(&value).map_err(config_error)?;
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set theme-colors to {}",
+                                symbols.success(),
+                                tui::theme::format_overrides(&config.theme_colors)
+                            );
+                        }
+                    }
+                    "default-parent-object" => {
+                        config.default_parent_object = match value.as_str() {
+                            "none" => None,
+                            object => {
+                                validate_object(object, &config, &config_path, cli.timeout).await?;
+                                Some(object.to_string())
+                            }
+                        };
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set default-parent-object to {}",
+                                symbols.success(),
+                                value
+                            );
+                        }
+                    }
+                    "log-file" => {
+                        config.log_file = match value.as_str() {
+                            "default" => None,
+                            path => Some(PathBuf::from(path)),
+                        };
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set log-file to {}",
+                                symbols.success(),
+                                config.effective_log_file().display()
+                            );
+                        }
+                    }
+                    "default-parent-record-id" => {
+                        config.default_parent_record_id = match value.as_str() {
+                            "none" => None,
+                            id if resolve::is_record_id(id) => Some(id.to_string()),
+                            _ => {
+                                return Err(config_error(
+                                    "Invalid value. default-parent-record-id must be a record ID or none.",
+                                ));
+                            }
+                        };
+                        save(&config)?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Set default-parent-record-id to {}",
+                                symbols.success(),
+                                value
+                            );
+                        }
+                    }
+                    _ => return Err(unknown_config_key(&key)),
+                }
+            }
+            ConfigCommands::Get { key } => {
+                let config = get_config(&config_path)?;
+                match key.as_str() {
+                    "cache-limit-mb" => {
+                        println!("{}", config.effective_cache_limit_mb());
+                    }
+                    "base-url" => println!("{}", config.base_url),
+                    "cache-enabled" => println!("{}", config.cache_enabled),
+                    "cache-ttl-secs" => println!("{}", config.cache_ttl_secs),
+                    "max-retries" => println!("{}", config.max_retries),
+                    "timeout-secs" => println!("{}", config.timeout_secs),
+                    "retry-base-delay-ms" => println!("{}", config.retry_base_delay_ms),
+                    "breaker-threshold" => println!("{}", config.breaker_threshold),
+                    "breaker-cooldown-secs" => println!("{}", config.breaker_cooldown_secs),
+                    "fetch-delay-ms" => println!("{}", config.fetch_delay_ms),
+                    "refresh-interval-secs" => println!("{}", config.refresh_interval_secs),
+                    "fetch-concurrency" => println!("{}", config.fetch_concurrency),
+                    "fetch-all-max" => println!("{}", config.fetch_all_max),
+                    "log-file" => println!("{}", config.effective_log_file().display()),
+                    "token-source-priority" => {
+                        println!("{}", config.token_source_priority.as_str())
+                    }
+                    "token-env-var" => println!("{}", config.token_env_var),
+                    "token-storage" => println!("{}", config.token_storage.as_str()),
+                    "symbols" => {
+                        println!("{}", config.symbols.map(|s| s.as_str()).unwrap_or("auto"))
+                    }
+                    "notify" => println!("{}", config.notify.as_str()),
+                    "compression" => println!("{}", config.compression),
+                    "mouse" => println!("{}", config.mouse),
+                    "theme" => println!("{}", config.theme.as_str()),
+                    "theme-colors" => {
+                        println!("{}", tui::theme::format_overrides(&config.theme_colors))
+                    }
+                    "default-parent-object" => println!(
+                        "{}",
+                        config.default_parent_object.as_deref().unwrap_or("none")
+                    ),
+                    "default-parent-record-id" => println!(
+                        "{}",
+                        config.default_parent_record_id.as_deref().unwrap_or("none")
+                    ),
+                    _ => return Err(unknown_config_key(&key)),
+                }
+            }
+            ConfigCommands::List => {
+                let config = get_config(&config_path)?;
+                let mut table = comfy_table::Table::new();
+                table
+                    .set_header(vec!["Key", "Value"])
+                    .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                    .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                table.add_row(vec![
+                    "profile",
+                    config.active_profile.as_deref().unwrap_or("none"),
+                ]);
+                let token = match config.token_storage {
+                    models::TokenStorage::Keyring if config.active_token().is_empty() => {
+                        "(in the OS keyring)"
+                    }
+                    _ => config.active_token(),
+                };
+                table.add_row(vec!["token", token]);
+                table.add_row(vec!["base-url", &config.base_url]);
+                table.add_row(vec![
+                    "cache-limit-mb",
+                    &config.effective_cache_limit_mb().to_string(),
+                ]);
+                table.add_row(vec!["cache-enabled", &config.cache_enabled.to_string()]);
+                table.add_row(vec!["cache-ttl-secs", &config.cache_ttl_secs.to_string()]);
+                table.add_row(vec!["max-retries", &config.max_retries.to_string()]);
+                table.add_row(vec!["timeout-secs", &config.timeout_secs.to_string()]);
+                table.add_row(vec![
+                    "retry-base-delay-ms",
+                    &config.retry_base_delay_ms.to_string(),
+                ]);
+                table.add_row(vec![
+                    "breaker-threshold",
+                    &config.breaker_threshold.to_string(),
+                ]);
+                table.add_row(vec![
+                    "breaker-cooldown-secs",
+                    &config.breaker_cooldown_secs.to_string(),
+                ]);
+                table.add_row(vec!["fetch-delay-ms", &config.fetch_delay_ms.to_string()]);
+                table.add_row(vec![
+                    "refresh-interval-secs",
+                    &config.refresh_interval_secs.to_string(),
+                ]);
+                table.add_row(vec![
+                    "fetch-concurrency",
+                    &config.fetch_concurrency.to_string(),
+                ]);
+                table.add_row(vec!["fetch-all-max", &config.fetch_all_max.to_string()]);
+                table.add_row(vec![
+                    "log-file",
+                    &config.effective_log_file().display().to_string(),
+                ]);
+                table.add_row(vec![
+                    "token-source-priority",
+                    config.token_source_priority.as_str(),
+                ]);
+                table.add_row(vec!["token-env-var", &config.token_env_var]);
+                table.add_row(vec!["token-storage", config.token_storage.as_str()]);
+                table.add_row(vec![
+                    "symbols",
+                    config.symbols.map(|s| s.as_str()).unwrap_or("auto"),
+                ]);
+                table.add_row(vec!["notify", config.notify.as_str()]);
+                table.add_row(vec!["compression", &config.compression.to_string()]);
+                table.add_row(vec!["mouse", &config.mouse.to_string()]);
+                table.add_row(vec!["theme", config.theme.as_str()]);
+                table.add_row(vec![
+                    "theme-colors",
+                    &tui::theme::format_overrides(&config.theme_colors),
+                ]);
+                table.add_row(vec![
+                    "default-parent-object",
+                    config.default_parent_object.as_deref().unwrap_or("none"),
+                ]);
+                table.add_row(vec![
+                    "default-parent-record-id",
+                    config.default_parent_record_id.as_deref().unwrap_or("none"),
+                ]);
+
+                println!("{table}");
+            }
+            ConfigCommands::Edit => edit_config(&config_path, symbols)?,
+            ConfigCommands::Profile { action } => {
+                run_profile_command(action, &config_path, symbols)?
+            }
+            ConfigCommands::Unset { key } => {
+                let mut config = read_config(&config_path)
+                    .unwrap_or_else(|_| models::Config::new(String::new()));
+                if !config.unset(&key) {
+                    return Err(unknown_config_key(&key));
+                }
+                write_config(&config, &config_path)?;
+                if !output::is_quiet() {
+                    println!("{} Reset {} to its default", symbols.success(), key);
+                }
+            }
+            ConfigCommands::Reset => {
+                // Only the global file is rewritten; a project config keeps its overrides
+                let config = read_config_file(&config_path)
+                    .unwrap_or_else(|_| models::Config::new(String::new()));
+                if let Some(parent) = config_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fsutil::write_private(
+                    &config_path,
+                    serde_json::to_string_pretty(&config.reset())?.as_bytes(),
+                )?;
+                if !output::is_quiet() {
+                    println!(
+                        "{} Reset {} to defaults",
+                        symbols.success(),
+                        config_path.display()
+                    );
+                    if let Some(local_path) = local_config_path() {
+                        eprintln!(
+                            "{} Still overridden by the project config {}",
+                            symbols.file(),
+                            local_path.display()
+                        );
+                    }
+                }
+            }
+            ConfigCommands::Path => {
+                println!("{}", config_path.display());
+                if let Some(local_path) = local_config_path()
+                    && !output::is_quiet()
+                {
+                    eprintln!(
+                        "{} Overridden by the project config {}",
+                        symbols.file(),
+                        local_path.display()
+                    );
+                }
+            }
+        },
+        Commands::Notes { action } => {
+            let token = get_token(&config_path)?;
+            let mut config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token.clone(), &config, cli.timeout)?;
+            match action {
+                NoteCommands::List {
+                    plain,
+                    fields,
+                    distinct_parents,
+                    resolve_names,
+                    only_empty,
+                    only_nonempty,
+                    contains,
+                    scope,
+                    since,
+                    until,
+                    no_dedupe,
+                    watch_record,
+                    interval,
+                    json_lines,
+                    limit,
+                    page,
+                    cache_only,
+                    fresh,
+                    parent_object,
+                    parent_record_id,
+                } => {
+                    let content_filter =
+                        filter::ContentFilter::from_flags(only_empty, only_nonempty);
+                    let date_range = filter::DateRange::new(since, until)?;
+                    let parent = parent_object.as_deref().zip(parent_record_id.as_deref());
+                    // The API narrows every page; --cache-only filters locally
+                    let client = match parent {
+                        Some((object, record_id)) => client.with_note_parent(object, record_id),
+                        None => client,
+                    };
+                    let matches = |note: &models::Note| {
+                        content_filter.matches(note)
+                            && date_range.matches(note)
+                            && parent.is_none_or(|(object, record_id)| {
+                                note.parent_object == object && note.parent_record_id == record_id
+                            })
+                            && contains
+                                .as_deref()
+                                .is_none_or(|query| filter::note_matches(note, query, scope))
+                    };
+                    let cached_notes = if cache_only {
+                        let cached = cache::load_for_listing(&token)?;
+                        let age = std::time::Duration::from_secs(
+                            cache::unix_now().saturating_sub(cached.saved_at),
+                        );
+                        if !output::is_quiet() {
+                            eprintln!(
+                                "{} Serving {} notes from cache ({} old).",
+                                symbols.file(),
+                                cached.notes.len(),
+                                cache::format_age(age)
+                            );
+                        }
+                        Some(cached.notes)
+                    } else {
+                        None
+                    };
+                    if json_lines {
+                        let started = std::time::Instant::now();
+                        output::stream_json_lines(
+                            &client,
+                            limit,
+                            |note| matches(note),
+                            &mut std::io::stdout(),
+                        )
+                        .await?;
+                        timings.record("stream notes", started.elapsed());
+                    } else if let Some(watch_record) = watch_record {
+                        let spec = resolve::ParentSpec::parse(&watch_record)?;
+                        let record_id = resolve::RecordResolver::new()
+                            .resolve(&client, &spec)
+                            .await?;
+                        watch::watch_record(
+                            &client,
+                            &spec.object,
+                            &record_id,
+                            std::time::Duration::from_secs(interval),
+                            content_filter,
+                            symbols,
+                        )
+                        .await?;
+                    } else if distinct_parents {
+                        let mut notes = match cached_notes {
+                            Some(notes) => notes,
+                            None => {
+                                let started = std::time::Instant::now();
+                                let notes = fetch_all_notes(&client).await?;
+                                timings.record("fetch all notes", started.elapsed());
+                                let body = format!("Fetched {} notes.", notes.len());
+                                tokio::task::spawn_blocking(move || {
+                                    notify_mode.notify("Attio notes fetched", &body)
+                                })
+                                .await?;
+                                notes
+                            }
+                        };
+                        notes.retain(|note| matches(note));
+
+                        let mut summaries = report::distinct_parents(&notes);
+                        if resolve_names {
+                            for summary in summaries.iter_mut() {
+                                // A missing or inaccessible record shouldn't abort the report
+                                summary.name = client
+                                    .get_record(&summary.parent_object, &summary.parent_record_id)
+                                    .await
+                                    .ok()
+                                    .and_then(|r| r.data.display_name());
+                            }
+                        }
+
+                        if cli.output.is_json() {
+                            output::print_json(&summaries)?;
+                        } else {
+                            let mut table = comfy_table::Table::new();
+                            let mut header = vec!["Object", "Record ID"];
+                            if resolve_names {
+                                header.push("Name");
+                            }
+                            header.push("Notes");
+                            table
+                                .set_header(header)
+                                .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                                .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                            for summary in summaries {
+                                let mut row = vec![summary.parent_object, summary.parent_record_id];
+                                if resolve_names {
+                                    row.push(summary.name.unwrap_or_default());
+                                }
+                                row.push(summary.note_count.to_string());
+                                table.add_row(row);
+                            }
+
+                            println!("{table}");
+                            println!("{} notes across {} records", notes.len(), table.row_count());
+                        }
+                    } else if plain
+                        || !fields.is_empty()
+                        || page.is_some()
+                        || limit.is_some()
+                        || cache_only
+                        || cli.output.is_json()
+                        || date_range.is_set()
+                    {
+                        let paging = output::Paging::new(page, limit);
+                        // JSON is for scripts, so it holds every note unless a
+                        // page is asked for
+                        let paged = page.is_some() || limit.is_some() || !cli.output.is_json();
+                        let paged_by_api = paged && cached_notes.is_none() && !date_range.is_set();
+                        let mut more = false;
+                        let mut notes = match cached_notes {
+                            Some(notes) => notes,
+                            None if paged_by_api => {
+                                let started = std::time::Instant::now();
+                                let response = client
+                                    .list_notes(
+                                        Some(paging.size as u32),
+                                        Some(paging.offset() as u32),
+                                    )
+                                    .await?;
+                                timings.record("list notes", started.elapsed());
+                                // A full page means there may be more after it. This
+                                // counts the page as the API sent it, before the
+                                // filters below drop anything.
+                                more = response.data.len() == paging.size;
+                                response.data
+                            }
+                            // A date window can span any page, so check every note
+                            None => {
+                                let started = std::time::Instant::now();
+                                let notes = fetch_all_notes(&client).await?;
+                                timings.record("fetch all notes", started.elapsed());
+                                notes
+                            }
+                        };
+                        notes.retain(|note| matches(note));
+                        // Notes already in memory are only paged when asked to
+                        let mut total = None;
+                        if !paged_by_api && (page.is_some() || limit.is_some()) {
+                            total = Some(notes.len());
+                            more = paging.offset() + paging.size < notes.len();
+                            notes = notes
+                                .into_iter()
+                                .skip(paging.offset())
+                                .take(paging.size)
+                                .collect();
+                        }
+                        let footer = (paged_by_api || total.is_some())
+                            .then(|| paging.footer(notes.len(), total, more));
+
+                        if cli.output.is_json() && !fields.is_empty() {
+                            let data = notes
+                                .iter()
+                                .map(|note| fields::project(note, &fields))
+                                .collect::<Result<Vec<_>, _>>()?;
+                            output::print_json(&serde_json::json!({ "data": data }))?;
+                        } else if cli.output.is_json() {
+                            output::print_json(&models::ListNotesResponse {
+                                data: notes,
+                                next_cursor: None,
+                                total_count: total,
+                            })?;
+                        } else {
+                            let columns = if fields.is_empty() {
+                                fields::NoteField::DEFAULT.to_vec()
+                            } else {
+                                fields
+                            };
+                            let mut header = vec!["#"];
+                            header.extend(columns.iter().map(|field| field.header()));
+                            let mut table = comfy_table::Table::new();
+                            table
+                                .set_header(header)
+                                .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                                .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                            // Number rows by their position across pages
+                            let skipped = if footer.is_some() { paging.offset() } else { 0 };
+                            for (i, note) in notes.iter().enumerate() {
+                                let mut row = vec![(skipped + i + 1).to_string()];
+                                row.extend(columns.iter().map(|field| field.value(note)));
+                                table.add_row(row);
+                            }
+
+                            println!("{table}");
+                            if let Some(footer) = footer {
+                                println!("{}", footer);
+                            }
+                        }
+                    } else {
+                        let options = tui::ListOptions {
+                            content_filter,
+                            initial_search: contains.map(|query| (query, scope)),
+                            dedupe: !no_dedupe,
+                            ..tui_options(
+                                &config,
+                                &config_path,
+                                &token,
+                                notify_mode,
+                                cli.dry_run,
+                                fresh,
+                            )?
+                        };
+                        tui::run_list_tui(client, options).await?;
+                    }
+                }
+                NoteCommands::Pick { contains, scope } => {
+                    let options = tui::ListOptions {
+                        initial_search: contains.map(|query| (query, scope)),
+                        ..tui_options(
+                            &config,
+                            &config_path,
+                            &token,
+                            notify_mode,
+                            cli.dry_run,
+                            true,
+                        )?
+                    };
+                    match tui::run_pick_tui(client, options).await? {
+                        Some(note_id) => println!("{}", note_id),
+                        None => return Err("No note picked".into()),
+                    }
+                }
+                NoteCommands::Search { query, scope } => {
+                    let started = std::time::Instant::now();
+                    let mut notes = fetch_all_notes(&client).await?;
+                    timings.record("fetch all notes", started.elapsed());
+                    let total = notes.len();
+                    notes.retain(|note| filter::note_matches(note, &query, scope));
+
+                    if cli.output.is_json() {
+                        output::print_json(&models::ListNotesResponse {
+                            data: notes,
+                            next_cursor: None,
+                            total_count: None,
+                        })?;
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["#", "ID", "Title", "Content"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                        for (i, note) in notes.iter().enumerate() {
+                            table.add_row(vec![
+                                (i + 1).to_string(),
+                                note.id.note_id.clone(),
+                                note.title.clone(),
+                                note.content_plaintext.clone(),
+                            ]);
+                        }
+
+                        println!("{table}");
+                        println!(
+                            "{} of {} notes match \"{}\" ({})",
+                            notes.len(),
+                            total,
+                            query,
+                            scope.label()
+                        );
+                    }
+                }
+                NoteCommands::Get {
+                    note_id,
+                    open_in_browser,
+                    refresh,
+                } => {
+                    let ttl = std::time::Duration::from_secs(config.cache_ttl_secs);
+                    let (note, cache_age) = cache::get_note(
+                        &client,
+                        cache::disk_cache_path(&token),
+                        &note_id,
+                        ttl,
+                        refresh,
+                        config.cache_limit_bytes(),
+                    )
+                    .await?;
+                    if let Some(age) = cache_age
+                        && !output::is_quiet()
+                    {
+                        eprintln!(
+                            "{} Served from cache ({} old). Use --refresh to fetch the latest.",
+                            symbols.file(),
+                            cache::format_age(age)
+                        );
+                    }
+
+                    if cli.output.is_json() {
+                        output::print_json(&models::GetNoteResponse { data: note.clone() })?;
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Attribute", "Value"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                        table.add_row(vec!["ID", &note.id.note_id]);
+                        table.add_row(vec!["Title", &note.title]);
+                        table.add_row(vec!["Content", &note.content_plaintext]);
+
+                        println!("{table}");
+                    }
+
+                    if open_in_browser
+                        && open_note(
+                            &client,
+                            &mut config,
+                            &config_path,
+                            &token,
+                            cli.refresh_identity,
+                            &note,
+                            symbols,
+                        )
+                        .await?
+                        .is_none()
+                    {
+                        eprintln!(
+                            "{} Could not determine workspace slug to open identification URL.",
+                            symbols.warning()
+                        );
+                    }
+                }
+                NoteCommands::Create {
+                    mut parent_object,
+                    mut parent_record_id,
+                    parent,
+                    parent_from_clipboard,
+                    title,
+                    mut content,
+                    content_file,
+                    content_stdin,
+                    format,
+                    from_template_file,
+                    allow_empty,
+                    verify_parent,
+                    open_in_browser,
+                } => {
+                    if let Some(path) = &content_file {
+                        content = Some(fs::read_to_string(path).map_err(|e| {
+                            format!("Failed to read content file {}: {}", path.display(), e)
+                        })?);
+                    } else if content_stdin {
+                        if std::io::stdin().is_terminal() {
+                            return Err(
+                                "--content-stdin expects piped input, e.g. `cat note.md | attio notes create --content-stdin ...`".into(),
+                            );
+                        }
+                        let mut piped = String::new();
+                        std::io::Read::read_to_string(&mut std::io::stdin(), &mut piped)?;
+                        content = Some(piped);
+                    }
+                    let template = match &from_template_file {
+                        Some(path) => {
+                            let raw = fs::read_to_string(path).map_err(|e| {
+                                format!("Failed to read template {}: {}", path.display(), e)
+                            })?;
+                            Some(template::parse_template(&raw)?)
+                        }
+                        None => None,
+                    };
+                    if let Some(parent) = parent {
+                        let spec = resolve::ParentSpec::parse(&parent)?;
+                        let mut resolver = resolve::RecordResolver::new();
+                        parent_record_id = Some(resolver.resolve(&client, &spec).await?);
+                        parent_object = Some(spec.object);
+                    }
+                    if parent_from_clipboard {
+                        let text = clipboard::read_text()?;
+                        let record = clipboard::extract_record(&text).ok_or(
+                            "The clipboard does not contain a record ID or an Attio record URL.",
+                        )?;
+                        parent_record_id = Some(record.record_id);
+                        parent_object = parent_object.or(record.object);
+                    }
+                    let flags = template::CreateFlags {
+                        parent_object,
+                        parent_record_id,
+                        title,
+                        content,
+                        format,
+                        allow_empty,
+                    };
+                    let defaults = template::CreateDefaults {
+                        parent_object: config.default_parent_object.clone(),
+                        parent_record_id: config.default_parent_record_id.clone(),
+                    };
+                    let params = template::resolve_create_params(flags, template, defaults)?;
+
+                    if let Some(path) = &from_template_file {
+                        let sources = params
+                            .sources
+                            .iter()
+                            .map(|(field, source)| format!("{} ({})", field, source))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if !output::is_quiet() {
+                            eprintln!(
+                                "{} Using template {}: {}",
+                                symbols.file(),
+                                path.display(),
+                                sources
+                            );
+                        }
+                    }
+                    let from_config: Vec<&str> = params
+                        .sources
+                        .iter()
+                        .filter(|(_, source)| *source == template::FieldSource::Config)
+                        .map(|(field, _)| *field)
+                        .collect();
+                    if !from_config.is_empty() && !output::is_quiet() {
+                        eprintln!(
+                            "{} Using config default for {}: {}/{}",
+                            symbols.file(),
+                            from_config.join(", "),
+                            params.parent_object,
+                            params.parent_record_id
+                        );
+                    }
+
+                    if verify_parent {
+                        resolve::ParentVerifier::new()
+                            .verify(&client, &params.parent_object, &params.parent_record_id)
+                            .await?;
+                    }
+
+                    let request = crate::models::CreateNoteRequest {
+                        data: crate::models::CreateNoteData {
+                            parent_object: params.parent_object,
+                            parent_record_id: params.parent_record_id,
+                            title: params.title,
+                            content: params.content,
+                            format: params.format,
+                        },
+                    };
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.create_note_request(&request))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        let response = client.create_note(request).await?;
+                        if cli.output.is_json() {
+                            output::print_json(&response)?;
+                        } else {
+                            let note = &response.data;
+                            if !output::is_quiet() {
+                                println!("{} Note created successfully!", symbols.success());
+                            }
+
+                            let mut table = comfy_table::Table::new();
+                            table
+                                .set_header(vec!["Attribute", "Value"])
+                                .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                                .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                            table.add_row(vec!["ID", &note.id.note_id]);
+                            table.add_row(vec!["Title", &note.title]);
+                            table.add_row(vec!["Content", &note.content_plaintext]);
+
+                            println!("{table}");
+                        }
+                        let note = response.data;
+
+                        if open_in_browser
+                            && open_note(
+                                &client,
+                                &mut config,
+                                &config_path,
+                                &token,
+                                cli.refresh_identity,
+                                &note,
+                                symbols,
+                            )
+                            .await?
+                            .is_none()
+                        {
+                            eprintln!(
+                                "{} Could not determine workspace slug to open identification URL.",
+                                symbols.warning()
+                            );
+                        }
+                    }
+                }
+                NoteCommands::Open { note_id } => {
+                    let ttl = std::time::Duration::from_secs(config.cache_ttl_secs);
+                    let (note, _) = cache::get_note(
+                        &client,
+                        cache::disk_cache_path(&token),
+                        &note_id,
+                        ttl,
+                        false,
+                        config.cache_limit_bytes(),
+                    )
+                    .await?;
+                    let url = open_note(
+                        &client,
+                        &mut config,
+                        &config_path,
+                        &token,
+                        cli.refresh_identity,
+                        &note,
+                        symbols,
+                    )
+                    .await?
+                    .ok_or("Could not determine the workspace slug to build the note's URL.")?;
+                    if cli.output.is_json() {
+                        output::print_json(&serde_json::json!({ "url": url }))?;
+                    } else {
+                        println!("{}", url);
+                    }
+                }
+                NoteCommands::Delete { note_id } => {
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.delete_note_request(&note_id))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        client.delete_note(&note_id).await?;
+                        if cli.output.is_json() {
+                            output::print_json(&serde_json::json!({ "deleted": note_id }))?;
+                        } else {
+                            if !output::is_quiet() {
+                                println!(
+                                    "{} Note {} deleted successfully.",
+                                    symbols.success(),
+                                    note_id
+                                );
+                            }
+                        }
+                    }
+                }
+                NoteCommands::DeleteMany {
+                    note_ids,
+                    from_file,
+                    yes,
+                } => {
+                    let note_ids = batch::collect_note_ids(note_ids, from_file.as_deref())?;
+                    if cli.dry_run {
+                        for note_id in &note_ids {
+                            let prepared =
+                                DryRunRequest::prepare(client.delete_note_request(note_id))?;
+                            output::print_dry_run(&prepared, cli.output)?;
+                        }
+                    } else {
+                        if !yes {
+                            confirm_delete_many(note_ids.len())?;
+                        }
+                        let delay = std::time::Duration::from_millis(config.fetch_delay_ms);
+                        let summary = delete_notes(&client, note_ids, delay, symbols).await;
+                        let failure_count = summary.failures.len();
+                        if cli.output.is_json() {
+                            output::print_json(&summary)?;
+                        } else {
+                            println!("Deleted {} of {} notes.", summary.deleted, summary.total);
+                            if failure_count > 0 {
+                                let mut table = comfy_table::Table::new();
+                                table
+                                    .set_header(vec!["Note ID", "Error"])
+                                    .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                                    .set_content_arrangement(
+                                        comfy_table::ContentArrangement::Dynamic,
+                                    );
+                                for failure in &summary.failures {
+                                    table.add_row(vec![&failure.note_id, &failure.error]);
+                                }
+                                println!("{table}");
+                            }
+                        }
+                        if failure_count > 0 {
+                            return Err(format!("{} notes failed to delete.", failure_count).into());
+                        }
+                    }
+                }
+                NoteCommands::Diff {
+                    note_id,
+                    other_note_id,
+                    field,
+                } => {
+                    let (old, new) =
+                        tokio::join!(client.get_note(&note_id), client.get_note(&other_note_id));
+                    let old =
+                        old.map_err(|e| format!("Could not fetch note {}: {}", note_id, e))?;
+                    let new =
+                        new.map_err(|e| format!("Could not fetch note {}: {}", other_note_id, e))?;
+
+                    if cli.output.is_json() {
+                        output::print_json(&diff::diff_json(&old.data, &new.data, field))?;
+                        return Ok(());
+                    }
+                    let color = std::io::stdout().is_terminal();
+                    let output = diff::diff_notes(&old.data, &new.data, field, color);
+                    if output.is_empty() {
+                        if !output::is_quiet() {
+                            println!("{} Notes are identical.", symbols.success());
+                        }
+                    } else {
+                        print!("{}", output);
+                    }
+                }
+                NoteCommands::Export {
+                    path,
+                    format,
+                    line_ending,
+                } => {
+                    let notes = fetch_all_notes(&client).await?;
+                    fs::write(&path, export::render(&notes, format, line_ending))?;
+                    let body = format!("Exported {} notes to {}.", notes.len(), path.display());
+                    tokio::task::spawn_blocking(move || {
+                        notify_mode.notify("Attio export finished", &body)
+                    })
+                    .await?;
+                    if cli.output.is_json() {
+                        output::print_json(&serde_json::json!({
+                            "exported": notes.len(),
+                            "path": path,
+                        }))?;
+                    } else if !output::is_quiet() {
+                        println!(
+                            "{} Exported {} notes to {}",
+                            symbols.success(),
+                            notes.len(),
+                            path.display()
+                        );
+                    }
+                }
+                NoteCommands::Attachments { note_id, download } => {
+                    let attachments = client.list_note_attachments(&note_id).await?.data;
+                    if cli.output.is_json() {
+                        output::print_json(&serde_json::json!({ "data": attachments }))?;
+                    } else if attachments.is_empty() {
+                        println!("Note {} has no attachments.", note_id);
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["ID", "Filename", "Type", "Size"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for attachment in &attachments {
+                            table.add_row(vec![
+                                attachment.id.attachment_id.clone(),
+                                attachment.filename.clone(),
+                                attachment
+                                    .content_type
+                                    .clone()
+                                    .unwrap_or_else(|| "-".to_string()),
+                                attachment.size_label(),
+                            ]);
+                        }
+                        println!("{table}");
+                    }
+
+                    if let Some(dir) = download {
+                        fs::create_dir_all(&dir)
+                            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+                        for attachment in &attachments {
+                            let path = dir.join(attachment.local_filename());
+                            if path.exists() {
+                                eprintln!(
+                                    "{} Skipping {}: it already exists",
+                                    symbols.warning(),
+                                    path.display()
+                                );
+                                continue;
+                            }
+                            let mut file = fs::File::create(&path).map_err(|e| {
+                                format!("Failed to create {}: {}", path.display(), e)
+                            })?;
+                            match client.download(&attachment.download_url, &mut file).await {
+                                Ok(bytes) if !output::is_quiet() => eprintln!(
+                                    "{} Downloaded {} ({} bytes)",
+                                    symbols.file(),
+                                    path.display(),
+                                    bytes
+                                ),
+                                Ok(_) => {}
+                                Err(e) => {
+                                    // Don't leave a truncated file behind
+                                    drop(file);
+                                    let _ = fs::remove_file(&path);
+                                    return Err(format!(
+                                        "Failed to download {}: {}",
+                                        attachment.filename, e
+                                    )
+                                    .into());
+                                }
+                            }
+                        }
+                    }
+                }
+                NoteCommands::Import {
+                    path,
+                    continue_on_error,
+                } => {
+                    let rows = import::read_rows(&path)?;
+                    let total = rows.len();
+                    let delay = std::time::Duration::from_millis(config.fetch_delay_ms);
+                    let mut created = 0;
+                    let mut failures = Vec::new();
+                    // Dry-run requests are printed as the loop goes, so skip the bar
+                    let bar = if cli.dry_run {
+                        indicatif::ProgressBar::hidden()
+                    } else {
+                        progress::bar(total as u64, "Importing")
+                    };
+
+                    for (index, row) in rows.into_iter().enumerate() {
+                        // Space requests out like paged fetches do, to stay under rate limits
+                        if index > 0 && !cli.dry_run {
+                            tokio::time::sleep(delay).await;
+                        }
+                        let title = row.title.clone();
+                        match import_note(&client, row, cli.dry_run, cli.output).await {
+                            Ok(Some(note_id)) => {
+                                created += 1;
+                                bar.suspend(|| {
+                                    eprintln!(
+                                        "[{}/{}] {} {} ({})",
+                                        index + 1,
+                                        total,
+                                        symbols.success(),
+                                        title,
+                                        note_id
+                                    )
+                                });
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                bar.suspend(|| {
+                                    eprintln!(
+                                        "[{}/{}] {} {}: {}",
+                                        index + 1,
+                                        total,
+                                        symbols.warning(),
+                                        title,
+                                        e
+                                    )
+                                });
+                                failures.push(import::ImportFailure {
+                                    row: index + 1,
+                                    title,
+                                    error: e.to_string(),
+                                });
+                                if !continue_on_error {
+                                    break;
+                                }
+                            }
+                        }
+                        bar.inc(1);
+                    }
+                    bar.finish_and_clear();
+
+                    let failed = failures.first().map(|failure| failure.row);
+                    let failure_count = failures.len();
+                    if cli.dry_run {
+                        // The requests themselves were the output
+                    } else if cli.output.is_json() {
+                        output::print_json(&import::ImportSummary {
+                            total,
+                            created,
+                            failures,
+                        })?;
+                    } else {
+                        println!("Imported {} of {} notes.", created, total);
+                        if !failures.is_empty() {
+                            let mut table = comfy_table::Table::new();
+                            table
+                                .set_header(vec!["Row", "Title", "Error"])
+                                .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                                .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                            for failure in &failures {
+                                table.add_row(vec![
+                                    failure.row.to_string(),
+                                    failure.title.clone(),
+                                    failure.error.clone(),
+                                ]);
+                            }
+                            println!("{table}");
+                        }
+                    }
+                    match failed {
+                        Some(row) if !continue_on_error => {
+                            return Err(format!(
+                                "Stopped at row {}. Fix it and import the remaining rows, or pass --continue-on-error.",
+                                row
+                            )
+                            .into());
+                        }
+                        Some(_) => {
+                            return Err(format!("{} notes failed to import.", failure_count).into());
+                        }
+                        None => {}
+                    }
+                }
+            }
+        }
+        Commands::Records { object, action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                RecordCommands::List {
+                    limit,
+                    offset,
+                    attributes,
+                } => {
+                    let query = models::RecordQuery {
+                        limit,
+                        offset,
+                        ..Default::default()
+                    };
+                    let response = client.query_records(&object, &query).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        print_records(&response.data, &attributes);
+                    }
+                }
+                RecordCommands::Query {
+                    filter,
+                    conditions,
+                    sorts,
+                    limit,
+                    offset,
+                    attributes,
+                } => {
+                    let filter = models::Filter {
+                        raw: filter,
+                        conditions,
+                    };
+                    let query = models::RecordQuery {
+                        filter: filter.to_json(),
+                        limit,
+                        offset,
+                        sorts,
+                    };
+                    let response = client.query_records(&object, &query).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        print_records(&response.data, &attributes);
+                    }
+                }
+                RecordCommands::Get {
+                    record_id,
+                    attributes,
+                } => {
+                    let response = client.get_record(&object, &record_id).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        let record = &response.data;
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Attribute", "Value"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                        table.add_row(vec!["ID", &record.id.record_id]);
+                        table.add_row(vec!["Created", &record.created_at]);
+                        let names = if attributes.is_empty() {
+                            record.attribute_names()
+                        } else {
+                            attributes.iter().map(String::as_str).collect()
+                        };
+                        for name in names {
+                            table.add_row(vec![
+                                name.to_string(),
+                                record
+                                    .attribute_text(name)
+                                    .unwrap_or_else(|| "-".to_string()),
                             ]);
                         }
 
                         println!("{table}");
+                    }
+                }
+            }
+        }
+        Commands::People { action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                PeopleCommands::List { limit, offset } => {
+                    let response = client.list_people(limit, offset).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
                     } else {
-                        tui::run_list_tui(client, config.cache_limit_mb).await?;
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["ID", "Name", "Email", "Created"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for person in &response.data {
+                            table.add_row(vec![
+                                person.id.record_id.as_str(),
+                                person.name().unwrap_or("-"),
+                                person.primary_email().unwrap_or("-"),
+                                person.created_at.as_str(),
+                            ]);
+                        }
+                        println!("{table}");
                     }
                 }
-                NoteCommands::Get {
-                    note_id,
-                    open_in_browser,
+                PeopleCommands::Get { record_id } => {
+                    let response = client.get_person(&record_id).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        let person = &response.data;
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Attribute", "Value"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                        table.add_row(vec!["ID", &person.id.record_id]);
+                        table.add_row(vec!["Name", person.name().unwrap_or("-")]);
+                        table.add_row(vec!["Email", person.primary_email().unwrap_or("-")]);
+                        table.add_row(vec!["Created", &person.created_at]);
+
+                        println!("{table}");
+                    }
+                }
+            }
+        }
+        Commands::Companies { action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                CompanyCommands::List { limit, offset } => {
+                    let response = client.list_companies(limit, offset).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["ID", "Name", "Domain", "Created"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for company in &response.data {
+                            table.add_row(vec![
+                                company.id.record_id.as_str(),
+                                company.name().unwrap_or("-"),
+                                company.domain().unwrap_or("-"),
+                                company.created_at.as_str(),
+                            ]);
+                        }
+                        println!("{table}");
+                    }
+                }
+                CompanyCommands::Get { record_id } => {
+                    let response = client.get_company(&record_id).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        let company = &response.data;
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Attribute", "Value"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                        table.add_row(vec!["ID", &company.id.record_id]);
+                        table.add_row(vec!["Name", company.name().unwrap_or("-")]);
+                        table.add_row(vec!["Domain", company.domain().unwrap_or("-")]);
+                        table.add_row(vec!["Created", &company.created_at]);
+
+                        println!("{table}");
+                    }
+                }
+            }
+        }
+        Commands::Tasks { action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                TaskCommands::List { limit, offset } => {
+                    let response = client.list_tasks(limit, offset).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else if response.data.is_empty() {
+                        println!("No tasks found.");
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Done", "ID", "Content", "Deadline", "Linked"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for task in &response.data {
+                            table.add_row(vec![
+                                task.checkbox().to_string(),
+                                task.id.task_id.clone(),
+                                task.content_plaintext.clone(),
+                                task.deadline_at.clone().unwrap_or_else(|| "-".to_string()),
+                                task.linked_summary(),
+                            ]);
+                        }
+                        println!("{table}");
+                    }
+                }
+                TaskCommands::Get { task_id } => {
+                    let response = client.get_task(&task_id).await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else {
+                        print_task(&response.data);
+                    }
+                }
+                TaskCommands::Create {
+                    content,
+                    deadline,
+                    link,
                 } => {
-                    let response = client.get_note(&note_id).await?;
-                    let note = response.data;
-
-                    let mut table = comfy_table::Table::new();
-                    table
-                        .set_header(vec!["Attribute", "Value"])
-                        .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
-                        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
-
-                    table.add_row(vec!["ID", &note.id.note_id]);
-                    table.add_row(vec!["Title", &note.title]);
-                    table.add_row(vec!["Content", &note.content_plaintext]);
-
-                    println!("{table}");
-
-                    if open_in_browser {
-                        let id_response = client.identify().await?;
-                        if let Some(slug) = id_response.workspace_slug {
-                            // Map common plural objects to singular for the URL
-                            let parent = match note.parent_object.as_str() {
-                                "people" => "person",
-                                "companies" => "company",
-                                other => other,
-                            };
-                            let url = format!(
-                                "https://app.attio.com/{}/{}/{}/notes?modal=note&id={}",
-                                slug, parent, note.parent_record_id, note.id.note_id
-                            );
-                            println!("🔗 Opening note in browser...");
-                            if let Err(e) = webbrowser::open(&url) {
-                                eprintln!("Failed to open browser: {}", e);
+                    let mut resolver = resolve::RecordResolver::new();
+                    let mut linked_records = Vec::new();
+                    for link in &link {
+                        let spec = resolve::ParentSpec::parse(link)?;
+                        let target_record_id = resolver.resolve(&client, &spec).await?;
+                        linked_records.push(models::TaskLink {
+                            target_object: spec.object,
+                            target_record_id,
+                        });
+                    }
+                    let request = models::CreateTaskRequest {
+                        data: models::CreateTaskData {
+                            content,
+                            format: "plaintext".to_string(),
+                            deadline_at: deadline.map(|deadline| deadline.to_rfc3339()),
+                            is_completed: false,
+                            linked_records,
+                            assignees: Vec::new(),
+                        },
+                    };
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.create_task_request(&request))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        let response = client.create_task(request).await?;
+                        if cli.output.is_json() {
+                            output::print_json(&response)?;
+                        } else {
+                            if !output::is_quiet() {
+                                println!("{} Task created successfully!", symbols.success());
                             }
+                            print_task(&response.data);
+                        }
+                    }
+                }
+                TaskCommands::Complete { task_id } => {
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.complete_task_request(&task_id))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        let response = client.complete_task(&task_id).await?;
+                        if cli.output.is_json() {
+                            output::print_json(&response)?;
                         } else {
-                            println!(
-                                "⚠️ Could not determine workspace slug to open identification URL."
-                            );
+                            if !output::is_quiet() {
+                                println!("{} Task {} completed.", symbols.success(), task_id);
+                            }
                         }
                     }
                 }
-                NoteCommands::Create {
+            }
+        }
+        Commands::Comments { action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                CommentCommands::List {
                     parent_object,
                     parent_record_id,
-                    title,
+                } => {
+                    let response = client
+                        .list_comments(&parent_object, &parent_record_id)
+                        .await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else if response
+                        .data
+                        .iter()
+                        .all(|thread| thread.comments.is_empty())
+                    {
+                        println!("No comments found.");
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Thread", "Author", "Created", "Comment"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for thread in &response.data {
+                            let resolved = thread.comments.iter().any(|c| c.resolved_at.is_some());
+                            for (i, comment) in thread.comments.iter().enumerate() {
+                                // Name each thread once, on its first comment
+                                let label = match (i, resolved) {
+                                    (0, true) => format!("{} (resolved)", thread.id.thread_id),
+                                    (0, false) => thread.id.thread_id.clone(),
+                                    _ => String::new(),
+                                };
+                                table.add_row(vec![
+                                    label,
+                                    comment.author_label().to_string(),
+                                    comment.created_at.clone(),
+                                    comment.content_plaintext.clone(),
+                                ]);
+                            }
+                        }
+                        println!("{table}");
+                    }
+                }
+                CommentCommands::Create {
                     content,
-                    format,
-                    open_in_browser,
+                    parent_object,
+                    parent_record_id,
+                    thread_id,
+                    author,
                 } => {
-                    let request = crate::models::CreateNoteRequest {
-                        data: crate::models::CreateNoteData {
-                            parent_object,
-                            parent_record_id,
-                            title,
+                    let author = match author {
+                        Some(author) => author,
+                        None => client
+                            .identify()
+                            .await?
+                            .authorized_by_workspace_member_id
+                            .ok_or(
+                                "Could not tell which workspace member owns this token. Pass --author <member id>.",
+                            )?,
+                    };
+                    let record = parent_object
+                        .zip(parent_record_id)
+                        .map(|(object, record_id)| models::CommentRecord { object, record_id });
+                    let request = models::CreateCommentRequest {
+                        data: models::CreateCommentData {
+                            format: "plaintext".to_string(),
                             content,
-                            format,
+                            author: models::Actor::workspace_member(author),
+                            thread_id,
+                            record,
                         },
                     };
-                    let response = client.create_note(request).await?;
-                    let note = response.data;
-                    println!("✅ Note created successfully!");
-
-                    let mut table = comfy_table::Table::new();
-                    table
-                        .set_header(vec!["Attribute", "Value"])
-                        .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
-                        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
-
-                    table.add_row(vec!["ID", &note.id.note_id]);
-                    table.add_row(vec!["Title", &note.title]);
-                    table.add_row(vec!["Content", &note.content_plaintext]);
-
-                    println!("{table}");
-
-                    if open_in_browser {
-                        let id_response = client.identify().await?;
-                        if let Some(slug) = id_response.workspace_slug {
-                            let parent = match note.parent_object.as_str() {
-                                "people" => "person",
-                                "companies" => "company",
-                                other => other,
-                            };
-                            let url = format!(
-                                "https://app.attio.com/{}/{}/{}/notes?modal=note&id={}",
-                                slug, parent, note.parent_record_id, note.id.note_id
-                            );
-                            println!("🔗 Opening note in browser...");
-                            if let Err(e) = webbrowser::open(&url) {
-                                eprintln!("Failed to open browser: {}", e);
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.create_comment_request(&request))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        let response = client.create_comment(request).await?;
+                        if cli.output.is_json() {
+                            output::print_json(&response)?;
+                        } else {
+                            if !output::is_quiet() {
+                                println!(
+                                    "{} Comment {} added to thread {}.",
+                                    symbols.success(),
+                                    response.data.id.comment_id,
+                                    response.data.thread_id
+                                );
                             }
                         }
                     }
                 }
-                NoteCommands::Delete { note_id } => {
-                    client.delete_note(&note_id).await?;
-                    println!("✅ Note {} deleted successfully.", note_id);
+            }
+        }
+        Commands::Cache { action } => {
+            let token = get_token(&config_path)?;
+            let path =
+                cache::disk_cache_path(&token).ok_or("Could not determine the cache directory.")?;
+            match action {
+                CacheCommands::Info => {
+                    let config = read_config(&config_path)
+                        .unwrap_or_else(|_| models::Config::new(String::new()));
+                    let info = cache::disk_cache_info(&path, config.cache_limit_bytes())?;
+                    if cli.output.is_json() {
+                        output::print_json(&info)?;
+                    } else {
+                        let mb = |bytes: f64| bytes / (1024.0 * 1024.0);
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Key", "Value"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        table.add_row(vec!["path", &info.path.display().to_string()]);
+                        table.add_row(vec![
+                            "size",
+                            &format!(
+                                "{:.1}MB / {:.0}MB",
+                                mb(info.size_bytes as f64),
+                                mb(info.limit_bytes as f64)
+                            ),
+                        ]);
+                        table.add_row(vec![
+                            "notes",
+                            &info
+                                .notes
+                                .map_or("unreadable".to_string(), |count| count.to_string()),
+                        ]);
+                        if let Some(saved_at) = info.saved_at {
+                            let age = std::time::Duration::from_secs(
+                                cache::unix_now().saturating_sub(saved_at),
+                            );
+                            table
+                                .add_row(vec!["saved", &format!("{} ago", cache::format_age(age))]);
+                        }
+                        println!("{table}");
+                    }
+                }
+                CacheCommands::Clear => {
+                    let cleared = cache::clear_disk_cache(&path).map_err(|e| {
+                        format!("Could not delete the notes cache {}: {}", path.display(), e)
+                    })?;
+                    if !output::is_quiet() {
+                        if cleared {
+                            println!(
+                                "{} Deleted the notes cache {}",
+                                symbols.success(),
+                                path.display()
+                            );
+                        } else {
+                            println!("{} There is no notes cache to delete.", symbols.success());
+                        }
+                    }
+                }
+            }
+        }
+        Commands::Version { check } => {
+            let version = env!("CARGO_PKG_VERSION");
+            // A failed check is skipped quietly; it's only ever informational
+            let latest = if check {
+                update::latest_version(update::LATEST_RELEASE_URL).await
+            } else {
+                None
+            };
+            let update_available = latest
+                .as_deref()
+                .is_some_and(|latest| update::is_newer(latest, version));
+            if cli.output.is_json() {
+                output::print_json(&update::VersionInfo {
+                    version,
+                    latest,
+                    update_available,
+                })?;
+            } else {
+                println!("attio {}", version);
+                match latest {
+                    Some(latest) if update_available => println!(
+                        "{} Version {} is available from {}",
+                        symbols.warning(),
+                        latest,
+                        update::RELEASES_PAGE_URL
+                    ),
+                    Some(_) if !output::is_quiet() => {
+                        println!("{} You're on the latest version.", symbols.success())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Commands::Doctor => {
+            let mut checks = vec![doctor::check_config_file(&config_path)];
+            let settings =
+                read_config(&config_path).unwrap_or_else(|_| models::Config::new(String::new()));
+            let env_var = settings.token_env_var.clone();
+            let token = get_token_with_source(&config_path).ok();
+
+            checks.push(match &token {
+                Some((_, source)) => {
+                    let found_in = match source {
+                        secrets::TokenSource::Env => env_var.clone(),
+                        secrets::TokenSource::Keyring => "the OS keyring".to_string(),
+                        secrets::TokenSource::Project => "the project config".to_string(),
+                        secrets::TokenSource::File => "the config file".to_string(),
+                    };
+                    doctor::Check::pass("Token", format!("found in {}", found_in))
+                }
+                None => doctor::Check::fail(
+                    "Token",
+                    format!(
+                        "No token in the project config, OS keyring, config file or {}",
+                        env_var
+                    ),
+                    format!("Run `attio auth <token>`, or set {}", env_var),
+                ),
+            });
+            if let Some(parent) = config_path.parent() {
+                checks.push(doctor::check_writable(parent));
+            }
+            checks.push(
+                // `--timeout 0` would let a dead network hang the check
+                doctor::check_reachable(
+                    &settings.base_url,
+                    request_timeout(cli.timeout, &settings)
+                        .unwrap_or(std::time::Duration::from_secs(settings.timeout_secs)),
+                )
+                .await,
+            );
+
+            let new_token_hint =
+                "Create a new API token in Attio's workspace settings and run `attio auth <token>`";
+            checks.push(match token {
+                None => doctor::Check::warn(
+                    "Authentication",
+                    "Skipped: there is no token to check",
+                    "Fix the token check above first",
+                ),
+                Some((token, _)) => {
+                    match async {
+                        build_client(token, &settings, cli.timeout)?
+                            .identify()
+                            .await
+                    }
+                    .await
+                    {
+                        Ok(identity) if identity.active => doctor::Check::pass(
+                            "Authentication",
+                            format!(
+                                "token is valid for {}",
+                                identity
+                                    .workspace_name
+                                    .as_deref()
+                                    .unwrap_or("the workspace")
+                            ),
+                        ),
+                        Ok(_) => doctor::Check::fail(
+                            "Authentication",
+                            "the token is no longer active",
+                            new_token_hint,
+                        ),
+                        Err(e)
+                            if e.downcast_ref::<error::AttioError>()
+                                .is_some_and(|e| e.is_unauthorized()) =>
+                        {
+                            doctor::Check::fail(
+                                "Authentication",
+                                "Attio rejected the token (401 Unauthorized)",
+                                new_token_hint,
+                            )
+                        }
+                        Err(e) => doctor::Check::fail(
+                            "Authentication",
+                            format!("could not check the token: {}", e),
+                            "Fix the network check above first",
+                        ),
+                    }
+                }
+            });
+
+            if cli.output.is_json() {
+                output::print_json(&checks)?;
+            } else {
+                doctor::print(&checks, symbols);
+            }
+            let failed = checks
+                .iter()
+                .filter(|check| check.status == doctor::Status::Fail)
+                .count();
+            if failed > 0 {
+                return Err(format!("{} of {} checks failed.", failed, checks.len()).into());
+            }
+        }
+        Commands::Whoami => {
+            let token = get_token(&config_path)?;
+            let mut config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token.clone(), &config, cli.timeout)?;
+            let identity = identify_cached(&client, &mut config, &config_path, &token).await?;
+            if cli.output.is_json() {
+                output::print_json(&identity)?;
+            } else {
+                let mut table = comfy_table::Table::new();
+                table
+                    .set_header(vec!["Attribute", "Value"])
+                    .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                    .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+
+                table.add_row(vec!["Active", if identity.active { "yes" } else { "no" }]);
+                table.add_row(vec![
+                    "Workspace ID",
+                    identity.workspace_id.as_deref().unwrap_or("-"),
+                ]);
+                table.add_row(vec![
+                    "Workspace Name",
+                    identity.workspace_name.as_deref().unwrap_or("-"),
+                ]);
+                table.add_row(vec![
+                    "Workspace Slug",
+                    identity.workspace_slug.as_deref().unwrap_or("-"),
+                ]);
+
+                println!("{table}");
+            }
+        }
+        Commands::Objects { action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                ObjectCommands::List => {
+                    let response = client.list_objects().await?;
+                    if cli.output.is_json() {
+                        output::print_json(&response)?;
+                    } else if response.data.is_empty() {
+                        println!("No objects found.");
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["Slug", "Singular", "Plural", "ID"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for object in &response.data {
+                            table.add_row(vec![
+                                object.api_slug.as_deref().unwrap_or("-"),
+                                object.singular_noun.as_deref().unwrap_or("-"),
+                                object.plural_noun.as_deref().unwrap_or("-"),
+                                object.id_label(),
+                            ]);
+                        }
+                        println!("{table}");
+                    }
+                }
+            }
+        }
+        Commands::Webhooks { action } => {
+            let token = get_token(&config_path)?;
+            let config =
+                get_config(&config_path).unwrap_or_else(|_| models::Config::new(token.clone()));
+            let client = build_client(token, &config, cli.timeout)?;
+            match action {
+                WebhookCommands::List => {
+                    let webhooks = client.list_webhooks().await?.data;
+                    if webhooks.is_empty() {
+                        println!("No webhooks found.");
+                    } else {
+                        let mut table = comfy_table::Table::new();
+                        table
+                            .set_header(vec!["ID", "URL", "Events", "Status"])
+                            .load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY)
+                            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+                        for webhook in &webhooks {
+                            table.add_row(vec![
+                                webhook.id.webhook_id.as_str(),
+                                webhook.target_url.as_str(),
+                                &webhook.event_types(),
+                                webhook.status.as_deref().unwrap_or("-"),
+                            ]);
+                        }
+                        println!("{table}");
+                    }
+                }
+                WebhookCommands::Create { url, events } => {
+                    let subscriptions = events
+                        .iter()
+                        .map(|event| event.trim())
+                        .filter(|event| !event.is_empty())
+                        .map(|event| models::WebhookSubscription {
+                            event_type: event.to_string(),
+                            filter: None,
+                        })
+                        .collect();
+                    let request = models::CreateWebhookRequest {
+                        data: models::CreateWebhookData {
+                            target_url: url,
+                            subscriptions,
+                        },
+                    };
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.create_webhook_request(&request))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        let webhook = client.create_webhook(request).await?.data;
+                        if output::is_quiet() {
+                            println!("{}", webhook.id.webhook_id);
+                        } else {
+                            println!(
+                                "{} Webhook {} created for {} ({}).",
+                                symbols.success(),
+                                webhook.id.webhook_id,
+                                webhook.target_url,
+                                webhook.event_types()
+                            );
+                        }
+                        if let Some(secret) = webhook.secret {
+                            println!(
+                                "{} Signing secret (shown only once): {}",
+                                symbols.warning(),
+                                secret
+                            );
+                        }
+                    }
+                }
+                WebhookCommands::Delete { webhook_id } => {
+                    if cli.dry_run {
+                        let prepared =
+                            DryRunRequest::prepare(client.delete_webhook_request(&webhook_id))?;
+                        output::print_dry_run(&prepared, cli.output)?;
+                    } else {
+                        client.delete_webhook(&webhook_id).await?;
+                        if !output::is_quiet() {
+                            println!(
+                                "{} Webhook {} deleted successfully.",
+                                symbols.success(),
+                                webhook_id
+                            );
+                        }
+                    }
                 }
             }
         }
     }
 
+    timings.report();
     Ok(())
 }
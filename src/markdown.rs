@@ -0,0 +1,395 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// A run of text sharing one style
+type Segment = (String, Style);
+
+/// Renders note markdown as styled lines word-wrapped to `width` columns.
+///
+/// Handles headings, bullet and numbered lists, block quotes, fenced code
+/// blocks, rules, and inline bold, italic and code. This never fails:
+/// unbalanced markers and anything else it doesn't understand are shown as
+/// plain text.
+pub fn render(markdown: &str, width: u16) -> Vec<Line<'static>> {
+    let width = usize::from(width.max(1));
+    let mut lines = Vec::new();
+    let mut in_code = false;
+
+    for raw in markdown.lines() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            let code = Style::default().fg(Color::Yellow);
+            lines.extend(wrap(vec![(raw.to_string(), code)], width, "  ", "  "));
+            continue;
+        }
+        if trimmed.is_empty() {
+            lines.push(Line::default());
+            continue;
+        }
+        if is_rule(trimmed) {
+            lines.push(Line::styled(
+                "─".repeat(width),
+                Style::default().fg(Color::DarkGray),
+            ));
+            continue;
+        }
+
+        let indent = " ".repeat(raw.len() - trimmed.len());
+        if let Some((level, text)) = heading(trimmed) {
+            let mut style = Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD);
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            lines.extend(wrap(inline(text, style), width, "", ""));
+        } else if let Some((marker, text)) = list_item(trimmed) {
+            let first = format!("{}{} ", indent, marker);
+            let rest = " ".repeat(Span::raw(first.as_str()).width());
+            lines.extend(wrap(inline(text, Style::default()), width, &first, &rest));
+        } else if let Some(text) = trimmed.strip_prefix('>') {
+            let quote = Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC);
+            lines.extend(wrap(inline(text.trim_start(), quote), width, "│ ", "│ "));
+        } else {
+            lines.extend(wrap(
+                inline(trimmed, Style::default()),
+                width,
+                &indent,
+                &indent,
+            ));
+        }
+    }
+    lines
+}
+
+/// Strips markdown down to the text a reader would see.
+///
+/// Markers the renderer understands are dropped; bullets are kept as `- `
+/// and numbered items keep their number. Like `render`, this never fails.
+pub fn to_plaintext(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+
+    for raw in markdown.lines() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(raw.to_string());
+            continue;
+        }
+        if trimmed.is_empty() || is_rule(trimmed) {
+            lines.push(String::new());
+            continue;
+        }
+
+        let indent = &raw[..raw.len() - trimmed.len()];
+        let (prefix, text) = if let Some((_, text)) = heading(trimmed) {
+            (String::new(), text)
+        } else if let Some((marker, text)) = list_item(trimmed) {
+            let marker = if marker == "•" {
+                "-".to_string()
+            } else {
+                marker
+            };
+            (format!("{}{} ", indent, marker), text)
+        } else if let Some(text) = trimmed.strip_prefix('>') {
+            (String::new(), text.trim_start())
+        } else {
+            (indent.to_string(), trimmed)
+        };
+        let text: String = inline(text, Style::default())
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect();
+        lines.push(prefix + &text);
+    }
+    lines.join("\n")
+}
+
+/// Escapes plain text so that `render` and `to_plaintext` show it as
+/// written, e.g. a line starting with `#` doesn't become a heading
+pub fn from_plaintext(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            let mut escaped = String::with_capacity(line.len());
+            for c in trimmed.chars() {
+                if matches!(c, '\\' | '*' | '`') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            let starts_block = heading(trimmed).is_some()
+                || list_item(trimmed).is_some()
+                || is_rule(trimmed)
+                || trimmed.starts_with('>');
+            if starts_block && !escaped.starts_with('\\') {
+                escape_block_marker(&mut escaped);
+            }
+            format!("{}{}", indent, escaped)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escapes the character that makes a line a heading, list item, rule or
+/// quote: the first one, or the dot or paren after an item number
+fn escape_block_marker(line: &mut String) {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let at = if digits > 0 && list_item(line).is_some() {
+        digits
+    } else {
+        0
+    };
+    line.insert(at, '\\');
+}
+
+/// `---`, `***` or `___`, optionally spaced out
+fn is_rule(line: &str) -> bool {
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3
+        && ["-", "*", "_"]
+            .iter()
+            .any(|mark| marks.chars().all(|c| c.to_string() == *mark))
+}
+
+/// Heading level and text for `# Title` through `###### Title`
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+/// The bullet to draw and the item text for `- item` or `1. item`
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(text) = line.strip_prefix(bullet) {
+            return Some(("•".to_string(), text));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    let text = rest
+        .strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))?;
+    (digits > 0).then(|| (format!("{}.", &line[..digits]), text))
+}
+
+/// Splits `text` on `**bold**`, `*italic*` and `` `code` `` markers. A
+/// backslash before punctuation keeps it literal, as in `\\*`.
+fn inline(text: &str, base: Style) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '\\'
+            && let Some(escaped) = rest[1..].chars().next().filter(char::is_ascii_punctuation)
+        {
+            plain.push(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+            continue;
+        }
+        let (marker, style) = if rest.starts_with("**") {
+            ("**", base.add_modifier(Modifier::BOLD))
+        } else if c == '`' {
+            ("`", Style::default().fg(Color::Yellow))
+        } else if c == '*' {
+            ("*", base.add_modifier(Modifier::ITALIC))
+        } else {
+            plain.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        let after = &rest[marker.len()..];
+        match after.find(marker) {
+            Some(end) if end > 0 => {
+                if !plain.is_empty() {
+                    segments.push((std::mem::take(&mut plain), base));
+                }
+                segments.push((after[..end].to_string(), style));
+                rest = &after[end + marker.len()..];
+            }
+            // Unbalanced: keep the marker as text
+            _ => {
+                plain.push_str(marker);
+                rest = after;
+            }
+        }
+    }
+    if !plain.is_empty() {
+        segments.push((plain, base));
+    }
+    segments
+}
+
+/// Greedily word-wraps segments to `width`, starting the first line with
+/// `first_prefix` and later ones with `rest_prefix`. Words longer than a
+/// whole line are broken mid-word.
+fn wrap(
+    segments: Vec<Segment>,
+    width: usize,
+    first_prefix: &str,
+    rest_prefix: &str,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut spans = vec![Span::raw(first_prefix.to_string())];
+    let mut used = Span::raw(first_prefix).width();
+
+    for (text, style) in segments {
+        for word in text.split_inclusive(' ') {
+            let mut word = word.to_string();
+            loop {
+                let word_width = Span::raw(word.trim_end()).width();
+                let prefix_width = Span::raw(rest_prefix).width();
+                if used + word_width <= width || used <= prefix_width {
+                    if word_width > width.saturating_sub(used) && used + 1 < width {
+                        // Too long for any line: split it here
+                        let fit = split_at_width(&word, width - used);
+                        let tail = word.split_off(fit);
+                        spans.push(Span::styled(word, style));
+                        lines.push(Line::from(std::mem::take(&mut spans)));
+                        spans.push(Span::raw(rest_prefix.to_string()));
+                        used = prefix_width;
+                        word = tail;
+                        continue;
+                    }
+                    used += Span::raw(word.as_str()).width();
+                    spans.push(Span::styled(word, style));
+                } else {
+                    lines.push(Line::from(std::mem::take(&mut spans)));
+                    spans.push(Span::raw(rest_prefix.to_string()));
+                    used = prefix_width;
+                    continue;
+                }
+                break;
+            }
+        }
+    }
+    lines.push(Line::from(spans));
+    lines
+}
+
+/// Byte index of the longest prefix of `word` that fits in `width` columns
+fn split_at_width(word: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (i, c) in word.char_indices() {
+        let mut buf = [0; 4];
+        used += Span::raw(&*c.encode_utf8(&mut buf)).width();
+        if used > width {
+            return i.max(c.len_utf8().min(word.len()));
+        }
+    }
+    word.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    fn texts(markdown: &str, width: u16) -> Vec<String> {
+        render(markdown, width).iter().map(text).collect()
+    }
+
+    #[test]
+    fn test_headings_and_lists() {
+        let lines = texts("# Agenda\n- pricing\n2. renewal", 40);
+        assert_eq!(lines, ["Agenda", "• pricing", "2. renewal"]);
+
+        let heading = &render("## Agenda", 40)[0];
+        assert!(heading.spans[1].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        let segments = inline("a **bold** and `code` and *it*", Style::default());
+        let styled: Vec<&str> = segments.iter().map(|(text, _)| text.as_str()).collect();
+        assert_eq!(styled, ["a ", "bold", " and ", "code", " and ", "it"]);
+        assert!(segments[1].1.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(segments[3].1.fg, Some(Color::Yellow));
+        assert!(segments[5].1.add_modifier.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_malformed_markdown_is_literal() {
+        assert_eq!(texts("**unclosed and `tick", 80), ["**unclosed and `tick"]);
+        // An unterminated code fence just runs to the end
+        assert_eq!(texts("```\nlet x = 1;", 80), ["  let x = 1;"]);
+        assert_eq!(texts("#nospace", 80), ["#nospace"]);
+    }
+
+    #[test]
+    fn test_wraps_words_to_width() {
+        let lines = texts("the quick brown fox jumps", 10);
+        assert_eq!(lines, ["the quick ", "brown fox ", "jumps"]);
+        assert!(
+            lines
+                .iter()
+                .all(|line| line.trim_end().chars().count() <= 10)
+        );
+    }
+
+    #[test]
+    fn test_list_continuation_is_indented() {
+        let lines = texts("- alpha beta gamma", 12);
+        assert_eq!(lines, ["• alpha beta ", "  gamma"]);
+    }
+
+    #[test]
+    fn test_long_words_are_broken() {
+        let lines = texts("abcdefghijkl", 5);
+        assert_eq!(lines, ["abcde", "fghij", "kl"]);
+    }
+
+    #[test]
+    fn test_to_plaintext_strips_markers() {
+        let markdown = "# Agenda\n\n- **pricing** and `terms`\n2. renewal\n> *quoted*\n---\n```\nlet x = 1;\n```";
+        assert_eq!(
+            to_plaintext(markdown),
+            "Agenda\n\n- pricing and terms\n2. renewal\nquoted\n\nlet x = 1;"
+        );
+        assert_eq!(to_plaintext("**unclosed"), "**unclosed");
+    }
+
+    #[test]
+    fn test_plaintext_round_trips() {
+        for text in [
+            "# not a heading",
+            "- not a list\n  * nor this",
+            "3. not numbered\n4) either",
+            "> not a quote",
+            "---",
+            "```",
+            "2 * 3 = `6` \\ done",
+            "Plain line\n\nAnother",
+        ] {
+            let markdown = from_plaintext(text);
+            assert_eq!(to_plaintext(&markdown), text, "{}", markdown);
+        }
+        assert_eq!(texts(&from_plaintext("# Agenda"), 40), ["# Agenda"]);
+    }
+
+    #[test]
+    fn test_rules_and_quotes() {
+        assert_eq!(texts("---", 4), ["────"]);
+        assert_eq!(texts("> quoted", 20), ["│ quoted"]);
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A file uploaded to a note
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: AttachmentId,
+    pub filename: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    pub download_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachmentId {
+    pub workspace_id: String,
+    pub attachment_id: String,
+}
+
+impl Attachment {
+    /// A name that is safe to join onto a download directory: the last path
+    /// component of `filename`, or the attachment ID when that is unusable
+    pub fn local_filename(&self) -> String {
+        Path::new(&self.filename)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| !name.trim().is_empty())
+            .map_or_else(|| self.id.attachment_id.clone(), str::to_string)
+    }
+
+    /// Human-readable size, e.g. "12.5 KB"
+    pub fn size_label(&self) -> String {
+        let Some(bytes) = self.size_bytes else {
+            return "-".to_string();
+        };
+        if bytes < 1024 {
+            return format!("{} B", bytes);
+        }
+        let mut size = bytes as f64 / 1024.0;
+        for unit in ["KB", "MB"] {
+            if size < 1024.0 {
+                return format!("{:.1} {}", size, unit);
+            }
+            size /= 1024.0;
+        }
+        format!("{:.1} GB", size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(filename: &str, size_bytes: Option<u64>) -> Attachment {
+        Attachment {
+            id: AttachmentId {
+                workspace_id: "ws".to_string(),
+                attachment_id: "att_1".to_string(),
+            },
+            filename: filename.to_string(),
+            content_type: None,
+            size_bytes,
+            download_url: "https://files.example.com/att_1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_local_filename_strips_directories() {
+        assert_eq!(attachment("deck.pdf", None).local_filename(), "deck.pdf");
+        assert_eq!(
+            attachment("../../etc/passwd", None).local_filename(),
+            "passwd"
+        );
+        assert_eq!(attachment("..", None).local_filename(), "att_1");
+        assert_eq!(attachment("", None).local_filename(), "att_1");
+    }
+
+    #[test]
+    fn test_size_label() {
+        assert_eq!(attachment("a", None).size_label(), "-");
+        assert_eq!(attachment("a", Some(512)).size_label(), "512 B");
+        assert_eq!(attachment("a", Some(12_800)).size_label(), "12.5 KB");
+        assert_eq!(
+            attachment("a", Some(3 * 1024 * 1024)).size_label(),
+            "3.0 MB"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::Cacheable;
+
+/// A conversation on a record, made up of one or more comments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Thread {
+    pub id: ThreadId,
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadId {
+    pub workspace_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: CommentId,
+    pub thread_id: String,
+    pub content_plaintext: String,
+    #[serde(default)]
+    pub author: Option<Actor>,
+    #[serde(default)]
+    pub resolved_at: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentId {
+    pub workspace_id: String,
+    pub comment_id: String,
+}
+
+/// Who wrote a comment, e.g. a workspace member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Actor {
+    #[serde(rename = "type")]
+    pub actor_type: String,
+    pub id: String,
+}
+
+/// The record a new thread is started on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentRecord {
+    pub object: String,
+    pub record_id: String,
+}
+
+/// A new comment: either starts a thread on `record` or replies in `thread_id`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCommentData {
+    pub format: String,
+    pub content: String,
+    pub author: Actor,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record: Option<CommentRecord>,
+}
+
+impl Actor {
+    pub fn workspace_member(id: String) -> Self {
+        Self {
+            actor_type: "workspace-member".to_string(),
+            id,
+        }
+    }
+}
+
+impl Comment {
+    /// Author ID for tables, or "-" when the API didn't say
+    pub fn author_label(&self) -> &str {
+        self.author
+            .as_ref()
+            .map_or("-", |author| author.id.as_str())
+    }
+}
+
+impl Cacheable for Comment {
+    /// Estimate the memory size of this comment in bytes
+    fn estimate_size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.id.workspace_id.capacity()
+            + self.id.comment_id.capacity()
+            + self.thread_id.capacity()
+            + self.content_plaintext.capacity()
+            + self.author.as_ref().map_or(0, |author| {
+                author.actor_type.capacity() + author.id.capacity()
+            })
+            + self.resolved_at.as_ref().map_or(0, String::capacity)
+            + self.created_at.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateCommentRequest, ListThreadsResponse};
+
+    const THREADS_JSON: &str = r#"
+    {
+        "data": [
+            {
+                "id": { "workspace_id": "ws_123", "thread_id": "thread_1" },
+                "comments": [
+                    {
+                        "id": { "workspace_id": "ws_123", "comment_id": "comment_1" },
+                        "thread_id": "thread_1",
+                        "content_plaintext": "Can we move the call?",
+                        "entry": null,
+                        "record": { "record_id": "rec_1", "object_id": "obj_people" },
+                        "resolved_at": null,
+                        "resolved_by": null,
+                        "created_at": "2024-02-20T09:00:00.000000000Z",
+                        "author": { "type": "workspace-member", "id": "member_1" }
+                    },
+                    {
+                        "id": { "workspace_id": "ws_123", "comment_id": "comment_2" },
+                        "thread_id": "thread_1",
+                        "content_plaintext": "Sure, Thursday works",
+                        "created_at": "2024-02-20T10:00:00.000000000Z"
+                    }
+                ],
+                "created_at": "2024-02-20T09:00:00.000000000Z"
+            }
+        ]
+    }
+    "#;
+
+    #[test]
+    fn test_deserialize_threads() {
+        let threads: ListThreadsResponse = serde_json::from_str(THREADS_JSON).unwrap();
+
+        assert_eq!(threads.data.len(), 1);
+        let thread = &threads.data[0];
+        assert_eq!(thread.id.thread_id, "thread_1");
+        assert_eq!(thread.comments.len(), 2);
+        assert_eq!(thread.comments[0].author_label(), "member_1");
+        assert_eq!(thread.comments[1].author_label(), "-");
+    }
+
+    #[test]
+    fn test_create_request_on_record() {
+        let request = CreateCommentRequest {
+            data: CreateCommentData {
+                format: "plaintext".to_string(),
+                content: "Looks good".to_string(),
+                author: Actor::workspace_member("member_1".to_string()),
+                thread_id: None,
+                record: Some(CommentRecord {
+                    object: "people".to_string(),
+                    record_id: "rec_1".to_string(),
+                }),
+            },
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "data": {
+                    "format": "plaintext",
+                    "content": "Looks good",
+                    "author": { "type": "workspace-member", "id": "member_1" },
+                    "record": { "object": "people", "record_id": "rec_1" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_estimate_size_counts_content() {
+        let threads: ListThreadsResponse = serde_json::from_str(THREADS_JSON).unwrap();
+        let comment = &threads.data[0].comments[0];
+        assert!(comment.estimate_size_bytes() > std::mem::size_of::<Comment>());
+    }
+}
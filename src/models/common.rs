@@ -11,6 +11,12 @@ pub trait Cacheable {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ListResponse<T> {
     pub data: Vec<T>,
+    /// Opaque cursor for the next page, when the endpoint supports cursor paging
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Total number of items, for endpoints that report one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<usize>,
 }
 
 /// Generic wrapper for single item responses
@@ -26,12 +32,15 @@ pub struct CreateRequest<T> {
 }
 
 /// Response from the identify/self endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IdentifyResponse {
     pub active: bool,
     pub workspace_id: Option<String>,
     pub workspace_name: Option<String>,
     pub workspace_slug: Option<String>,
+    /// The workspace member who created the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorized_by_workspace_member_id: Option<String>,
 }
 
 #[cfg(test)]
@@ -57,7 +66,11 @@ mod tests {
             },
         ];
 
-        let response = ListResponse { data: items };
+        let response = ListResponse {
+            data: items,
+            next_cursor: None,
+            total_count: None,
+        };
         let json = serde_json::to_string(&response).unwrap();
         let deserialized: ListResponse<TestItem> = serde_json::from_str(&json).unwrap();
 
@@ -66,6 +79,18 @@ mod tests {
         assert_eq!(deserialized.data[1].name, "Item 2");
     }
 
+    #[test]
+    fn test_list_response_next_cursor() {
+        let json = r#"{"data": [], "next_cursor": "abc"}"#;
+        let response: ListResponse<TestItem> = serde_json::from_str(json).unwrap();
+        assert_eq!(response.next_cursor.as_deref(), Some("abc"));
+
+        let response: ListResponse<TestItem> = serde_json::from_str(r#"{"data": []}"#).unwrap();
+        assert_eq!(response.next_cursor, None);
+        // Absent cursors aren't written back out
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"data":[]}"#);
+    }
+
     #[test]
     fn test_get_response_serialization() {
         let item = TestItem {
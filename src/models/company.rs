@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::Cacheable;
+use super::record::{RecordId, first_value};
+
+/// A record of the `companies` object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Company {
+    pub id: RecordId,
+    pub created_at: String,
+    #[serde(default)]
+    pub values: serde_json::Value,
+}
+
+impl Company {
+    pub fn name(&self) -> Option<&str> {
+        first_value(&self.values, "name")?.get("value")?.as_str()
+    }
+
+    /// The first of the company's domains
+    pub fn domain(&self) -> Option<&str> {
+        first_value(&self.values, "domains")?
+            .get("domain")?
+            .as_str()
+    }
+}
+
+impl Cacheable for Company {
+    /// Estimate the memory size of this company in bytes
+    fn estimate_size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.id.workspace_id.capacity()
+            + self.id.object_id.capacity()
+            + self.id.record_id.capacity()
+            + self.created_at.capacity()
+            + self.values.to_string().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{GetCompanyResponse, ListCompaniesResponse};
+
+    #[test]
+    fn test_deserialize_companies() {
+        let json = r#"
+        {
+            "data": [
+                {
+                    "id": {
+                        "workspace_id": "ws_123",
+                        "object_id": "obj_companies",
+                        "record_id": "rec_1"
+                    },
+                    "created_at": "2024-02-10T12:30:00.000000000Z",
+                    "web_url": "https://app.attio.com/acme/company/rec_1",
+                    "values": {
+                        "name": [{
+                            "active_from": "2024-02-10T12:30:00.000000000Z",
+                            "active_until": null,
+                            "value": "Acme Inc",
+                            "attribute_type": "text"
+                        }],
+                        "domains": [
+                            {
+                                "domain": "acme.com",
+                                "root_domain": "acme.com",
+                                "attribute_type": "domain"
+                            },
+                            { "domain": "acme.io", "root_domain": "acme.io", "attribute_type": "domain" }
+                        ],
+                        "employee_range": []
+                    }
+                }
+            ]
+        }
+        "#;
+        let companies: ListCompaniesResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(companies.data.len(), 1);
+        let acme = &companies.data[0];
+        assert_eq!(acme.id.record_id, "rec_1");
+        assert_eq!(acme.name(), Some("Acme Inc"));
+        assert_eq!(acme.domain(), Some("acme.com"));
+        assert_eq!(acme.created_at, "2024-02-10T12:30:00.000000000Z");
+    }
+
+    #[test]
+    fn test_company_without_values() {
+        let json = r#"
+        {
+            "data": {
+                "id": { "workspace_id": "ws", "object_id": "obj", "record_id": "rec_2" },
+                "created_at": "2024-02-10T12:30:00Z"
+            }
+        }
+        "#;
+        let company = serde_json::from_str::<GetCompanyResponse>(json)
+            .unwrap()
+            .data;
+
+        assert_eq!(company.name(), None);
+        assert_eq!(company.domain(), None);
+    }
+}
@@ -1,23 +1,658 @@
+use super::common::IdentifyResponse;
+use crate::client::DEFAULT_BASE_URL;
+use crate::notify::NotifyMode;
+use crate::symbols::SymbolSet;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
+    /// Legacy single-workspace token, used when no profile is active
+    #[serde(default)]
     pub token: String,
+    /// Named workspaces, each with its own token
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Profile whose token and cache limit are used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    /// API root, e.g. a corporate proxy in front of Attio
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
     #[serde(default = "default_cache_limit_mb")]
     pub cache_limit_mb: u64,
+    /// Keep browsed notes in the TUI's cache; when off, only the current page
+    /// is held and every page change is fetched again
+    #[serde(default = "default_cache_enabled")]
+    pub cache_enabled: bool,
+    /// How long `notes get` trusts a note from the disk cache
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// Whether the config file token or the token environment variable wins
+    /// when both are set
+    #[serde(default)]
+    pub token_source_priority: TokenSourcePriority,
+    /// Environment variable the token is read from
+    #[serde(default = "default_token_env_var")]
+    pub token_env_var: String,
+    /// Whether tokens are kept in this file or in the OS keyring
+    #[serde(default)]
+    pub token_storage: TokenStorage,
+    /// Status symbols to print; detected from the terminal when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbols: Option<SymbolSet>,
+    /// How to signal that fetch-all and other long operations have finished
+    #[serde(default)]
+    pub notify: NotifyMode,
+    /// Retries for rate-limited (429) and 5xx responses
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+    /// Failed requests in a row (5xx, timeouts, connection errors) after
+    /// which no more are sent for `breaker_cooldown_secs`; 0 turns this off
+    #[serde(default = "default_breaker_threshold")]
+    pub breaker_threshold: u32,
+    #[serde(default = "default_breaker_cooldown_secs")]
+    pub breaker_cooldown_secs: u64,
+    /// Pause between pages while the TUI fetches all notes
+    #[serde(default = "default_fetch_delay_ms")]
+    pub fetch_delay_ms: u64,
+    /// Pages the TUI fetch-all requests at once
+    #[serde(default = "default_fetch_concurrency")]
+    pub fetch_concurrency: usize,
+    /// Notes after which the TUI fetch-all stops; 0 means no cap
+    #[serde(default = "default_fetch_all_max")]
+    pub fetch_all_max: usize,
+    /// Seconds between re-fetches of the first page in the TUI; 0 turns
+    /// auto-refresh off
+    #[serde(default)]
+    pub refresh_interval_secs: u64,
+    /// Limit for connecting and for each whole API request
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Ask the API for gzip/brotli/deflate-compressed responses
+    #[serde(default = "default_compression")]
+    pub compression: bool,
+    /// Let the TUI take mouse clicks and scrolling; off leaves text
+    /// selection to the terminal
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+    /// Built-in TUI palette
+    #[serde(default)]
+    pub theme: ThemeName,
+    /// TUI colors replacing the theme's, as color key to `#rrggbb`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub theme_colors: BTreeMap<String, String>,
+    /// Parent object for `notes create` when `--parent-object` is omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_parent_object: Option<String>,
+    /// Parent record for `notes create`, used together with `default_parent_object`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_parent_record_id: Option<String>,
+    /// Workspace details from the last identify call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<CachedIdentity>,
+    /// Where logs go while the TUI is running; an OS cache directory when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+    /// Token set by the project config, which wins over profile and keyring
+    /// tokens; never saved
+    #[serde(skip)]
+    pub project_token: Option<String>,
+}
+
+/// Workspace details saved after an identify call, so commands that only
+/// need the slug can skip the request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CachedIdentity {
+    /// `token_fingerprint` of the token the details were fetched with
+    pub token_fingerprint: String,
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub workspace_name: Option<String>,
+    #[serde(default)]
+    pub workspace_slug: Option<String>,
+    /// ETag of the identify response, sent back as `If-None-Match`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The whole identify response, reused when the API answers 304
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<IdentifyResponse>,
+}
+
+/// A stable 64-bit FNV-1a hash of `token` in hex. Identifies which token
+/// cached details belong to without storing another copy of a token that may
+/// only live in the environment.
+pub fn token_fingerprint(token: &str) -> String {
+    let hash = token.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Credentials and limits for one workspace
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Profile {
+    pub token: String,
+    /// Overrides the top-level `cache_limit_mb` while this profile is active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_limit_mb: Option<u64>,
+}
+
+/// Order in which token sources are consulted
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenSourcePriority {
+    #[default]
+    ConfigFirst,
+    EnvFirst,
+}
+
+impl TokenSourcePriority {
+    pub const VARIANTS: &'static [&'static str] = &["config-first", "env-first"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "config-first" => Some(Self::ConfigFirst),
+            "env-first" => Some(Self::EnvFirst),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConfigFirst => "config-first",
+            Self::EnvFirst => "env-first",
+        }
+    }
+
+    /// Picks the first non-blank token in priority order, trimmed
+    pub fn resolve(&self, config_token: Option<&str>, env_token: Option<&str>) -> Option<String> {
+        let ordered = match self {
+            Self::ConfigFirst => [config_token, env_token],
+            Self::EnvFirst => [env_token, config_token],
+        };
+        ordered
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .find(|token| !token.is_empty())
+            .map(str::to_string)
+    }
+}
+
+/// Where `auth` saves tokens and `get_token` looks for them
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenStorage {
+    /// In the config file, readable by anyone who can read the file
+    #[default]
+    File,
+    /// In the OS keychain (macOS Keychain, Windows Credential Manager, Secret Service)
+    Keyring,
+}
+
+impl TokenStorage {
+    pub const VARIANTS: &'static [&'static str] = &["file", "keyring"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "file" => Some(Self::File),
+            "keyring" => Some(Self::Keyring),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Keyring => "keyring",
+        }
+    }
+}
+
+/// Built-in palettes for the TUI
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    /// The original colors, readable on most terminals
+    #[default]
+    Default,
+    /// Brighter colors for dark backgrounds
+    Dark,
+    /// Deeper colors that stay readable on light backgrounds
+    Light,
+    /// No colors, only bold and reversed text
+    Monochrome,
+}
+
+impl ThemeName {
+    pub const VARIANTS: &'static [&'static str] = &["default", "dark", "light", "monochrome"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "default" => Some(Self::Default),
+            "dark" => Some(Self::Dark),
+            "light" => Some(Self::Light),
+            "monochrome" => Some(Self::Monochrome),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Monochrome => "monochrome",
+        }
+    }
+}
+
+/// Environment variable that replaces the default config directory
+pub const CONFIG_DIR_ENV: &str = "ATTIO_CONFIG_DIR";
+
+/// Environment variable the token is read from unless `token_env_var` says otherwise
+pub const DEFAULT_TOKEN_ENV_VAR: &str = "ATTIO_API_TOKEN";
+
+/// Where the config file lives, in order of precedence: the `--config` flag,
+/// `config.json` in the `ATTIO_CONFIG_DIR` directory, then
+/// `attio/config.json` in the OS config directory. `~` and `$VAR` in the
+/// first two are expanded using `var`.
+pub fn resolve_config_path(
+    flag: Option<&Path>,
+    default_dir: Option<PathBuf>,
+    var: impl Fn(&str) -> Option<String>,
+) -> PathBuf {
+    if let Some(flag) = flag {
+        return PathBuf::from(expand_path(&flag.to_string_lossy(), &var));
+    }
+    if let Some(dir) = var(CONFIG_DIR_ENV).filter(|dir| !dir.trim().is_empty()) {
+        return PathBuf::from(expand_path(dir.trim(), &var)).join("config.json");
+    }
+    default_dir
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("attio")
+        .join("config.json")
+}
+
+/// Directory holding a project's config file, e.g. `.attio/config.json`
+pub const LOCAL_CONFIG_DIR: &str = ".attio";
+
+/// The nearest project config: `.attio/config.json` in `start` or the closest
+/// ancestor directory that has one
+pub fn find_local_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(LOCAL_CONFIG_DIR).join("config.json"))
+        .find(|path| path.is_file())
+}
+
+/// Keys a project config may set. Where the token comes from and where it is
+/// sent (`base_url`, `token_env_var`, `token_storage`, profiles) and where
+/// logs are written stay global, so a checkout can't redirect them.
+pub const PROJECT_KEYS: &[&str] = &[
+    "token",
+    "default_parent_object",
+    "default_parent_record_id",
+    "symbols",
+    "notify",
+    "mouse",
+    "theme",
+    "theme_colors",
+];
+
+/// `global` with each top-level key set in `local` replaced by the local
+/// value; keys outside `PROJECT_KEYS` are left alone
+pub fn overlay(mut global: Value, local: &Value) -> Value {
+    if let (Some(global), Some(local)) = (global.as_object_mut(), local.as_object()) {
+        for (key, value) in local {
+            if PROJECT_KEYS.contains(&key.as_str()) {
+                global.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    global
+}
+
+/// Keys in `local` that `overlay` ignores
+pub fn ignored_project_keys(local: &Value) -> Vec<&str> {
+    local
+        .as_object()
+        .into_iter()
+        .flat_map(|local| local.keys())
+        .map(String::as_str)
+        .filter(|key| !PROJECT_KEYS.contains(key))
+        .collect()
+}
+
+/// The global file's contents after saving `updated`, a config read with the
+/// project config on top. Keys the project config sets keep their global
+/// value, unless the command changed them.
+pub fn without_overrides(mut updated: Value, global: &Value, local: &Value) -> Value {
+    if let (Some(fields), Some(local)) = (updated.as_object_mut(), local.as_object()) {
+        for (key, local_value) in local {
+            if fields.get(key) != Some(local_value) {
+                continue;
+            }
+            match global.get(key) {
+                Some(global_value) => fields.insert(key.clone(), global_value.clone()),
+                None => fields.remove(key),
+            };
+        }
+    }
+    updated
+}
+
+/// The project file's contents after saving `updated`: the keys it already
+/// had, plus any of `PROJECT_KEYS` that now differ from the `global` config
+pub fn local_overrides(updated: &Value, global: &Value, local: &Value) -> Value {
+    let mut overrides = local.as_object().cloned().unwrap_or_default();
+    for (key, value) in updated.as_object().into_iter().flatten() {
+        if overrides.contains_key(key)
+            || (PROJECT_KEYS.contains(&key.as_str()) && global.get(key) != Some(value))
+        {
+            overrides.insert(key.clone(), value.clone());
+        }
+    }
+    Value::Object(overrides)
+}
+
+/// Expands a leading `~` and `$VAR` / `${VAR}` references. Variables that
+/// aren't set are left as written.
+pub fn expand_path(raw: &str, var: &impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::new();
+    let mut rest = raw;
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        let home = var("HOME").or_else(|| dirs::home_dir().map(|h| h.display().to_string()));
+        if let Some(home) = home {
+            expanded.push_str(&home);
+            rest = &rest[1..];
+        }
+    }
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, consumed) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        match var(name).filter(|_| !name.is_empty()) {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[start..start + 1 + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+fn default_token_env_var() -> String {
+    DEFAULT_TOKEN_ENV_VAR.to_string()
 }
 
 fn default_cache_limit_mb() -> u64 {
     50
 }
 
+fn default_cache_enabled() -> bool {
+    true
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_compression() -> bool {
+    true
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+fn default_base_url() -> String {
+    DEFAULT_BASE_URL.to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    500
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    30_000
+}
+
+fn default_breaker_threshold() -> u32 {
+    5
+}
+
+fn default_breaker_cooldown_secs() -> u64 {
+    30
+}
+
+fn default_fetch_delay_ms() -> u64 {
+    200
+}
+
+fn default_fetch_concurrency() -> usize {
+    1
+}
+
+fn default_fetch_all_max() -> usize {
+    10_000
+}
+
 impl Config {
     pub fn new(token: String) -> Self {
         Self {
             token,
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            base_url: default_base_url(),
             cache_limit_mb: default_cache_limit_mb(),
+            cache_enabled: default_cache_enabled(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+            token_source_priority: TokenSourcePriority::default(),
+            token_env_var: default_token_env_var(),
+            token_storage: TokenStorage::default(),
+            symbols: None,
+            notify: NotifyMode::default(),
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            retry_max_delay_ms: default_retry_max_delay_ms(),
+            breaker_threshold: default_breaker_threshold(),
+            breaker_cooldown_secs: default_breaker_cooldown_secs(),
+            fetch_delay_ms: default_fetch_delay_ms(),
+            refresh_interval_secs: 0,
+            fetch_concurrency: default_fetch_concurrency(),
+            fetch_all_max: default_fetch_all_max(),
+            timeout_secs: default_timeout_secs(),
+            compression: default_compression(),
+            mouse: default_mouse(),
+            theme: ThemeName::default(),
+            theme_colors: BTreeMap::new(),
+            default_parent_object: None,
+            default_parent_record_id: None,
+            identity: None,
+            log_file: None,
+            project_token: None,
+        }
+    }
+
+    fn active(&self) -> Option<&Profile> {
+        self.profiles.get(self.active_profile.as_deref()?)
+    }
+
+    fn active_mut(&mut self) -> Option<&mut Profile> {
+        self.profiles.get_mut(self.active_profile.as_deref()?)
+    }
+
+    /// Token of the active profile, or the legacy top-level token
+    pub fn active_token(&self) -> &str {
+        self.active().map_or(&self.token, |profile| &profile.token)
+    }
+
+    /// Name of the active profile, if it exists
+    pub fn active_profile_name(&self) -> Option<&str> {
+        self.active().and(self.active_profile.as_deref())
+    }
+
+    /// Stores `token` in the active profile, or at the top level when none is active
+    pub fn set_token(&mut self, token: String) {
+        match self.active_mut() {
+            Some(profile) => profile.token = token,
+            None => self.token = token,
+        }
+    }
+
+    pub fn effective_cache_limit_mb(&self) -> u64 {
+        self.active()
+            .and_then(|profile| profile.cache_limit_mb)
+            .unwrap_or(self.cache_limit_mb)
+    }
+
+    /// `effective_cache_limit_mb` in bytes, the size both the TUI and the
+    /// on-disk notes cache are held to
+    pub fn cache_limit_bytes(&self) -> usize {
+        self.effective_cache_limit_mb() as usize * 1024 * 1024
+    }
+
+    /// Whether the TUI keeps a cache at all; a cache limit of 0 also turns it off
+    pub fn tui_cache_enabled(&self) -> bool {
+        self.cache_enabled && self.effective_cache_limit_mb() > 0
+    }
+
+    /// Stores the cache limit on the active profile, or at the top level when none is active
+    pub fn set_cache_limit_mb(&mut self, limit: u64) {
+        match self.active_mut() {
+            Some(profile) => profile.cache_limit_mb = Some(limit),
+            None => self.cache_limit_mb = limit,
+        }
+    }
+
+    /// Puts the setting behind the `config set` key `key` back to its
+    /// default. With a profile active, `cache-limit-mb` falls back to the
+    /// top-level limit. Returns false for unknown keys.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let defaults = Config::new(String::new());
+        match key {
+            "base-url" => self.base_url = defaults.base_url,
+            "cache-limit-mb" => match self.active_mut() {
+                Some(profile) => profile.cache_limit_mb = None,
+                None => self.cache_limit_mb = defaults.cache_limit_mb,
+            },
+            "cache-enabled" => self.cache_enabled = defaults.cache_enabled,
+            "cache-ttl-secs" => self.cache_ttl_secs = defaults.cache_ttl_secs,
+            "token-source-priority" => self.token_source_priority = defaults.token_source_priority,
+            "token-env-var" => self.token_env_var = defaults.token_env_var,
+            "token-storage" => self.token_storage = defaults.token_storage,
+            "symbols" => self.symbols = defaults.symbols,
+            "notify" => self.notify = defaults.notify,
+            "compression" => self.compression = defaults.compression,
+            "mouse" => self.mouse = defaults.mouse,
+            "theme" => self.theme = defaults.theme,
+            "theme-colors" => self.theme_colors = defaults.theme_colors,
+            "default-parent-object" => self.default_parent_object = defaults.default_parent_object,
+            "default-parent-record-id" => {
+                self.default_parent_record_id = defaults.default_parent_record_id
+            }
+            "max-retries" => self.max_retries = defaults.max_retries,
+            "retry-base-delay-ms" => self.retry_base_delay_ms = defaults.retry_base_delay_ms,
+            "breaker-threshold" => self.breaker_threshold = defaults.breaker_threshold,
+            "breaker-cooldown-secs" => self.breaker_cooldown_secs = defaults.breaker_cooldown_secs,
+            "timeout-secs" => self.timeout_secs = defaults.timeout_secs,
+            "fetch-delay-ms" => self.fetch_delay_ms = defaults.fetch_delay_ms,
+            "refresh-interval-secs" => self.refresh_interval_secs = defaults.refresh_interval_secs,
+            "fetch-concurrency" => self.fetch_concurrency = defaults.fetch_concurrency,
+            "fetch-all-max" => self.fetch_all_max = defaults.fetch_all_max,
+            "log-file" => self.log_file = defaults.log_file,
+            _ => return false,
+        }
+        true
+    }
+
+    /// A config with every setting at its default, keeping the token,
+    /// profiles and cached workspace details
+    pub fn reset(&self) -> Config {
+        Config {
+            profiles: self.profiles.clone(),
+            active_profile: self.active_profile.clone(),
+            identity: self.identity.clone(),
+            ..Config::new(self.token.clone())
         }
     }
+
+    /// The configured log file, or the default location
+    pub fn effective_log_file(&self) -> PathBuf {
+        self.log_file
+            .clone()
+            .unwrap_or_else(crate::logging::default_log_file)
+    }
+
+    /// The cached workspace details, if they were fetched with `token`
+    pub fn cached_identity(&self, token: &str) -> Option<&CachedIdentity> {
+        self.identity
+            .as_ref()
+            .filter(|identity| identity.token_fingerprint == token_fingerprint(token))
+    }
+
+    /// Remembers the workspace details `token` resolved to, and the ETag
+    /// they came with
+    pub fn cache_identity(
+        &mut self,
+        token: &str,
+        identity: &IdentifyResponse,
+        etag: Option<String>,
+    ) {
+        self.identity = Some(CachedIdentity {
+            token_fingerprint: token_fingerprint(token),
+            workspace_id: identity.workspace_id.clone(),
+            workspace_name: identity.workspace_name.clone(),
+            workspace_slug: identity.workspace_slug.clone(),
+            etag,
+            response: Some(identity.clone()),
+        });
+    }
+
+    pub fn use_profile(&mut self, name: &str) -> Result<(), String> {
+        if !self.profiles.contains_key(name) {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            return Err(if known.is_empty() {
+                format!(
+                    "Unknown profile '{}'. Add one with `attio config profile add <name>`.",
+                    name
+                )
+            } else {
+                format!("Unknown profile '{}'. Profiles: {}", name, known.join(", "))
+            });
+        }
+        self.active_profile = Some(name.to_string());
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -29,13 +664,100 @@ mod tests {
         let config = Config::new("test_token".to_string());
         assert_eq!(config.token, "test_token");
         assert_eq!(config.cache_limit_mb, 50);
+        assert!(config.tui_cache_enabled());
+        assert_eq!(
+            config.token_source_priority,
+            TokenSourcePriority::ConfigFirst
+        );
+    }
+
+    #[test]
+    fn test_unset_restores_defaults() {
+        let mut config = Config::new("token".to_string());
+        config.cache_limit_mb = 10;
+        config.mouse = false;
+        config.log_file = Some(PathBuf::from("/tmp/attio.log"));
+
+        assert!(config.unset("cache-limit-mb"));
+        assert!(config.unset("mouse"));
+        assert!(config.unset("log-file"));
+        assert_eq!(config.cache_limit_mb, 50);
+        assert!(config.mouse);
+        assert_eq!(config.log_file, None);
+        assert!(!config.unset("no-such-key"));
+    }
+
+    #[test]
+    fn test_unset_cache_limit_falls_back_to_top_level_for_profile() {
+        let mut config = Config::new(String::new());
+        config.cache_limit_mb = 20;
+        config.profiles.insert(
+            "work".to_string(),
+            Profile {
+                token: "work-token".to_string(),
+                cache_limit_mb: Some(5),
+            },
+        );
+        config.active_profile = Some("work".to_string());
+
+        assert!(config.unset("cache-limit-mb"));
+        assert_eq!(config.effective_cache_limit_mb(), 20);
+    }
+
+    #[test]
+    fn test_reset_keeps_token_and_profiles() {
+        let mut config = Config::new("token".to_string());
+        config.timeout_secs = 5;
+        config.default_parent_object = Some("people".to_string());
+        config.profiles.insert(
+            "work".to_string(),
+            Profile {
+                token: "work-token".to_string(),
+                cache_limit_mb: None,
+            },
+        );
+
+        let reset = config.reset();
+        assert_eq!(reset.token, "token");
+        assert!(reset.profiles.contains_key("work"));
+        assert_eq!(reset.timeout_secs, Config::new(String::new()).timeout_secs);
+        assert_eq!(reset.default_parent_object, None);
     }
 
     #[test]
     fn test_config_serialization() {
         let config = Config {
             token: "my_token".to_string(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            base_url: "https://proxy.example.com/v2".to_string(),
             cache_limit_mb: 100,
+            cache_enabled: false,
+            cache_ttl_secs: 60,
+            token_source_priority: TokenSourcePriority::EnvFirst,
+            token_env_var: "CI_ATTIO_TOKEN".to_string(),
+            token_storage: TokenStorage::Keyring,
+            symbols: Some(SymbolSet::Ascii),
+            notify: NotifyMode::Desktop,
+            max_retries: 5,
+            retry_base_delay_ms: 100,
+            retry_max_delay_ms: 2_000,
+            breaker_threshold: 0,
+            breaker_cooldown_secs: 5,
+            fetch_delay_ms: 0,
+            refresh_interval_secs: 15,
+            fetch_concurrency: 4,
+            fetch_all_max: 0,
+            timeout_secs: 10,
+            compression: false,
+            mouse: false,
+            theme: ThemeName::Light,
+            theme_colors: BTreeMap::from([("header".to_string(), "#ff8800".to_string())]),
+            default_parent_object: Some("companies".to_string()),
+            default_parent_record_id: None,
+            identity: None,
+            log_file: Some(PathBuf::from("/var/log/attio.log")),
+            project_token: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -43,6 +765,237 @@ mod tests {
 
         assert_eq!(deserialized.token, "my_token");
         assert_eq!(deserialized.cache_limit_mb, 100);
+        assert!(!deserialized.cache_enabled);
+        assert_eq!(
+            deserialized.token_source_priority,
+            TokenSourcePriority::EnvFirst
+        );
+        assert_eq!(deserialized.token_env_var, "CI_ATTIO_TOKEN");
+        assert_eq!(deserialized.token_storage, TokenStorage::Keyring);
+        assert!(json.contains(r#""token_storage":"keyring""#));
+        assert_eq!(deserialized.symbols, Some(SymbolSet::Ascii));
+        assert_eq!(deserialized.notify, NotifyMode::Desktop);
+        assert_eq!(deserialized.max_retries, 5);
+        assert_eq!(deserialized.retry_base_delay_ms, 100);
+        assert_eq!(deserialized.retry_max_delay_ms, 2_000);
+        assert_eq!(deserialized.breaker_threshold, 0);
+        assert_eq!(deserialized.fetch_delay_ms, 0);
+        assert_eq!(deserialized.refresh_interval_secs, 15);
+        assert_eq!(deserialized.fetch_concurrency, 4);
+        assert_eq!(deserialized.fetch_all_max, 0);
+        assert!(!deserialized.compression);
+        assert!(!deserialized.mouse);
+        assert_eq!(deserialized.theme, ThemeName::Light);
+        assert_eq!(deserialized.theme_colors["header"], "#ff8800");
+        assert_eq!(
+            deserialized.effective_log_file(),
+            PathBuf::from("/var/log/attio.log")
+        );
+    }
+
+    /// A fake environment
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn test_config_path_defaults_to_os_config_dir() {
+        let path = resolve_config_path(None, Some(PathBuf::from("/home/me/.config")), env(&[]));
+        assert_eq!(path, PathBuf::from("/home/me/.config/attio/config.json"));
+    }
+
+    #[test]
+    fn test_config_dir_env_overrides_default() {
+        let path = resolve_config_path(
+            None,
+            Some(PathBuf::from("/home/me/.config")),
+            env(&[(CONFIG_DIR_ENV, "/etc/attio")]),
+        );
+        assert_eq!(path, PathBuf::from("/etc/attio/config.json"));
+
+        // A blank value is ignored
+        let path = resolve_config_path(
+            None,
+            Some(PathBuf::from("/home/me/.config")),
+            env(&[(CONFIG_DIR_ENV, " ")]),
+        );
+        assert_eq!(path, PathBuf::from("/home/me/.config/attio/config.json"));
+    }
+
+    #[test]
+    fn test_config_flag_beats_env() {
+        let path = resolve_config_path(
+            Some(Path::new("./ci.json")),
+            None,
+            env(&[(CONFIG_DIR_ENV, "/etc/attio")]),
+        );
+        assert_eq!(path, PathBuf::from("./ci.json"));
+    }
+
+    #[test]
+    fn test_config_paths_expand_variables() {
+        let vars = env(&[
+            (CONFIG_DIR_ENV, "$WORKSPACE/${STAGE}-config"),
+            ("WORKSPACE", "/builds/42"),
+            ("STAGE", "prod"),
+        ]);
+        let path = resolve_config_path(None, None, vars);
+        assert_eq!(path, PathBuf::from("/builds/42/prod-config/config.json"));
+
+        let path = resolve_config_path(
+            Some(Path::new("~/attio.json")),
+            None,
+            env(&[("HOME", "/home/me")]),
+        );
+        assert_eq!(path, PathBuf::from("/home/me/attio.json"));
+    }
+
+    #[test]
+    fn test_find_local_config_walks_up() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("project").join("src").join("deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_local_config(&nested), None);
+
+        let local = root.path().join("project").join(LOCAL_CONFIG_DIR);
+        std::fs::create_dir_all(&local).unwrap();
+        // A directory without a config file isn't a match
+        assert_eq!(find_local_config(&nested), None);
+
+        std::fs::write(local.join("config.json"), "{}").unwrap();
+        assert_eq!(find_local_config(&nested), Some(local.join("config.json")));
+    }
+
+    #[test]
+    fn test_local_config_overrides_global() {
+        let global = serde_json::json!({"token": "global", "cache_limit_mb": 50});
+        let local = serde_json::json!({"token": "project"});
+        let merged: Config = serde_json::from_value(overlay(global, &local)).unwrap();
+        assert_eq!(merged.token, "project");
+        assert_eq!(merged.cache_limit_mb, 50);
+    }
+
+    #[test]
+    fn test_local_config_cannot_redirect_the_token() {
+        let global = serde_json::json!({"token": "global", "base_url": "https://api.attio.com/v2"});
+        let local = serde_json::json!({
+            "base_url": "https://evil.example",
+            "token_env_var": "OTHER",
+            "log_file": "/tmp/log",
+            "theme": "dark",
+        });
+        let merged: Config = serde_json::from_value(overlay(global, &local)).unwrap();
+        assert_eq!(merged.token, "global");
+        assert_eq!(merged.base_url, "https://api.attio.com/v2");
+        assert_eq!(merged.token_env_var, default_token_env_var());
+        assert_eq!(merged.log_file, None);
+        assert_eq!(merged.theme, ThemeName::Dark);
+        assert_eq!(
+            ignored_project_keys(&local),
+            ["base_url", "log_file", "token_env_var"]
+        );
+    }
+
+    #[test]
+    fn test_global_write_keeps_local_values_out() {
+        let global = serde_json::json!({"token": "global", "cache_limit_mb": 50});
+        let local = serde_json::json!({"token": "project", "mouse": false});
+        let updated = serde_json::json!({"token": "project", "mouse": false, "cache_limit_mb": 10});
+
+        assert_eq!(
+            without_overrides(updated, &global, &local),
+            serde_json::json!({"token": "global", "cache_limit_mb": 10})
+        );
+
+        // A key the command changed is saved globally even if it's overridden
+        let updated = serde_json::json!({"token": "new", "mouse": false, "cache_limit_mb": 50});
+        assert_eq!(without_overrides(updated, &global, &local)["token"], "new");
+    }
+
+    #[test]
+    fn test_local_write_only_stores_differences() {
+        let global =
+            serde_json::json!({"token": "global", "cache_limit_mb": 50, "theme": "default"});
+        let local = serde_json::json!({"token": "project"});
+        let updated =
+            serde_json::json!({"token": "project", "cache_limit_mb": 50, "theme": "dark"});
+
+        assert_eq!(
+            local_overrides(&updated, &global, &local),
+            serde_json::json!({"token": "project", "theme": "dark"})
+        );
+
+        // Keys a project can't set are never written to it
+        let updated = serde_json::json!({"token": "project", "cache_limit_mb": 10});
+        assert_eq!(
+            local_overrides(&updated, &global, &local),
+            serde_json::json!({"token": "project"})
+        );
+    }
+
+    #[test]
+    fn test_expand_path_leaves_unknown_variables() {
+        let vars = env(&[("HOME", "/home/me")]);
+        assert_eq!(expand_path("$MISSING/x", &vars), "$MISSING/x");
+        assert_eq!(expand_path("${MISSING}/x", &vars), "${MISSING}/x");
+        assert_eq!(expand_path("${unclosed", &vars), "${unclosed");
+        assert_eq!(expand_path("cost$", &vars), "cost$");
+        assert_eq!(expand_path("a~b", &vars), "a~b");
+    }
+
+    fn identify(slug: &str) -> IdentifyResponse {
+        IdentifyResponse {
+            active: true,
+            workspace_id: Some("ws_1".to_string()),
+            workspace_name: Some("Acme".to_string()),
+            workspace_slug: Some(slug.to_string()),
+            authorized_by_workspace_member_id: None,
+        }
+    }
+
+    #[test]
+    fn test_cached_identity_is_keyed_by_token() {
+        let mut config = Config::new("token_a".to_string());
+        assert!(config.cached_identity("token_a").is_none());
+
+        config.cache_identity("token_a", &identify("acme"), None);
+        let cached = config.cached_identity("token_a").unwrap();
+        assert_eq!(cached.workspace_slug.as_deref(), Some("acme"));
+        assert_eq!(cached.workspace_name.as_deref(), Some("Acme"));
+        // Switching tokens invalidates it
+        assert!(config.cached_identity("token_b").is_none());
+    }
+
+    #[test]
+    fn test_cached_identity_does_not_store_the_token() {
+        let mut config = Config::new(String::new());
+        config.cache_identity(
+            "secret_env_token",
+            &identify("acme"),
+            Some("\"v1\"".to_string()),
+        );
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("secret_env_token"));
+        let restored: Config = serde_json::from_str(&json).unwrap();
+        let cached = restored.cached_identity("secret_env_token").unwrap();
+        assert_eq!(cached.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(cached.response, Some(identify("acme")));
+    }
+
+    #[test]
+    fn test_token_fingerprint_is_stable() {
+        // FNV-1a test vectors
+        assert_eq!(token_fingerprint(""), "cbf29ce484222325");
+        assert_eq!(token_fingerprint("a"), "af63dc4c8601ec8c");
     }
 
     #[test]
@@ -53,6 +1006,20 @@ mod tests {
 
         assert_eq!(config.token, "old_token");
         assert_eq!(config.cache_limit_mb, 50); // Should use default
+        assert_eq!(config.cache_ttl_secs, 300);
+        assert_eq!(config.base_url, "https://api.attio.com/v2");
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.retry_base_delay_ms, 500);
+        assert_eq!(config.breaker_threshold, 5);
+        assert_eq!(config.timeout_secs, 30);
+        assert_eq!(config.fetch_delay_ms, 200);
+        assert_eq!(config.refresh_interval_secs, 0);
+        assert_eq!(config.fetch_concurrency, 1);
+        assert_eq!(config.fetch_all_max, 10_000);
+        assert_eq!(config.token_env_var, "ATTIO_API_TOKEN");
+        assert!(config.compression);
+        assert!(config.mouse);
+        assert!(config.cache_enabled);
     }
 
     #[test]
@@ -62,4 +1029,143 @@ mod tests {
 
         assert_eq!(config.cache_limit_mb, 200);
     }
+
+    #[test]
+    fn test_zero_cache_limit_disables_the_cache() {
+        let json = r#"{"token": "test", "cache_limit_mb": 0}"#;
+        let mut config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.cache_enabled);
+        assert!(!config.tui_cache_enabled());
+
+        config.cache_limit_mb = 50;
+        config.cache_enabled = false;
+        assert!(!config.tui_cache_enabled());
+    }
+
+    #[test]
+    fn test_token_priority_config_first() {
+        let priority = TokenSourcePriority::ConfigFirst;
+        assert_eq!(
+            priority.resolve(Some("config"), Some("env")),
+            Some("config".to_string())
+        );
+        assert_eq!(priority.resolve(None, Some("env")), Some("env".to_string()));
+    }
+
+    #[test]
+    fn test_token_priority_env_first() {
+        let priority = TokenSourcePriority::EnvFirst;
+        assert_eq!(
+            priority.resolve(Some("config"), Some("env")),
+            Some("env".to_string())
+        );
+        assert_eq!(
+            priority.resolve(Some("config"), None),
+            Some("config".to_string())
+        );
+    }
+
+    #[test]
+    fn test_token_priority_empty_token_falls_through() {
+        assert_eq!(
+            TokenSourcePriority::ConfigFirst.resolve(Some("  "), Some(" env ")),
+            Some("env".to_string())
+        );
+        assert_eq!(
+            TokenSourcePriority::EnvFirst.resolve(Some("config"), Some("")),
+            Some("config".to_string())
+        );
+        assert_eq!(TokenSourcePriority::EnvFirst.resolve(Some(""), None), None);
+    }
+
+    #[test]
+    fn test_token_priority_parse_round_trip() {
+        for name in TokenSourcePriority::VARIANTS {
+            assert_eq!(TokenSourcePriority::parse(name).unwrap().as_str(), *name);
+        }
+        assert_eq!(TokenSourcePriority::parse("env"), None);
+    }
+
+    fn profile(token: &str, cache_limit_mb: Option<u64>) -> Profile {
+        Profile {
+            token: token.to_string(),
+            cache_limit_mb,
+        }
+    }
+
+    #[test]
+    fn test_flat_config_uses_legacy_token() {
+        let config: Config =
+            serde_json::from_str(r#"{"token": "flat", "cache_limit_mb": 80}"#).unwrap();
+
+        assert!(config.profiles.is_empty());
+        assert_eq!(config.active_token(), "flat");
+        assert_eq!(config.effective_cache_limit_mb(), 80);
+    }
+
+    #[test]
+    fn test_active_profile_overrides_token_and_cache_limit() {
+        let mut config = Config::new("legacy".to_string());
+        config
+            .profiles
+            .insert("work".to_string(), profile("work_token", Some(200)));
+        config
+            .profiles
+            .insert("home".to_string(), profile("home_token", None));
+
+        config.use_profile("work").unwrap();
+        assert_eq!(config.active_token(), "work_token");
+        assert_eq!(config.effective_cache_limit_mb(), 200);
+
+        // Profiles without their own limit use the top-level one
+        config.use_profile("home").unwrap();
+        assert_eq!(config.active_token(), "home_token");
+        assert_eq!(config.effective_cache_limit_mb(), 50);
+    }
+
+    #[test]
+    fn test_setters_write_to_active_profile() {
+        let mut config = Config::new("legacy".to_string());
+        config
+            .profiles
+            .insert("work".to_string(), profile("old", None));
+        config.use_profile("work").unwrap();
+
+        config.set_token("new".to_string());
+        config.set_cache_limit_mb(75);
+
+        assert_eq!(config.profiles["work"], profile("new", Some(75)));
+        assert_eq!(config.token, "legacy");
+        assert_eq!(config.cache_limit_mb, 50);
+    }
+
+    #[test]
+    fn test_use_unknown_profile_fails() {
+        let mut config = Config::new("legacy".to_string());
+        config
+            .profiles
+            .insert("work".to_string(), profile("t", None));
+
+        let err = config.use_profile("wrok").unwrap_err();
+
+        assert!(err.contains("work"));
+        assert_eq!(config.active_profile, None);
+    }
+
+    #[test]
+    fn test_missing_active_profile_falls_back_to_legacy_token() {
+        let json = r#"{"token": "legacy", "active_profile": "gone"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.active_token(), "legacy");
+    }
+
+    #[test]
+    fn test_profiles_only_config_loads() {
+        let json = r#"{"profiles": {"work": {"token": "w"}}, "active_profile": "work"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.token, "");
+        assert_eq!(config.active_token(), "w");
+    }
 }
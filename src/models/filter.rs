@@ -0,0 +1,202 @@
+use serde_json::{Map, Value, json};
+
+/// Comparison in a `--where` condition
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Eq,
+    Contains,
+    Gt,
+    Lt,
+}
+
+impl Operator {
+    const ALL: [Self; 4] = [Self::Eq, Self::Contains, Self::Gt, Self::Lt];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Eq => "eq",
+            Self::Contains => "contains",
+            Self::Gt => "gt",
+            Self::Lt => "lt",
+        }
+    }
+
+    /// The operator's key in Attio's filter JSON
+    fn key(self) -> &'static str {
+        match self {
+            Self::Eq => "$eq",
+            Self::Contains => "$contains",
+            Self::Gt => "$gt",
+            Self::Lt => "$lt",
+        }
+    }
+}
+
+/// One `attribute operator value` condition, e.g. `employees gt 50`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    /// Attribute slug; a dotted path such as `name.full_name` filters on a
+    /// property of the attribute
+    pub attribute: String,
+    pub operator: Operator,
+    pub value: Value,
+}
+
+impl Condition {
+    /// Parses `attribute operator value`. Numbers, booleans and quoted
+    /// strings are read as JSON; anything else is taken as plain text.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parts = input.trim().splitn(3, char::is_whitespace);
+        let (Some(attribute), Some(operator), Some(raw)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!(
+                "expected 'attribute operator value', e.g. 'name contains Acme', got '{}'",
+                input
+            ));
+        };
+        let operator = Operator::ALL
+            .into_iter()
+            .find(|op| op.name() == operator)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Operator::ALL.iter().map(|op| op.name()).collect();
+                format!(
+                    "unknown operator '{}'. Valid operators: {}",
+                    operator,
+                    valid.join(", ")
+                )
+            })?;
+        let raw = raw.trim();
+        let value = match serde_json::from_str::<Value>(raw) {
+            Ok(value @ (Value::Number(_) | Value::Bool(_) | Value::String(_))) => value,
+            _ => Value::String(raw.to_string()),
+        };
+        Ok(Self {
+            attribute: attribute.to_string(),
+            operator,
+            value,
+        })
+    }
+
+    /// `{"attribute": {"$op": value}}`, nesting one object per path segment
+    pub fn to_json(&self) -> Value {
+        self.attribute.split('.').rev().fold(
+            json!({ self.operator.key(): self.value }),
+            |inner, segment| {
+                let mut outer = Map::new();
+                outer.insert(segment.to_string(), inner);
+                Value::Object(outer)
+            },
+        )
+    }
+}
+
+/// Parses a `--filter` argument, which must be a JSON object
+pub fn parse_filter_json(input: &str) -> Result<Value, String> {
+    match serde_json::from_str::<Value>(input) {
+        Ok(value @ Value::Object(_)) => Ok(value),
+        Ok(_) => Err("the filter must be a JSON object, e.g. '{\"name\": \"Acme\"}'".to_string()),
+        Err(e) => Err(format!("invalid filter JSON: {}", e)),
+    }
+}
+
+/// A records query filter: a raw JSON filter and/or conditions, all of which
+/// must hold
+#[derive(Debug, Default)]
+pub struct Filter {
+    pub raw: Option<Value>,
+    pub conditions: Vec<Condition>,
+}
+
+impl Filter {
+    /// The query body's `filter`, or `None` when there is nothing to filter on
+    pub fn to_json(&self) -> Option<Value> {
+        let mut clauses: Vec<Value> = self
+            .raw
+            .iter()
+            .cloned()
+            .chain(self.conditions.iter().map(Condition::to_json))
+            .collect();
+        match clauses.len() {
+            0 => None,
+            1 => clauses.pop(),
+            _ => Some(json!({ "$and": clauses })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_condition_values() {
+        let condition = Condition::parse("employees gt 50").unwrap();
+        assert_eq!(condition.operator, Operator::Gt);
+        assert_eq!(condition.value, json!(50));
+
+        // Text after the operator is kept whole, spaces included
+        let condition = Condition::parse("name contains Acme Inc").unwrap();
+        assert_eq!(condition.value, json!("Acme Inc"));
+
+        // Quoting keeps a number as text
+        let condition = Condition::parse(r#"zip eq "10115""#).unwrap();
+        assert_eq!(condition.value, json!("10115"));
+    }
+
+    #[test]
+    fn test_parse_condition_errors() {
+        assert!(Condition::parse("name contains").is_err());
+        let error = Condition::parse("name like Acme").unwrap_err();
+        assert_eq!(
+            error,
+            "unknown operator 'like'. Valid operators: eq, contains, gt, lt"
+        );
+    }
+
+    #[test]
+    fn test_multi_condition_filter_json() {
+        let filter = Filter {
+            raw: Some(json!({ "domains": { "domain": { "$eq": "acme.com" } } })),
+            conditions: vec![
+                Condition::parse("name.full_name contains Jane").unwrap(),
+                Condition::parse("employees lt 200").unwrap(),
+            ],
+        };
+
+        assert_eq!(
+            filter.to_json().unwrap(),
+            json!({
+                "$and": [
+                    { "domains": { "domain": { "$eq": "acme.com" } } },
+                    { "name": { "full_name": { "$contains": "Jane" } } },
+                    { "employees": { "$lt": 200 } }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_single_condition_is_not_wrapped() {
+        let filter = Filter {
+            conditions: vec![Condition::parse("stage eq Won").unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.to_json().unwrap(),
+            json!({ "stage": { "$eq": "Won" } })
+        );
+        assert_eq!(Filter::default().to_json(), None);
+    }
+
+    #[test]
+    fn test_parse_filter_json_requires_object() {
+        assert!(parse_filter_json(r#"{"name": "Acme"}"#).is_ok());
+        assert!(parse_filter_json("[1, 2]").is_err());
+        assert!(
+            parse_filter_json("{name: Acme}")
+                .unwrap_err()
+                .starts_with("invalid filter JSON")
+        );
+    }
+}
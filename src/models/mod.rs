@@ -3,15 +3,51 @@ mod common;
 pub use common::*;
 
 // Resource modules
+pub mod attachment;
+pub mod comment;
+pub mod company;
 pub mod config;
+pub mod filter;
 pub mod note;
+pub mod object;
+pub mod person;
+pub mod record;
+pub mod task;
+pub mod webhook;
 
 // Re-export commonly used types
-pub use config::Config;
+pub use attachment::Attachment;
+pub use comment::{Actor, CommentRecord, CreateCommentData, Thread};
+pub use company::Company;
+pub use config::{Config, Profile, ThemeName, TokenSourcePriority, TokenStorage};
+pub use filter::{Condition, Filter};
 #[allow(unused_imports)]
 pub use note::{CreateNoteData, Note, NoteId};
+pub use object::Object;
+pub use person::Person;
+pub use record::{Record, RecordQuery, RecordSort};
+pub use task::{CreateTaskData, Task, TaskLink, UpdateTaskData};
+pub use webhook::{CreateWebhookData, Webhook, WebhookSubscription};
 
 // Type aliases for backward compatibility and convenience
 pub type ListNotesResponse = ListResponse<Note>;
 pub type GetNoteResponse = GetResponse<Note>;
 pub type CreateNoteRequest = CreateRequest<CreateNoteData>;
+pub type ListAttachmentsResponse = ListResponse<Attachment>;
+pub type ListRecordsResponse = ListResponse<Record>;
+pub type GetRecordResponse = GetResponse<Record>;
+pub type ListPeopleResponse = ListResponse<Person>;
+pub type GetPersonResponse = GetResponse<Person>;
+pub type ListCompaniesResponse = ListResponse<Company>;
+pub type GetCompanyResponse = GetResponse<Company>;
+pub type ListTasksResponse = ListResponse<Task>;
+pub type GetTaskResponse = GetResponse<Task>;
+pub type CreateTaskRequest = CreateRequest<CreateTaskData>;
+pub type UpdateTaskRequest = CreateRequest<UpdateTaskData>;
+pub type ListObjectsResponse = ListResponse<Object>;
+pub type ListWebhooksResponse = ListResponse<Webhook>;
+pub type GetWebhookResponse = GetResponse<Webhook>;
+pub type CreateWebhookRequest = CreateRequest<CreateWebhookData>;
+pub type ListThreadsResponse = ListResponse<Thread>;
+pub type GetCommentResponse = GetResponse<comment::Comment>;
+pub type CreateCommentRequest = CreateRequest<CreateCommentData>;
@@ -1,8 +1,11 @@
+use chrono::{DateTime, ParseError, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 use super::common::Cacheable;
+use crate::markdown;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Note {
     pub id: NoteId,
     pub parent_object: String,
@@ -10,10 +13,11 @@ pub struct Note {
     pub title: String,
     pub content_plaintext: String,
     pub content_markdown: String,
+    /// RFC 3339 timestamp, kept as the API sent it; see `created_at_datetime`
     pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NoteId {
     pub workspace_id: String,
     pub note_id: String,
@@ -28,6 +32,108 @@ pub struct CreateNoteData {
     pub content: String,
 }
 
+impl CreateNoteData {
+    /// Content formats Attio accepts when creating a note
+    pub const FORMATS: [&'static str; 2] = ["plaintext", "markdown"];
+
+    /// Checks a `format` value, ignoring case, so a typo is caught before any
+    /// request is sent
+    pub fn parse_format(value: &str) -> Result<String, String> {
+        let format = value.trim().to_ascii_lowercase();
+        if Self::FORMATS.contains(&format.as_str()) {
+            Ok(format)
+        } else {
+            Err(format!(
+                "unknown format '{}'. Valid formats: {}",
+                value.trim(),
+                Self::FORMATS.join(", ")
+            ))
+        }
+    }
+}
+
+impl Note {
+    /// True when the note has no visible content (blank after trimming)
+    pub fn is_empty_content(&self) -> bool {
+        self.plaintext_content().trim().is_empty()
+    }
+
+    /// `content_plaintext`, or the markdown stripped down when only that is set
+    pub fn plaintext_content(&self) -> Cow<'_, str> {
+        if self.content_plaintext.is_empty() {
+            Cow::Owned(markdown::to_plaintext(&self.content_markdown))
+        } else {
+            Cow::Borrowed(&self.content_plaintext)
+        }
+    }
+
+    /// `content_markdown`, or the plaintext escaped as markdown when only that
+    /// is set
+    pub fn markdown_content(&self) -> Cow<'_, str> {
+        if self.content_markdown.is_empty() {
+            Cow::Owned(markdown::from_plaintext(&self.content_plaintext))
+        } else {
+            Cow::Borrowed(&self.content_markdown)
+        }
+    }
+
+    /// `created_at` parsed and converted to UTC
+    pub fn created_at_datetime(&self) -> Result<DateTime<Utc>, ParseError> {
+        DateTime::parse_from_rfc3339(&self.created_at).map(|created| created.with_timezone(&Utc))
+    }
+}
+
+/// Test fixtures: `Note::test(id)` with placeholder fields, adjusted with
+/// the `with_*` setters
+#[cfg(test)]
+impl Note {
+    pub fn test(id: &str) -> Self {
+        Self {
+            id: NoteId {
+                workspace_id: "ws".to_string(),
+                note_id: id.to_string(),
+            },
+            parent_object: "people".to_string(),
+            parent_record_id: "rec".to_string(),
+            title: "Title".to_string(),
+            content_plaintext: "Body".to_string(),
+            content_markdown: "Body".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    pub fn with_parent(mut self, object: &str, record_id: &str) -> Self {
+        self.parent_object = object.to_string();
+        self.parent_record_id = record_id.to_string();
+        self
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Sets both the plaintext and the markdown content
+    pub fn with_content(self, content: &str) -> Self {
+        self.with_plaintext(content).with_markdown(content)
+    }
+
+    pub fn with_plaintext(mut self, content: &str) -> Self {
+        self.content_plaintext = content.to_string();
+        self
+    }
+
+    pub fn with_markdown(mut self, content: &str) -> Self {
+        self.content_markdown = content.to_string();
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: &str) -> Self {
+        self.created_at = created_at.to_string();
+        self
+    }
+}
+
 impl Cacheable for Note {
     /// Estimate the memory size of this note in bytes
     fn estimate_size_bytes(&self) -> usize {
@@ -95,20 +201,46 @@ mod tests {
         assert!(json.contains("\"format\":\"markdown\""));
     }
 
+    fn created(created_at: &str) -> Note {
+        Note::test("note_456").with_created_at(created_at)
+    }
+
+    #[test]
+    fn test_created_at_datetime_parses_utc() {
+        let parsed = created("2024-03-01T09:30:00.123456789Z")
+            .created_at_datetime()
+            .unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-03-01T09:30:00.123456789+00:00");
+    }
+
+    #[test]
+    fn test_created_at_datetime_converts_offsets() {
+        let parsed = created("2024-03-01T11:30:00+02:00")
+            .created_at_datetime()
+            .unwrap();
+        assert_eq!(
+            parsed,
+            created("2024-03-01T09:30:00Z")
+                .created_at_datetime()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_created_at_datetime_rejects_malformed() {
+        assert!(created("").created_at_datetime().is_err());
+        assert!(created("2024-03-01").created_at_datetime().is_err());
+        assert!(created("yesterday").created_at_datetime().is_err());
+        // The raw string still round-trips unchanged
+        let json = serde_json::to_value(created("yesterday")).unwrap();
+        assert_eq!(json["created_at"], "yesterday");
+    }
+
     #[test]
     fn test_note_estimate_size_bytes() {
-        let note = Note {
-            id: NoteId {
-                workspace_id: "ws_123".to_string(),
-                note_id: "note_456".to_string(),
-            },
-            parent_object: "people".to_string(),
-            parent_record_id: "rec_789".to_string(),
-            title: "Test".to_string(),
-            content_plaintext: "Content".to_string(),
-            content_markdown: "**Content**".to_string(),
-            created_at: "2023-01-01T00:00:00Z".to_string(),
-        };
+        let note = Note::test("note_456")
+            .with_plaintext("Content")
+            .with_markdown("**Content**");
 
         let size = note.estimate_size_bytes();
 
@@ -122,19 +254,55 @@ mod tests {
         // Test that Note implements Cacheable
         fn assert_cacheable<T: Cacheable>(_: &T) {}
 
-        let note = Note {
-            id: NoteId {
-                workspace_id: "ws".to_string(),
-                note_id: "note".to_string(),
-            },
-            parent_object: "test".to_string(),
-            parent_record_id: "rec".to_string(),
-            title: "Title".to_string(),
-            content_plaintext: "Plain".to_string(),
-            content_markdown: "Mark".to_string(),
-            created_at: "2023".to_string(),
-        };
+        let note = Note::test("note");
 
         assert_cacheable(&note);
     }
+
+    fn note_with_content(content: &str) -> Note {
+        Note::test("note").with_content(content)
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            CreateNoteData::parse_format(" Markdown").unwrap(),
+            "markdown"
+        );
+        assert_eq!(
+            CreateNoteData::parse_format("markdwn").unwrap_err(),
+            "unknown format 'markdwn'. Valid formats: plaintext, markdown"
+        );
+        assert!(CreateNoteData::parse_format("html").is_err());
+    }
+
+    #[test]
+    fn test_content_falls_back_to_the_other_format() {
+        let mut note = note_with_content("");
+        note.content_markdown = "# Agenda\n- **pricing**".to_string();
+        assert_eq!(note.plaintext_content(), "Agenda\n- pricing");
+        assert!(!note.is_empty_content());
+
+        let mut note = note_with_content("");
+        note.content_plaintext = "# 1 is *the* answer".to_string();
+        assert_eq!(note.markdown_content(), "\\# 1 is \\*the\\* answer");
+
+        // Set fields are used as they are
+        let note = note_with_content("**both**");
+        assert_eq!(note.plaintext_content(), "**both**");
+        assert_eq!(note.markdown_content(), "**both**");
+    }
+
+    #[test]
+    fn test_is_empty_content() {
+        assert!(note_with_content("").is_empty_content());
+        assert!(!note_with_content("Hello").is_empty_content());
+    }
+
+    #[test]
+    fn test_whitespace_only_content_is_empty() {
+        assert!(note_with_content("   ").is_empty_content());
+        assert!(note_with_content("\n\t \r\n").is_empty_content());
+        assert!(!note_with_content("  x  ").is_empty_content());
+    }
 }
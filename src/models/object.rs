@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// An Attio object definition (people, companies, or a custom object)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Object {
+    #[serde(default)]
+    pub id: Option<ObjectId>,
+    /// Slug used in API paths, e.g. `people`
+    pub api_slug: Option<String>,
+    pub singular_noun: Option<String>,
+    pub plural_noun: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObjectId {
+    pub workspace_id: String,
+    pub object_id: String,
+}
+
+impl Object {
+    /// The object's ID for tables, or "-" when the API didn't send one
+    pub fn id_label(&self) -> &str {
+        self.id.as_ref().map_or("-", |id| id.object_id.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::ListObjectsResponse;
+
+    #[test]
+    fn test_list_objects_deserialization() {
+        let json = r#"{
+            "data": [
+                {
+                    "id": { "workspace_id": "ws", "object_id": "obj" },
+                    "api_slug": "people",
+                    "singular_noun": "Person",
+                    "plural_noun": "People",
+                    "created_at": "2023-01-01T00:00:00Z"
+                },
+                { "api_slug": null, "singular_noun": null, "plural_noun": null }
+            ]
+        }"#;
+
+        let response: ListObjectsResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.data[0].api_slug.as_deref(), Some("people"));
+        assert_eq!(response.data[1].api_slug, None);
+        assert_eq!(response.data[0].id_label(), "obj");
+        assert_eq!(response.data[1].id_label(), "-");
+    }
+}
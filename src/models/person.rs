@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::Cacheable;
+use super::record::{RecordId, first_value};
+
+/// A record of the `people` object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Person {
+    pub id: RecordId,
+    pub created_at: String,
+    #[serde(default)]
+    pub values: serde_json::Value,
+}
+
+impl Person {
+    pub fn name(&self) -> Option<&str> {
+        first_value(&self.values, "name")?
+            .get("full_name")?
+            .as_str()
+    }
+
+    /// The first of the person's email addresses
+    pub fn primary_email(&self) -> Option<&str> {
+        first_value(&self.values, "email_addresses")?
+            .get("email_address")?
+            .as_str()
+    }
+}
+
+impl Cacheable for Person {
+    /// Estimate the memory size of this person in bytes
+    fn estimate_size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.id.workspace_id.capacity()
+            + self.id.object_id.capacity()
+            + self.id.record_id.capacity()
+            + self.created_at.capacity()
+            + self.values.to_string().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Cacheable, ListPeopleResponse};
+
+    const PEOPLE_JSON: &str = r#"
+    {
+        "data": [
+            {
+                "id": {
+                    "workspace_id": "ws_123",
+                    "object_id": "obj_people",
+                    "record_id": "rec_1"
+                },
+                "created_at": "2024-03-01T09:00:00.000000000Z",
+                "web_url": "https://app.attio.com/acme/person/rec_1",
+                "values": {
+                    "name": [{
+                        "active_from": "2024-03-01T09:00:00.000000000Z",
+                        "first_name": "Jane",
+                        "last_name": "Doe",
+                        "full_name": "Jane Doe",
+                        "attribute_type": "personal-name"
+                    }],
+                    "email_addresses": [
+                        {
+                            "original_email_address": "Jane@Acme.com",
+                            "email_address": "jane@acme.com",
+                            "email_domain": "acme.com",
+                            "attribute_type": "email-address"
+                        },
+                        {
+                            "email_address": "jane@example.org",
+                            "attribute_type": "email-address"
+                        }
+                    ]
+                }
+            },
+            {
+                "id": {
+                    "workspace_id": "ws_123",
+                    "object_id": "obj_people",
+                    "record_id": "rec_2"
+                },
+                "created_at": "2024-03-02T09:00:00.000000000Z",
+                "values": { "name": [], "email_addresses": [] }
+            }
+        ]
+    }
+    "#;
+
+    #[test]
+    fn test_deserialize_people() {
+        let people: ListPeopleResponse = serde_json::from_str(PEOPLE_JSON).unwrap();
+
+        assert_eq!(people.data.len(), 2);
+        let jane = &people.data[0];
+        assert_eq!(jane.id.record_id, "rec_1");
+        assert_eq!(jane.name(), Some("Jane Doe"));
+        assert_eq!(jane.primary_email(), Some("jane@acme.com"));
+    }
+
+    #[test]
+    fn test_missing_values_are_none() {
+        let people: ListPeopleResponse = serde_json::from_str(PEOPLE_JSON).unwrap();
+
+        assert_eq!(people.data[1].name(), None);
+        assert_eq!(people.data[1].primary_email(), None);
+    }
+
+    #[test]
+    fn test_estimate_size_counts_values() {
+        let people: ListPeopleResponse = serde_json::from_str(PEOPLE_JSON).unwrap();
+
+        assert!(people.data[0].estimate_size_bytes() > people.data[1].estimate_size_bytes());
+    }
+}
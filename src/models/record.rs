@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::common::Cacheable;
+
+/// Keys holding the readable part of an attribute value, across attribute types
+const VALUE_KEYS: [&str; 8] = [
+    "full_name",
+    "value",
+    "email_address",
+    "domain",
+    "original_phone_number",
+    "currency_value",
+    "target_record_id",
+    "referenced_actor_id",
+];
+
+/// Bookkeeping keys every attribute value carries
+const METADATA_KEYS: [&str; 4] = [
+    "active_from",
+    "active_until",
+    "created_by_actor",
+    "attribute_type",
+];
+
+/// A record of any Attio object (people, companies, custom objects, ...)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Record {
+    pub id: RecordId,
+    pub created_at: String,
+    #[serde(default)]
+    pub values: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordId {
+    pub workspace_id: String,
+    pub object_id: String,
+    pub record_id: String,
+}
+
+/// Request body for the records query endpoint
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecordQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sorts: Vec<RecordSort>,
+}
+
+/// One entry of a records query's `sorts`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordSort {
+    pub attribute: String,
+    /// "asc" or "desc"
+    pub direction: String,
+}
+
+impl RecordSort {
+    /// Parses `attribute` or `attribute:asc|desc` (ascending by default)
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (attribute, direction) = input.split_once(':').unwrap_or((input, "asc"));
+        let attribute = attribute.trim();
+        if attribute.is_empty() {
+            return Err("the sort attribute is empty".to_string());
+        }
+        match direction.trim() {
+            direction @ ("asc" | "desc") => Ok(Self {
+                attribute: attribute.to_string(),
+                direction: direction.to_string(),
+            }),
+            other => Err(format!(
+                "unknown sort direction '{}' (expected asc or desc)",
+                other
+            )),
+        }
+    }
+}
+
+/// The first (current) value of `attribute` in a record's `values` map
+pub fn first_value<'a>(
+    values: &'a serde_json::Value,
+    attribute: &str,
+) -> Option<&'a serde_json::Value> {
+    values.get(attribute)?.as_array()?.first()
+}
+
+impl Record {
+    /// Returns the record's display name from its `name` attribute.
+    ///
+    /// Handles both personal names (people) and plain text names (companies and
+    /// most custom objects).
+    pub fn display_name(&self) -> Option<String> {
+        let name = first_value(&self.values, "name")?;
+        name.get("full_name")
+            .or_else(|| name.get("value"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    }
+
+    /// Every current value of `attribute` as text, comma-separated, or `None`
+    /// when the record has no value for it
+    pub fn attribute_text(&self, attribute: &str) -> Option<String> {
+        let values = self.values.get(attribute)?.as_array()?;
+        let texts: Vec<String> = values.iter().filter_map(value_text).collect();
+        (!texts.is_empty()).then(|| texts.join(", "))
+    }
+
+    /// Slugs of the attributes that have at least one value, in name order
+    pub fn attribute_names(&self) -> Vec<&str> {
+        self.values
+            .as_object()
+            .map(|values| {
+                values
+                    .iter()
+                    .filter(|(_, value)| value.as_array().is_some_and(|v| !v.is_empty()))
+                    .map(|(name, _)| name.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Readable text for one attribute value: a select option's title, the
+/// type's main field, or else the value's JSON without its bookkeeping keys
+fn value_text(value: &Value) -> Option<String> {
+    let title = value
+        .get("option")
+        .or_else(|| value.get("status"))
+        .and_then(|choice| choice.get("title"))
+        .and_then(Value::as_str);
+    if let Some(title) = title {
+        return Some(title.to_string());
+    }
+    match VALUE_KEYS.iter().find_map(|key| value.get(key)) {
+        Some(Value::String(text)) => Some(text.clone()),
+        Some(Value::Null) => None,
+        Some(other) => Some(other.to_string()),
+        None => {
+            let mut rest = value.as_object()?.clone();
+            for key in METADATA_KEYS {
+                rest.remove(key);
+            }
+            (!rest.is_empty()).then(|| Value::Object(rest).to_string())
+        }
+    }
+}
+
+impl Cacheable for Record {
+    /// Estimate the memory size of this record in bytes, counting `values`
+    /// by the length of its JSON
+    fn estimate_size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.id.workspace_id.capacity()
+            + self.id.object_id.capacity()
+            + self.id.record_id.capacity()
+            + self.created_at.capacity()
+            + serde_json::to_vec(&self.values).map_or(0, |json| json.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_with_values(values: serde_json::Value) -> Record {
+        Record {
+            id: RecordId {
+                workspace_id: "ws".to_string(),
+                object_id: "obj".to_string(),
+                record_id: "rec".to_string(),
+            },
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            values,
+        }
+    }
+
+    #[test]
+    fn test_deserialize_record() {
+        let json = r#"
+        {
+            "id": {
+                "workspace_id": "ws_123",
+                "object_id": "obj_456",
+                "record_id": "rec_789"
+            },
+            "created_at": "2023-01-01T00:00:00Z",
+            "web_url": "https://app.attio.com/acme/person/rec_789",
+            "values": {
+                "name": [{"first_name": "Jane", "last_name": "Doe", "full_name": "Jane Doe"}]
+            }
+        }
+        "#;
+        let record: Record = serde_json::from_str(json).unwrap();
+
+        assert_eq!(record.id.record_id, "rec_789");
+        assert_eq!(record.display_name(), Some("Jane Doe".to_string()));
+    }
+
+    #[test]
+    fn test_display_name_for_text_attribute() {
+        let record = record_with_values(serde_json::json!({
+            "name": [{"value": "Acme Inc", "attribute_type": "text"}]
+        }));
+        assert_eq!(record.display_name(), Some("Acme Inc".to_string()));
+    }
+
+    #[test]
+    fn test_display_name_missing() {
+        let record = record_with_values(serde_json::json!({"name": []}));
+        assert_eq!(record.display_name(), None);
+
+        let record = record_with_values(serde_json::Value::Null);
+        assert_eq!(record.display_name(), None);
+    }
+
+    #[test]
+    fn test_attribute_text_across_types() {
+        let record = record_with_values(serde_json::json!({
+            "email_addresses": [
+                {"email_address": "a@example.com", "attribute_type": "email-address"},
+                {"email_address": "b@example.com", "attribute_type": "email-address"}
+            ],
+            "stage": [{"status": {"title": "Won"}, "attribute_type": "status"}],
+            "employees": [{"value": 42, "attribute_type": "number"}],
+            "location": [{"locality": "Berlin", "active_from": "2024-01-01", "attribute_type": "location"}],
+            "tags": []
+        }));
+
+        assert_eq!(
+            record.attribute_text("email_addresses").as_deref(),
+            Some("a@example.com, b@example.com")
+        );
+        assert_eq!(record.attribute_text("stage").as_deref(), Some("Won"));
+        assert_eq!(record.attribute_text("employees").as_deref(), Some("42"));
+        assert_eq!(
+            record.attribute_text("location").as_deref(),
+            Some(r#"{"locality":"Berlin"}"#)
+        );
+        assert_eq!(record.attribute_text("tags"), None);
+        assert_eq!(record.attribute_text("missing"), None);
+        assert_eq!(
+            record.attribute_names(),
+            ["email_addresses", "employees", "location", "stage"]
+        );
+    }
+
+    #[test]
+    fn test_estimate_size_counts_values() {
+        let small = record_with_values(serde_json::json!({}));
+        let large = record_with_values(serde_json::json!({
+            "description": [{"value": "x".repeat(1000)}]
+        }));
+        assert!(large.estimate_size_bytes() >= small.estimate_size_bytes() + 1000);
+    }
+
+    #[test]
+    fn test_parse_record_sort() {
+        assert_eq!(
+            RecordSort::parse("name").unwrap(),
+            RecordSort {
+                attribute: "name".to_string(),
+                direction: "asc".to_string(),
+            }
+        );
+        assert_eq!(
+            RecordSort::parse("created_at:desc").unwrap().direction,
+            "desc"
+        );
+        assert!(RecordSort::parse("name:up").is_err());
+        assert!(RecordSort::parse(":desc").is_err());
+    }
+
+    #[test]
+    fn test_record_query_skips_empty_fields() {
+        let query = RecordQuery {
+            limit: Some(10),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(json, r#"{"limit":10}"#);
+    }
+}
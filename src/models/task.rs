@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::Cacheable;
+
+/// A to-do item, optionally with a deadline and linked records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: TaskId,
+    pub content_plaintext: String,
+    pub is_completed: bool,
+    #[serde(default)]
+    pub deadline_at: Option<String>,
+    #[serde(default)]
+    pub linked_records: Vec<LinkedRecord>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskId {
+    pub workspace_id: String,
+    pub task_id: String,
+}
+
+/// A record a task is linked to, as returned by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedRecord {
+    pub target_object_id: String,
+    pub target_record_id: String,
+}
+
+/// A record to link a new task to; `target_object` is an object slug or ID
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskLink {
+    pub target_object: String,
+    pub target_record_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTaskData {
+    pub content: String,
+    pub format: String,
+    /// Sent as `null` when there is no deadline; the API requires the field
+    pub deadline_at: Option<String>,
+    pub is_completed: bool,
+    pub linked_records: Vec<TaskLink>,
+    pub assignees: Vec<serde_json::Value>,
+}
+
+/// Fields changed by a task update; unset fields are left alone
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateTaskData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_completed: Option<bool>,
+}
+
+impl Task {
+    /// Checkbox-style completion marker for tables
+    pub fn checkbox(&self) -> &'static str {
+        if self.is_completed { "[x]" } else { "[ ]" }
+    }
+
+    /// Number of linked records, for display
+    pub fn linked_summary(&self) -> String {
+        match self.linked_records.len() {
+            0 => "-".to_string(),
+            1 => "1 record".to_string(),
+            n => format!("{} records", n),
+        }
+    }
+}
+
+impl Cacheable for Task {
+    /// Estimate the memory size of this task in bytes
+    fn estimate_size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.id.workspace_id.capacity()
+            + self.id.task_id.capacity()
+            + self.content_plaintext.capacity()
+            + self.deadline_at.as_ref().map_or(0, String::capacity)
+            + self.created_at.as_ref().map_or(0, String::capacity)
+            + self
+                .linked_records
+                .iter()
+                .map(|link| {
+                    std::mem::size_of::<LinkedRecord>()
+                        + link.target_object_id.capacity()
+                        + link.target_record_id.capacity()
+                })
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateTaskRequest, ListTasksResponse};
+
+    const TASKS_JSON: &str = r#"
+    {
+        "data": [
+            {
+                "id": { "workspace_id": "ws_123", "task_id": "task_1" },
+                "content_plaintext": "Send the renewal quote",
+                "format": "plaintext",
+                "deadline_at": "2024-03-01T17:00:00.000000000Z",
+                "is_completed": false,
+                "linked_records": [
+                    { "target_object_id": "obj_companies", "target_record_id": "rec_1" }
+                ],
+                "assignees": [],
+                "created_at": "2024-02-20T09:00:00.000000000Z"
+            },
+            {
+                "id": { "workspace_id": "ws_123", "task_id": "task_2" },
+                "content_plaintext": "Book the venue",
+                "deadline_at": null,
+                "is_completed": true
+            }
+        ]
+    }
+    "#;
+
+    #[test]
+    fn test_deserialize_tasks() {
+        let tasks: ListTasksResponse = serde_json::from_str(TASKS_JSON).unwrap();
+
+        assert_eq!(tasks.data.len(), 2);
+        let quote = &tasks.data[0];
+        assert_eq!(quote.id.task_id, "task_1");
+        assert_eq!(quote.checkbox(), "[ ]");
+        assert_eq!(quote.linked_summary(), "1 record");
+        assert_eq!(quote.linked_records[0].target_record_id, "rec_1");
+
+        let venue = &tasks.data[1];
+        assert_eq!(venue.checkbox(), "[x]");
+        assert_eq!(venue.deadline_at, None);
+        assert_eq!(venue.linked_summary(), "-");
+    }
+
+    #[test]
+    fn test_create_request_sends_null_deadline() {
+        let request = CreateTaskRequest {
+            data: CreateTaskData {
+                content: "Call back".to_string(),
+                format: "plaintext".to_string(),
+                deadline_at: None,
+                is_completed: false,
+                linked_records: vec![],
+                assignees: vec![],
+            },
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["data"]["deadline_at"], serde_json::Value::Null);
+        assert_eq!(json["data"]["assignees"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_update_skips_unset_fields() {
+        let json = serde_json::to_value(UpdateTaskData::default()).unwrap();
+        assert_eq!(json, serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_estimate_size_counts_links() {
+        let tasks: ListTasksResponse = serde_json::from_str(TASKS_JSON).unwrap();
+        assert!(tasks.data[0].estimate_size_bytes() > std::mem::size_of::<Task>());
+    }
+}
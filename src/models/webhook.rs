@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+/// A webhook subscription that delivers workspace events to a URL
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub target_url: String,
+    pub subscriptions: Vec<WebhookSubscription>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Signing secret; only returned when the webhook is created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookId {
+    pub workspace_id: String,
+    pub webhook_id: String,
+}
+
+/// An event type the webhook is subscribed to, e.g. `note.created`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub event_type: String,
+    /// Optional filter narrowing which events are delivered
+    #[serde(default)]
+    pub filter: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookData {
+    pub target_url: String,
+    pub subscriptions: Vec<WebhookSubscription>,
+}
+
+impl Webhook {
+    /// Comma-separated event types, for display
+    pub fn event_types(&self) -> String {
+        self.subscriptions
+            .iter()
+            .map(|s| s.event_type.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateWebhookRequest;
+
+    #[test]
+    fn test_webhook_deserialization() {
+        let json = r#"{
+            "id": { "workspace_id": "ws", "webhook_id": "wh_1" },
+            "target_url": "https://example.com/hook",
+            "subscriptions": [
+                { "event_type": "note.created", "filter": null },
+                { "event_type": "note.deleted", "filter": null }
+            ],
+            "status": "active",
+            "created_at": "2024-01-01T00:00:00Z"
+        }"#;
+
+        let webhook: Webhook = serde_json::from_str(json).unwrap();
+
+        assert_eq!(webhook.id.webhook_id, "wh_1");
+        assert_eq!(webhook.status.as_deref(), Some("active"));
+        assert_eq!(webhook.secret, None);
+        assert_eq!(webhook.event_types(), "note.created, note.deleted");
+    }
+
+    #[test]
+    fn test_create_webhook_request_serialization() {
+        let request = CreateWebhookRequest {
+            data: CreateWebhookData {
+                target_url: "https://example.com/hook".to_string(),
+                subscriptions: vec![WebhookSubscription {
+                    event_type: "note.created".to_string(),
+                    filter: None,
+                }],
+            },
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["data"]["target_url"], "https://example.com/hook");
+        assert_eq!(
+            json["data"]["subscriptions"][0]["event_type"],
+            "note.created"
+        );
+        assert!(json["data"]["subscriptions"][0]["filter"].is_null());
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::io::{self, IsTerminal, Write};
+
+/// How to signal that a long-running operation has finished
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyMode {
+    /// Stay quiet
+    #[default]
+    Off,
+    /// Ring the terminal bell
+    Bell,
+    /// Show a desktop notification, falling back to the bell without a notification daemon
+    Desktop,
+}
+
+impl NotifyMode {
+    pub const VARIANTS: &'static [&'static str] = &["off", "bell", "desktop"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Self::Off),
+            "bell" => Some(Self::Bell),
+            "desktop" => Some(Self::Desktop),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Bell => "bell",
+            Self::Desktop => "desktop",
+        }
+    }
+
+    /// Signals completion of `summary`. Never fails: a missing notification
+    /// daemon degrades to the terminal bell.
+    ///
+    /// Desktop notifications may block briefly on D-Bus, so async callers
+    /// should run this on a blocking task.
+    pub fn notify(self, summary: &str, body: &str) {
+        match self {
+            Self::Off => {}
+            Self::Bell => bell(),
+            Self::Desktop => {
+                if notify_rust::Notification::new()
+                    .appname("attio")
+                    .summary(summary)
+                    .body(body)
+                    .show()
+                    .is_err()
+                {
+                    bell();
+                }
+            }
+        }
+    }
+}
+
+/// Rings the bell on stderr, so piped stdout stays clean; skipped when
+/// stderr isn't a terminal either
+fn bell() {
+    let mut stderr = io::stderr();
+    if stderr.is_terminal() {
+        let _ = stderr.write_all(b"\x07");
+        let _ = stderr.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for name in NotifyMode::VARIANTS {
+            assert_eq!(NotifyMode::parse(name).unwrap().as_str(), *name);
+        }
+        assert_eq!(NotifyMode::parse("loud"), None);
+    }
+
+    #[test]
+    fn test_default_is_off() {
+        assert_eq!(NotifyMode::default(), NotifyMode::Off);
+    }
+}
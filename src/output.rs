@@ -0,0 +1,259 @@
+use crate::client::{AttioClient, DryRunRequest};
+use crate::models::Note;
+use futures::StreamExt;
+use serde::Serialize;
+use std::error::Error;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Notes requested per page while streaming
+const STREAM_PAGE_SIZE: u32 = 50;
+
+/// How command results are printed to stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables
+    #[default]
+    Table,
+    /// Pretty-printed JSON, for scripts
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == Self::Json
+    }
+}
+
+/// Notes per page in `notes list --plain` when `--limit` isn't given
+pub const DEFAULT_PAGE_SIZE: usize = 25;
+
+/// Which page of notes a plain list shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Paging {
+    /// 1-based page number
+    pub page: usize,
+    pub size: usize,
+}
+
+impl Paging {
+    pub fn new(page: Option<usize>, limit: Option<usize>) -> Self {
+        Self {
+            page: page.unwrap_or(1).max(1),
+            size: limit.unwrap_or(DEFAULT_PAGE_SIZE).max(1),
+        }
+    }
+
+    /// Notes skipped before this page
+    pub fn offset(&self) -> usize {
+        (self.page - 1) * self.size
+    }
+
+    /// A line like "Showing 1–25 of many (use --page 2)". `total` is known
+    /// when every note was loaded; `more` says whether a later page has notes.
+    pub fn footer(&self, shown: usize, total: Option<usize>, more: bool) -> String {
+        if shown == 0 {
+            return format!("No notes on page {}.", self.page);
+        }
+        let first = self.offset() + 1;
+        let last = self.offset() + shown;
+        let of = match total {
+            Some(total) => format!(" of {}", total),
+            None if more => " of many".to_string(),
+            None => String::new(),
+        };
+        let next = if more {
+            format!(" (use --page {})", self.page + 1)
+        } else {
+            String::new()
+        };
+        format!("Showing {}–{}{}{}", first, last, of, next)
+    }
+}
+
+/// Set by `--quiet`
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turns off success banners and progress messages for the rest of the run
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether decorative output should be left out; requested data, warnings and
+/// errors are printed either way
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Prints `value` to stdout as pretty JSON
+pub fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Prints a request that `--dry-run` kept from being sent
+pub fn print_dry_run(request: &DryRunRequest, format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    if format.is_json() {
+        print_json(request)
+    } else {
+        println!("Dry run, not sent:\n{}", request);
+        Ok(())
+    }
+}
+
+/// Writes notes accepted by `matches` to `out` as NDJSON, one page at a time.
+///
+/// Each line is flushed as soon as it is written so consumers see results
+/// incrementally. Stops after `limit` notes, or quietly when the reader goes
+/// away (e.g. `| head`). Returns how many notes were written.
+pub async fn stream_json_lines(
+    client: &AttioClient,
+    limit: Option<usize>,
+    matches: impl Fn(&Note) -> bool,
+    out: &mut impl Write,
+) -> Result<usize, Box<dyn Error>> {
+    let mut written = 0;
+    // Pages are only fetched as notes are read, so stopping early saves requests
+    let mut notes = std::pin::pin!(client.notes_stream(STREAM_PAGE_SIZE));
+    while let Some(note) = notes.next().await {
+        let note = note?;
+        if !matches(&note) {
+            continue;
+        }
+        if limit.is_some_and(|limit| written >= limit) {
+            break;
+        }
+        let line = serde_json::to_string(&note)?;
+        match writeln!(out, "{}", line).and_then(|_| out.flush()) {
+            Ok(()) => written += 1,
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_output_format_values() {
+        use clap::ValueEnum;
+        assert_eq!(OutputFormat::default(), OutputFormat::Table);
+        assert_eq!(
+            OutputFormat::from_str("json", false),
+            Ok(OutputFormat::Json)
+        );
+        assert_eq!(
+            OutputFormat::from_str("table", false),
+            Ok(OutputFormat::Table)
+        );
+        assert!(OutputFormat::from_str("yaml", false).is_err());
+        assert!(OutputFormat::Json.is_json());
+        assert!(!OutputFormat::Table.is_json());
+    }
+
+    #[test]
+    fn test_paging_footer() {
+        let paging = Paging::new(None, None);
+        assert_eq!(paging.offset(), 0);
+        assert_eq!(
+            paging.footer(25, None, true),
+            "Showing 1–25 of many (use --page 2)"
+        );
+
+        let paging = Paging::new(Some(3), Some(10));
+        assert_eq!(paging.offset(), 20);
+        assert_eq!(paging.footer(4, None, false), "Showing 21–24");
+        assert_eq!(
+            paging.footer(10, Some(45), true),
+            "Showing 21–30 of 45 (use --page 4)"
+        );
+        assert_eq!(paging.footer(0, None, false), "No notes on page 3.");
+    }
+
+    fn note_json(id: usize) -> serde_json::Value {
+        let note = Note::test(&format!("note_{}", id))
+            .with_title(&format!("Note {}", id))
+            .with_plaintext(if id.is_multiple_of(2) { "" } else { "text" })
+            .with_markdown("");
+        serde_json::to_value(note).unwrap()
+    }
+
+    async fn mount_page(server: &MockServer, offset: usize, ids: std::ops::Range<usize>) {
+        let data: Vec<_> = ids.map(note_json).collect();
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("offset", offset.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data })),
+            )
+            .mount(server)
+            .await;
+    }
+
+    fn client(server: &MockServer) -> AttioClient {
+        AttioClient::new("token".to_string())
+            .unwrap()
+            .with_base_url(&server.uri())
+    }
+
+    #[tokio::test]
+    async fn test_streams_every_page_as_lines() {
+        let server = MockServer::start().await;
+        mount_page(&server, 0, 0..50).await;
+        mount_page(&server, 50, 50..53).await;
+        let mut out = Vec::new();
+
+        let written = stream_json_lines(&client(&server), None, |_| true, &mut out)
+            .await
+            .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(written, 53);
+        assert_eq!(lines.len(), 53);
+        let last: Note = serde_json::from_str(lines[52]).unwrap();
+        assert_eq!(last.id.note_id, "note_52");
+    }
+
+    #[tokio::test]
+    async fn test_limit_stops_without_fetching_more_pages() {
+        let server = MockServer::start().await;
+        mount_page(&server, 0, 0..50).await;
+        mount_page(&server, 50, 50..100).await;
+        let mut out = Vec::new();
+
+        let written = stream_json_lines(&client(&server), Some(5), |_| true, &mut out)
+            .await
+            .unwrap();
+
+        assert_eq!(written, 5);
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 5);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_limit_counts_only_matching_notes() {
+        let server = MockServer::start().await;
+        mount_page(&server, 0, 0..10).await;
+        let mut out = Vec::new();
+
+        let written = stream_json_lines(
+            &client(&server),
+            Some(3),
+            |note| !note.is_empty_content(),
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(written, 3);
+        assert!(text.lines().all(|line| line.contains("\"text\"")));
+    }
+}
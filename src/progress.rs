@@ -0,0 +1,39 @@
+use crate::output;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+/// How often a spinner redraws while waiting on a request
+const TICK: Duration = Duration::from_millis(100);
+
+/// True when progress can be drawn without ending up in piped output or
+/// cluttering `--quiet` runs
+fn enabled() -> bool {
+    io::stdout().is_terminal() && io::stderr().is_terminal() && !output::is_quiet()
+}
+
+/// A spinner on stderr showing `message`, for work of unknown length.
+/// Hidden when `enabled` is false, so callers can update it unconditionally.
+pub fn spinner(message: impl Into<String>) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let spinner = ProgressBar::new_spinner().with_message(message.into());
+    if let Ok(style) = ProgressStyle::with_template("{spinner} {msg} ({elapsed})") {
+        spinner.set_style(style);
+    }
+    spinner.enable_steady_tick(TICK);
+    spinner
+}
+
+/// A bar on stderr counting up to `len` steps, hidden like `spinner`
+pub fn bar(len: u64, message: impl Into<String>) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(len).with_message(message.into());
+    if let Ok(style) = ProgressStyle::with_template("{msg} [{bar:30}] {pos}/{len} ({eta} left)") {
+        bar.set_style(style.progress_chars("=> "));
+    }
+    bar
+}
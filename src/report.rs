@@ -0,0 +1,88 @@
+use crate::models::Note;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A parent record that has at least one note attached
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ParentSummary {
+    pub parent_object: String,
+    pub parent_record_id: String,
+    pub note_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Groups notes by parent record, sorted by descending note count.
+///
+/// Ties keep a stable `(parent_object, parent_record_id)` order.
+pub fn distinct_parents(notes: &[Note]) -> Vec<ParentSummary> {
+    let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for note in notes {
+        *counts
+            .entry((&note.parent_object, &note.parent_record_id))
+            .or_default() += 1;
+    }
+
+    let mut summaries: Vec<ParentSummary> = counts
+        .into_iter()
+        .map(|((object, record_id), note_count)| ParentSummary {
+            parent_object: object.to_string(),
+            parent_record_id: record_id.to_string(),
+            note_count,
+            name: None,
+        })
+        .collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.note_count));
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, object: &str, record_id: &str) -> Note {
+        Note::test(id).with_parent(object, record_id)
+    }
+
+    #[test]
+    fn test_distinct_parents_counts_and_sorts() {
+        let notes = vec![
+            note("1", "people", "p1"),
+            note("2", "companies", "c1"),
+            note("3", "people", "p1"),
+            note("4", "people", "p2"),
+        ];
+
+        let summaries = distinct_parents(&notes);
+
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].parent_record_id, "p1");
+        assert_eq!(summaries[0].note_count, 2);
+        // Ties are ordered by object then record ID
+        assert_eq!(summaries[1].parent_object, "companies");
+        assert_eq!(summaries[2].parent_record_id, "p2");
+    }
+
+    #[test]
+    fn test_distinct_parents_same_id_different_objects() {
+        let notes = vec![note("1", "people", "x"), note("2", "deals", "x")];
+        assert_eq!(distinct_parents(&notes).len(), 2);
+    }
+
+    #[test]
+    fn test_distinct_parents_empty() {
+        assert!(distinct_parents(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parent_summary_json_omits_missing_name() {
+        let summary = ParentSummary {
+            parent_object: "people".to_string(),
+            parent_record_id: "p1".to_string(),
+            note_count: 1,
+            name: None,
+        };
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.contains("name"));
+    }
+}
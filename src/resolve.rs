@@ -0,0 +1,313 @@
+use crate::client::AttioClient;
+use crate::models::{Record, RecordQuery};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Maximum number of candidate records fetched for a single name lookup
+const LOOKUP_LIMIT: u32 = 25;
+
+/// A parent given as `<object>:<record name or id>`, e.g. `people:Jane Doe`
+#[derive(Debug, PartialEq)]
+pub struct ParentSpec {
+    pub object: String,
+    pub name: String,
+}
+
+impl ParentSpec {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        match input.split_once(':') {
+            Some((object, name)) if !object.trim().is_empty() && !name.trim().is_empty() => {
+                Ok(Self {
+                    object: object.trim().to_string(),
+                    name: name.trim().to_string(),
+                })
+            }
+            _ => Err(format!(
+                "Invalid --parent value '{}'. Expected <object>:<record name>, e.g. \"people:Jane Doe\".",
+                input
+            )
+            .into()),
+        }
+    }
+}
+
+/// Returns true if `value` looks like an Attio record ID (a UUID)
+pub fn is_record_id(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// Builds the records query filter used to find candidates for a display name.
+///
+/// People use a personal-name attribute, which is filtered on its `full_name`
+/// property; other objects use a plain text `name` attribute.
+fn name_filter(object: &str, name: &str) -> serde_json::Value {
+    if object == "people" {
+        serde_json::json!({ "name": { "full_name": { "$contains": name } } })
+    } else {
+        serde_json::json!({ "name": { "$contains": name } })
+    }
+}
+
+/// Returns the candidates whose display name equals `name` (case-insensitive)
+fn exact_matches<'a>(records: &'a [Record], name: &str) -> Vec<&'a Record> {
+    let name = name.to_lowercase();
+    records
+        .iter()
+        .filter(|r| r.display_name().is_some_and(|n| n.to_lowercase() == name))
+        .collect()
+}
+
+fn describe(record: &Record) -> String {
+    format!(
+        "{} ({})",
+        record
+            .display_name()
+            .unwrap_or_else(|| "<unnamed>".to_string()),
+        record.id.record_id
+    )
+}
+
+/// Resolves record names to IDs, caching lookups for the rest of the invocation.
+#[derive(Default)]
+pub struct RecordResolver {
+    cache: HashMap<(String, String), String>,
+}
+
+impl RecordResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves a parent spec to its record ID.
+    ///
+    /// Values that already look like a record ID are returned as-is. When
+    /// several records share the name, an interactive picker is shown if stdin
+    /// is a terminal; otherwise the candidates are reported in the error.
+    pub async fn resolve(
+        &mut self,
+        client: &AttioClient,
+        spec: &ParentSpec,
+    ) -> Result<String, Box<dyn Error>> {
+        if is_record_id(&spec.name) {
+            return Ok(spec.name.clone());
+        }
+
+        let key = (spec.object.clone(), spec.name.to_lowercase());
+        if let Some(id) = self.cache.get(&key) {
+            return Ok(id.clone());
+        }
+
+        let query = RecordQuery {
+            filter: Some(name_filter(&spec.object, &spec.name)),
+            limit: Some(LOOKUP_LIMIT),
+            ..Default::default()
+        };
+        let records = client.query_records(&spec.object, &query).await?.data;
+        let matches = exact_matches(&records, &spec.name);
+
+        let id = match matches.as_slice() {
+            [record] => record.id.record_id.clone(),
+            [] if records.is_empty() => {
+                return Err(
+                    format!("No {} record named '{}' found.", spec.object, spec.name).into(),
+                );
+            }
+            [] => {
+                let candidates: Vec<String> = records.iter().map(describe).collect();
+                return Err(format!(
+                    "No {} record named exactly '{}'. Similar records:\n  {}",
+                    spec.object,
+                    spec.name,
+                    candidates.join("\n  ")
+                )
+                .into());
+            }
+            _ if io::stdin().is_terminal() => pick_record(&matches)?,
+            _ => {
+                let candidates: Vec<String> = matches.iter().map(|r| describe(r)).collect();
+                return Err(format!(
+                    "Multiple {} records named '{}'. Use a record ID instead:\n  {}",
+                    spec.object,
+                    spec.name,
+                    candidates.join("\n  ")
+                )
+                .into());
+            }
+        };
+
+        self.cache.insert(key, id.clone());
+        Ok(id)
+    }
+}
+
+/// Confirms parent records exist before notes are attached to them, checking
+/// each record at most once per invocation.
+#[derive(Default)]
+pub struct ParentVerifier {
+    verified: HashSet<(String, String)>,
+}
+
+impl ParentVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn verify(
+        &mut self,
+        client: &AttioClient,
+        object: &str,
+        record_id: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let key = (object.to_string(), record_id.to_string());
+        if self.verified.contains(&key) {
+            return Ok(());
+        }
+        if !client.record_exists(object, record_id).await? {
+            return Err(format!("Parent record not found: {} {}", object, record_id).into());
+        }
+        self.verified.insert(key);
+        Ok(())
+    }
+}
+
+/// Prompts the user to choose one of several records by number
+fn pick_record(records: &[&Record]) -> Result<String, Box<dyn Error>> {
+    eprintln!("Multiple records match:");
+    for (i, record) in records.iter().enumerate() {
+        eprintln!("  {}) {}", i + 1, describe(record));
+    }
+
+    let stdin = io::stdin();
+    loop {
+        eprint!("Select a record [1-{}]: ", records.len());
+        io::stderr().flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Err("No record selected.".into());
+        }
+        match line.trim().parse::<usize>() {
+            Ok(n) if (1..=records.len()).contains(&n) => {
+                return Ok(records[n - 1].id.record_id.clone());
+            }
+            _ => eprintln!("Please enter a number between 1 and {}.", records.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::record::RecordId;
+
+    fn record(id: &str, name: &str) -> Record {
+        Record {
+            id: RecordId {
+                workspace_id: "ws".to_string(),
+                object_id: "obj".to_string(),
+                record_id: id.to_string(),
+            },
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            values: serde_json::json!({ "name": [{ "full_name": name }] }),
+        }
+    }
+
+    #[test]
+    fn test_parse_parent_spec() {
+        let spec = ParentSpec::parse("people:Jane Doe").unwrap();
+        assert_eq!(spec.object, "people");
+        assert_eq!(spec.name, "Jane Doe");
+    }
+
+    #[test]
+    fn test_parse_parent_spec_keeps_colons_in_name() {
+        let spec = ParentSpec::parse("companies:Acme: EMEA").unwrap();
+        assert_eq!(spec.object, "companies");
+        assert_eq!(spec.name, "Acme: EMEA");
+    }
+
+    #[test]
+    fn test_parse_parent_spec_invalid() {
+        assert!(ParentSpec::parse("Jane Doe").is_err());
+        assert!(ParentSpec::parse("people:").is_err());
+        assert!(ParentSpec::parse(":Jane").is_err());
+    }
+
+    #[test]
+    fn test_is_record_id() {
+        assert!(is_record_id("12345678-1234-1234-1234-123456789abc"));
+        assert!(!is_record_id("Jane Doe"));
+        assert!(!is_record_id("12345678-1234-1234-1234-123456789abz"));
+    }
+
+    #[test]
+    fn test_name_filter_shape() {
+        assert_eq!(
+            name_filter("people", "Jane"),
+            serde_json::json!({ "name": { "full_name": { "$contains": "Jane" } } })
+        );
+        assert_eq!(
+            name_filter("companies", "Acme"),
+            serde_json::json!({ "name": { "$contains": "Acme" } })
+        );
+    }
+
+    #[test]
+    fn test_exact_matches_is_case_insensitive() {
+        let records = vec![
+            record("1", "Jane Doe"),
+            record("2", "Jane Doering"),
+            record("3", "jane doe"),
+        ];
+
+        let matches = exact_matches(&records, "JANE DOE");
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].id.record_id, "1");
+        assert_eq!(matches[1].id.record_id, "3");
+    }
+
+    #[tokio::test]
+    async fn test_parent_verifier_checks_each_record_once() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/objects/people/records/rec_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": { "workspace_id": "ws", "object_id": "obj", "record_id": "rec_1" },
+                    "created_at": "2024-01-01T00:00:00Z"
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/objects/people/records/typo"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let client = AttioClient::new("token".to_string())
+            .unwrap()
+            .with_base_url(&server.uri());
+        let mut verifier = ParentVerifier::new();
+
+        verifier.verify(&client, "people", "rec_1").await.unwrap();
+        verifier.verify(&client, "people", "rec_1").await.unwrap();
+        let err = verifier
+            .verify(&client, "people", "typo")
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("not found"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::models::Config;
+use reqwest::{StatusCode, header};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How `AttioClient` retries transient failures (rate limits, 5xx, dropped connections)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on each subsequent retry
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including server-provided `Retry-After`
+    pub max_delay: Duration,
+    /// Randomize each delay between 50% and 100% of its computed value
+    pub jitter: bool,
+    /// Response statuses that are worth retrying
+    pub retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: false,
+            retry_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.max_retries.saturating_add(1),
+            base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            max_delay: Duration::from_millis(config.retry_max_delay_ms),
+            // Clients retrying in lockstep would hit a recovering API all at once
+            jitter: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn should_retry(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    /// Delay before retry number `retry` (0 for the first retry).
+    ///
+    /// A server-provided `Retry-After` takes precedence over the exponential
+    /// backoff; both are capped at `max_delay`.
+    pub fn delay_for(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| {
+            self.base_delay
+                .saturating_mul(2u32.saturating_pow(retry))
+                .min(self.max_delay)
+        });
+        let delay = delay.min(self.max_delay);
+
+        if self.jitter && retry_after.is_none() {
+            jittered(delay)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Stops sending requests for a while once the API has failed several
+/// requests in a row, so loops like the TUI fetch-all don't keep hammering an
+/// outage. Clones share their state.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    /// Failures in a row that trip the breaker; 0 never trips it
+    threshold: u32,
+    cooldown: Duration,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Default for CircuitBreaker {
+    /// A breaker that never trips
+    fn default() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            state: Arc::default(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.breaker_threshold,
+            Duration::from_secs(config.breaker_cooldown_secs),
+        )
+    }
+
+    /// `Err` with the failures that tripped the breaker and the rest of the
+    /// cooldown while requests are stopped
+    pub fn check(&self) -> Result<(), (u32, Duration)> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.open_until {
+            Some(until) if until > Instant::now() => Err((state.failures, until - Instant::now())),
+            _ => Ok(()),
+        }
+    }
+
+    /// A request got an answer from the API, so it is up again
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        *state = BreakerState::default();
+    }
+
+    /// A request failed after its retries. Once the breaker has tripped, the
+    /// first request after the cooldown trips it again if it fails too.
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.failures += 1;
+        if self.threshold > 0 && state.failures >= self.threshold {
+            state.open_until = Some(Instant::now() + self.cooldown);
+        }
+    }
+}
+
+/// Scales `delay` by a random factor in [0.5, 1.0)
+fn jittered(delay: Duration) -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    let factor = 0.5 + (random % 1000) as f64 / 2000.0;
+    delay.mul_f64(factor)
+}
+
+/// Parses a `Retry-After` header given in seconds
+pub fn retry_after(headers: &header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = policy();
+        assert_eq!(policy.delay_for(0, None), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(1, None), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(2, None), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = policy();
+        assert_eq!(policy.delay_for(5, None), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(u32::MAX, None), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_retry_after_takes_precedence() {
+        let policy = policy();
+        assert_eq!(
+            policy.delay_for(0, Some(Duration::from_millis(700))),
+            Duration::from_millis(700)
+        );
+        // Still capped by max_delay
+        assert_eq!(
+            policy.delay_for(0, Some(Duration::from_secs(60))),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy()
+        };
+        for _ in 0..100 {
+            let delay = policy.delay_for(1, None);
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay < Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(StatusCode::TOO_MANY_REQUESTS));
+        assert!(policy.should_retry(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!policy.should_retry(StatusCode::BAD_REQUEST));
+        assert!(!policy.should_retry(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut config = Config::new("token".to_string());
+        config.max_retries = 5;
+        config.retry_base_delay_ms = 250;
+
+        let policy = RetryPolicy::from_config(&config);
+
+        assert_eq!(policy.max_attempts, 6);
+        assert_eq!(policy.base_delay, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_zero_retries_means_single_attempt() {
+        let mut config = Config::new("token".to_string());
+        config.max_retries = 0;
+
+        assert_eq!(RetryPolicy::from_config(&config).max_attempts, 1);
+    }
+
+    #[test]
+    fn test_breaker_trips_after_threshold() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        assert!(breaker.check().is_ok());
+
+        // Clones share the count
+        breaker.clone().record_failure();
+        let (failures, wait) = breaker.check().unwrap_err();
+        assert_eq!(failures, 2);
+        assert!(wait > Duration::from_secs(59));
+
+        breaker.record_success();
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_breaker_reopens_after_cooldown_failure() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+        // A zero cooldown is already over
+        assert!(breaker.check().is_ok());
+
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(20));
+        breaker.record_failure();
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.check().is_ok());
+        breaker.record_failure();
+        assert_eq!(breaker.check().unwrap_err().0, 3);
+    }
+
+    #[test]
+    fn test_disabled_breaker_never_trips() {
+        let breaker = CircuitBreaker::default();
+        for _ in 0..100 {
+            breaker.record_failure();
+        }
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(header::RETRY_AFTER, "3".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+
+        // HTTP-date values are ignored in favour of the computed backoff
+        headers.insert(
+            header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::error::AttioError;
+use keyring::Entry;
+use std::error::Error;
+use std::thread;
+
+/// Keyring service the tokens are stored under
+const SERVICE: &str = "attio-cli";
+
+/// Keyring account holding the token of `profile`, or the top-level token
+pub fn account(profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("profile:{}", name),
+        None => "default".to_string(),
+    }
+}
+
+/// Runs `f` on the keyring entry for `account`, on its own thread: the
+/// Secret Service backend drives its own async runtime, which can't start on
+/// a Tokio worker thread
+fn with_entry<T: Send>(
+    account: &str,
+    f: impl FnOnce(&Entry) -> keyring::Result<T> + Send,
+) -> Result<T, Box<dyn Error>> {
+    thread::scope(|scope| {
+        scope
+            .spawn(|| Entry::new(SERVICE, account).and_then(|entry| f(&entry)))
+            .join()
+    })
+    .map_err(|_| AttioError::Config("The OS keyring crashed".to_string()))?
+    .map_err(|e| AttioError::Config(format!("Could not use the OS keyring: {}", e)).into())
+}
+
+/// The token stored for `account`, or `None` if there isn't one
+pub fn read_token(account: &str) -> Result<Option<String>, Box<dyn Error>> {
+    with_entry(account, password)
+}
+
+fn password(entry: &Entry) -> keyring::Result<Option<String>> {
+    match entry.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Where the token in use was found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenSource {
+    /// The token environment variable
+    Env,
+    /// The OS keyring
+    Keyring,
+    /// The project config (`.attio/config.json`)
+    Project,
+    /// The global config file
+    File,
+}
+
+/// The token to use and where it came from: one set by the project config
+/// wins, then the one `keyring` finds, then `file_token` from the config
+/// file. The environment variable is weighed against the result by
+/// `token-source-priority`.
+pub fn resolve_token(
+    project_token: Option<&str>,
+    keyring: impl FnOnce() -> Result<Option<String>, Box<dyn Error>>,
+    file_token: &str,
+) -> Result<(String, TokenSource), Box<dyn Error>> {
+    if let Some(token) = project_token.filter(|token| !token.trim().is_empty()) {
+        return Ok((token.to_string(), TokenSource::Project));
+    }
+    Ok(match keyring()? {
+        Some(token) => (token, TokenSource::Keyring),
+        None => (file_token.to_string(), TokenSource::File),
+    })
+}
+
+/// Saves `token` for `account`, replacing any token stored before
+pub fn store_token(account: &str, token: &str) -> Result<(), Box<dyn Error>> {
+    with_entry(account, |entry| entry.set_password(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TokenSourcePriority;
+    use keyring::mock::MockCredential;
+
+    fn mock_entry(token: Option<&str>) -> Entry {
+        let entry = Entry::new_with_credential(Box::new(MockCredential::default()));
+        if let Some(token) = token {
+            entry.set_password(token).unwrap();
+        }
+        entry
+    }
+
+    fn read(entry: &Entry) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(password(entry)?)
+    }
+
+    #[test]
+    fn test_token_fallback_order() {
+        let keyring = mock_entry(Some("keyring"));
+        let empty = mock_entry(None);
+
+        // A project token beats the keyring, which beats the config file
+        let token = resolve_token(Some("project"), || read(&keyring), "file").unwrap();
+        assert_eq!(token, ("project".to_string(), TokenSource::Project));
+        let token = resolve_token(None, || read(&keyring), "file").unwrap();
+        assert_eq!(token, ("keyring".to_string(), TokenSource::Keyring));
+        let (token, source) = resolve_token(Some(" "), || read(&empty), "file").unwrap();
+        assert_eq!((token.as_str(), source), ("file", TokenSource::File));
+
+        // Whatever the config provides is then weighed against the environment
+        let priority = TokenSourcePriority::ConfigFirst;
+        assert_eq!(priority.resolve(Some(&token), Some("env")).unwrap(), "file");
+        let (token, _) = resolve_token(None, || read(&empty), "").unwrap();
+        assert_eq!(priority.resolve(Some(&token), Some("env")).unwrap(), "env");
+    }
+
+    #[test]
+    fn test_unreachable_keyring_is_an_error() {
+        let broken = mock_entry(None);
+        let mock: &MockCredential = broken.get_credential().downcast_ref().unwrap();
+        mock.set_error(keyring::Error::Invalid(
+            "service".to_string(),
+            "locked".to_string(),
+        ));
+
+        // A project token doesn't need the keyring at all
+        let (token, _) = resolve_token(Some("project"), || read(&broken), "file").unwrap();
+        assert_eq!(token, "project");
+        assert!(resolve_token(None, || read(&broken), "file").is_err());
+    }
+
+    #[test]
+    fn test_profiles_get_their_own_account() {
+        assert_eq!(account(None), "default");
+        assert_eq!(account(Some("work")), "profile:work");
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Glyphs used to prefix status output
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolSet {
+    /// Unicode emoji (needs an emoji-capable font)
+    Emoji,
+    /// Nerd Font icons (needs a patched font)
+    Nerdfont,
+    /// Plain ASCII, works everywhere
+    Ascii,
+}
+
+impl SymbolSet {
+    pub const VARIANTS: &'static [&'static str] = &["emoji", "nerdfont", "ascii"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "emoji" => Some(Self::Emoji),
+            "nerdfont" => Some(Self::Nerdfont),
+            "ascii" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Emoji => "emoji",
+            Self::Nerdfont => "nerdfont",
+            Self::Ascii => "ascii",
+        }
+    }
+
+    /// Guesses what the current terminal can render
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|name| env::var(name).ok().filter(|v| !v.is_empty()));
+        Self::detect_from(
+            env::var("TERM").ok().as_deref(),
+            locale.as_deref(),
+            cfg!(windows) && env::var_os("WT_SESSION").is_none(),
+        )
+    }
+
+    /// Emoji unless the terminal is dumb, the locale isn't UTF-8, or we're on
+    /// the legacy Windows console (which can't render emoji)
+    fn detect_from(term: Option<&str>, locale: Option<&str>, legacy_console: bool) -> Self {
+        if term == Some("dumb") || legacy_console {
+            return Self::Ascii;
+        }
+        match locale {
+            Some(locale) => {
+                let locale = locale.to_lowercase();
+                if locale.contains("utf-8") || locale.contains("utf8") {
+                    Self::Emoji
+                } else {
+                    Self::Ascii
+                }
+            }
+            None => Self::Emoji,
+        }
+    }
+
+    pub fn success(&self) -> &'static str {
+        match self {
+            Self::Emoji => "✅",
+            Self::Nerdfont => "\u{f00c}",
+            Self::Ascii => "[ok]",
+        }
+    }
+
+    pub fn warning(&self) -> &'static str {
+        match self {
+            Self::Emoji => "⚠️",
+            Self::Nerdfont => "\u{f071}",
+            Self::Ascii => "[!]",
+        }
+    }
+
+    pub fn failure(&self) -> &'static str {
+        match self {
+            Self::Emoji => "❌",
+            Self::Nerdfont => "\u{f00d}",
+            Self::Ascii => "[x]",
+        }
+    }
+
+    pub fn link(&self) -> &'static str {
+        match self {
+            Self::Emoji => "🔗",
+            Self::Nerdfont => "\u{f0c1}",
+            Self::Ascii => "->",
+        }
+    }
+
+    pub fn file(&self) -> &'static str {
+        match self {
+            Self::Emoji => "📄",
+            Self::Nerdfont => "\u{f15c}",
+            Self::Ascii => "[file]",
+        }
+    }
+
+    pub fn timer(&self) -> &'static str {
+        match self {
+            Self::Emoji => "⏱",
+            Self::Nerdfont => "\u{f017}",
+            Self::Ascii => "[time]",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_symbols_are_ascii() {
+        let set = SymbolSet::Ascii;
+        for symbol in [
+            set.success(),
+            set.warning(),
+            set.failure(),
+            set.link(),
+            set.file(),
+            set.timer(),
+        ] {
+            assert!(symbol.is_ascii(), "{symbol} is not ASCII");
+        }
+    }
+
+    #[test]
+    fn test_symbol_mapping() {
+        assert_eq!(SymbolSet::Emoji.success(), "✅");
+        assert_eq!(SymbolSet::Nerdfont.link(), "\u{f0c1}");
+        assert_eq!(SymbolSet::Ascii.warning(), "[!]");
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for name in SymbolSet::VARIANTS {
+            assert_eq!(SymbolSet::parse(name).unwrap().as_str(), *name);
+        }
+        assert_eq!(SymbolSet::parse("unicode"), None);
+    }
+
+    #[test]
+    fn test_detect_dumb_terminal_uses_ascii() {
+        assert_eq!(
+            SymbolSet::detect_from(Some("dumb"), Some("en_US.UTF-8"), false),
+            SymbolSet::Ascii
+        );
+    }
+
+    #[test]
+    fn test_detect_from_locale() {
+        assert_eq!(
+            SymbolSet::detect_from(Some("xterm-256color"), Some("en_US.UTF-8"), false),
+            SymbolSet::Emoji
+        );
+        assert_eq!(
+            SymbolSet::detect_from(Some("xterm"), Some("C"), false),
+            SymbolSet::Ascii
+        );
+        assert_eq!(
+            SymbolSet::detect_from(Some("xterm"), None, false),
+            SymbolSet::Emoji
+        );
+    }
+
+    #[test]
+    fn test_detect_legacy_windows_console_uses_ascii() {
+        assert_eq!(SymbolSet::detect_from(None, None, true), SymbolSet::Ascii);
+    }
+}
@@ -0,0 +1,449 @@
+use crate::models::CreateNoteData;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+
+/// Frontmatter fields accepted at the top of a note template file.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateFrontmatter {
+    pub parent_object: Option<String>,
+    pub parent_record_id: Option<String>,
+    pub title: Option<String>,
+    pub format: Option<String>,
+}
+
+/// A markdown note template: optional YAML frontmatter plus a body used as content.
+#[derive(Debug, Default, PartialEq)]
+pub struct NoteTemplate {
+    pub frontmatter: TemplateFrontmatter,
+    pub body: String,
+}
+
+/// Where a resolved create parameter came from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldSource {
+    Flag,
+    Template,
+    Config,
+    Default,
+}
+
+impl fmt::Display for FieldSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldSource::Flag => write!(f, "flag"),
+            FieldSource::Template => write!(f, "template"),
+            FieldSource::Config => write!(f, "config"),
+            FieldSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// Values for `notes create` as given on the command line (all optional).
+#[derive(Debug, Default)]
+pub struct CreateFlags {
+    pub parent_object: Option<String>,
+    pub parent_record_id: Option<String>,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub format: Option<String>,
+    /// Accept empty or whitespace-only content
+    pub allow_empty: bool,
+}
+
+/// Fallback parent for `notes create` from the `default-parent-*` config keys.
+#[derive(Debug, Default)]
+pub struct CreateDefaults {
+    pub parent_object: Option<String>,
+    /// Only applied when the note's parent object is `parent_object`
+    pub parent_record_id: Option<String>,
+}
+
+/// Fully resolved `notes create` parameters, with the source of each field.
+#[derive(Debug)]
+pub struct CreateParams {
+    pub parent_object: String,
+    pub parent_record_id: String,
+    pub title: String,
+    pub content: String,
+    pub format: String,
+    pub sources: Vec<(&'static str, FieldSource)>,
+}
+
+/// Parses a template file. Frontmatter is optional and must be delimited by `---` lines.
+pub fn parse_template(input: &str) -> Result<NoteTemplate, Box<dyn Error>> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut lines = input.split_inclusive('\n');
+
+    let starts_with_frontmatter = input
+        .lines()
+        .next()
+        .is_some_and(|line| line.trim_end() == "---");
+    if !starts_with_frontmatter {
+        return Ok(NoteTemplate {
+            frontmatter: TemplateFrontmatter::default(),
+            body: input.to_string(),
+        });
+    }
+
+    // Skip the opening delimiter
+    lines.next();
+
+    let mut yaml = String::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == "---" {
+            closed = true;
+            break;
+        }
+        yaml.push_str(line);
+    }
+    if !closed {
+        return Err("Template frontmatter is missing its closing `---` line.".into());
+    }
+
+    let frontmatter = if yaml.trim().is_empty() {
+        TemplateFrontmatter::default()
+    } else {
+        serde_yaml::from_str(&yaml).map_err(|e| format!("Invalid template frontmatter: {}", e))?
+    };
+
+    let body: String = lines.collect();
+    let body = body
+        .strip_prefix("\r\n")
+        .or(body.strip_prefix('\n'))
+        .unwrap_or(&body);
+
+    Ok(NoteTemplate {
+        frontmatter,
+        body: body.to_string(),
+    })
+}
+
+/// Merges command-line flags over template values, then config defaults.
+/// Flags always win.
+pub fn resolve_create_params(
+    flags: CreateFlags,
+    template: Option<NoteTemplate>,
+    defaults: CreateDefaults,
+) -> Result<CreateParams, Box<dyn Error>> {
+    let template = template.unwrap_or_default();
+    let mut sources = Vec::new();
+    let mut missing = Vec::new();
+
+    let mut pick = |name: &'static str,
+                    flag: Option<String>,
+                    file: Option<String>,
+                    config: Option<String>| match (flag, file, config) {
+        (Some(value), _, _) => {
+            sources.push((name, FieldSource::Flag));
+            value
+        }
+        (None, Some(value), _) => {
+            sources.push((name, FieldSource::Template));
+            value
+        }
+        (None, None, Some(value)) => {
+            sources.push((name, FieldSource::Config));
+            value
+        }
+        (None, None, None) => {
+            missing.push(name);
+            String::new()
+        }
+    };
+
+    let body = Some(template.body).filter(|body| !body.trim().is_empty());
+    let parent_object = pick(
+        "parent_object",
+        flags.parent_object,
+        template.frontmatter.parent_object,
+        defaults.parent_object.clone(),
+    );
+    // A default record only makes sense for the object it was configured with
+    let default_record_id = defaults
+        .parent_record_id
+        .filter(|_| defaults.parent_object.as_deref() == Some(parent_object.as_str()));
+    let parent_record_id = pick(
+        "parent_record_id",
+        flags.parent_record_id,
+        template.frontmatter.parent_record_id,
+        default_record_id,
+    );
+    let title = pick("title", flags.title, template.frontmatter.title, None);
+    let content = if flags.allow_empty && flags.content.is_none() && body.is_none() {
+        sources.push(("content", FieldSource::Default));
+        String::new()
+    } else {
+        pick("content", flags.content, body, None)
+    };
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "Missing required note fields: {}. Pass them as flags (e.g. --{}) or set them in the template.",
+            missing.join(", "),
+            missing[0].replace('_', "-")
+        )
+        .into());
+    }
+
+    if !flags.allow_empty && content.trim().is_empty() {
+        return Err(format!(
+            "Note content is empty. Add some content, or pass --allow-empty if {} accepts empty notes.",
+            parent_object
+        )
+        .into());
+    }
+
+    let format = match (flags.format, template.frontmatter.format) {
+        (Some(value), _) => {
+            sources.push(("format", FieldSource::Flag));
+            value
+        }
+        (None, Some(value)) => {
+            sources.push(("format", FieldSource::Template));
+            CreateNoteData::parse_format(&value)
+                .map_err(|e| format!("Invalid format in the template: {}", e))?
+        }
+        (None, None) => {
+            sources.push(("format", FieldSource::Default));
+            "plaintext".to_string()
+        }
+    };
+
+    Ok(CreateParams {
+        parent_object,
+        parent_record_id,
+        title,
+        content,
+        format,
+        sources,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "---\nparent_object: people\nparent_record_id: rec_123\ntitle: Intro call\nformat: markdown\n---\n# Agenda\n\n- Goals\n";
+
+    #[test]
+    fn test_parse_template_with_frontmatter() {
+        let template = parse_template(TEMPLATE).unwrap();
+
+        assert_eq!(
+            template.frontmatter.parent_object,
+            Some("people".to_string())
+        );
+        assert_eq!(template.frontmatter.title, Some("Intro call".to_string()));
+        assert_eq!(template.frontmatter.format, Some("markdown".to_string()));
+        assert_eq!(template.body, "# Agenda\n\n- Goals\n");
+    }
+
+    #[test]
+    fn test_parse_template_without_frontmatter() {
+        let template = parse_template("Just a body\n").unwrap();
+
+        assert_eq!(template.frontmatter, TemplateFrontmatter::default());
+        assert_eq!(template.body, "Just a body\n");
+    }
+
+    #[test]
+    fn test_parse_template_unclosed_frontmatter() {
+        let result = parse_template("---\ntitle: Oops\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_template_rejects_unknown_keys() {
+        let result = parse_template("---\ntitel: Typo\n---\nbody");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_flags_override_template() {
+        let template = parse_template(TEMPLATE).unwrap();
+        let flags = CreateFlags {
+            title: Some("Follow-up call".to_string()),
+            ..Default::default()
+        };
+
+        let params =
+            resolve_create_params(flags, Some(template), CreateDefaults::default()).unwrap();
+
+        assert_eq!(params.title, "Follow-up call");
+        assert_eq!(params.parent_object, "people");
+        assert_eq!(params.format, "markdown");
+        assert!(params.sources.contains(&("title", FieldSource::Flag)));
+        assert!(
+            params
+                .sources
+                .contains(&("parent_object", FieldSource::Template))
+        );
+        assert!(params.sources.contains(&("content", FieldSource::Template)));
+    }
+
+    #[test]
+    fn test_missing_required_fields_are_reported() {
+        let template = parse_template("---\ntitle: Only a title\n---\n").unwrap();
+
+        let err = resolve_create_params(
+            CreateFlags::default(),
+            Some(template),
+            CreateDefaults::default(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("parent_object"));
+        assert!(err.contains("parent_record_id"));
+        assert!(err.contains("content"));
+        assert!(!err.contains("title,"));
+    }
+
+    #[test]
+    fn test_format_defaults_to_plaintext() {
+        let flags = CreateFlags {
+            parent_object: Some("people".to_string()),
+            parent_record_id: Some("rec".to_string()),
+            title: Some("Title".to_string()),
+            content: Some("Body".to_string()),
+            format: None,
+            allow_empty: false,
+        };
+
+        let params = resolve_create_params(flags, None, CreateDefaults::default()).unwrap();
+
+        assert_eq!(params.format, "plaintext");
+        assert!(params.sources.contains(&("format", FieldSource::Default)));
+    }
+
+    #[test]
+    fn test_template_format_is_checked() {
+        let template =
+            parse_template(&TEMPLATE.replace("format: markdown", "format: html")).unwrap();
+        let err = resolve_create_params(
+            CreateFlags::default(),
+            Some(template),
+            CreateDefaults::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid format in the template: unknown format 'html'. Valid formats: plaintext, markdown"
+        );
+    }
+
+    #[test]
+    fn test_config_defaults_fill_missing_parent() {
+        let flags = CreateFlags {
+            title: Some("Title".to_string()),
+            content: Some("Body".to_string()),
+            ..Default::default()
+        };
+        let defaults = CreateDefaults {
+            parent_object: Some("companies".to_string()),
+            parent_record_id: Some("rec_default".to_string()),
+        };
+
+        let params = resolve_create_params(flags, None, defaults).unwrap();
+
+        assert_eq!(params.parent_object, "companies");
+        assert_eq!(params.parent_record_id, "rec_default");
+        assert!(
+            params
+                .sources
+                .contains(&("parent_object", FieldSource::Config))
+        );
+    }
+
+    #[test]
+    fn test_flags_and_template_override_config_defaults() {
+        let template = parse_template(TEMPLATE).unwrap();
+        let defaults = CreateDefaults {
+            parent_object: Some("companies".to_string()),
+            parent_record_id: Some("rec_default".to_string()),
+        };
+
+        let params =
+            resolve_create_params(CreateFlags::default(), Some(template), defaults).unwrap();
+
+        assert_eq!(params.parent_object, "people");
+        assert_eq!(params.parent_record_id, "rec_123");
+    }
+
+    #[test]
+    fn test_default_record_ignored_for_other_object() {
+        let flags = CreateFlags {
+            parent_object: Some("people".to_string()),
+            title: Some("Title".to_string()),
+            content: Some("Body".to_string()),
+            ..Default::default()
+        };
+        let defaults = CreateDefaults {
+            parent_object: Some("companies".to_string()),
+            parent_record_id: Some("rec_default".to_string()),
+        };
+
+        let err = resolve_create_params(flags, None, defaults)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("parent_record_id"));
+    }
+
+    fn flags_with_content(content: &str) -> CreateFlags {
+        CreateFlags {
+            parent_object: Some("people".to_string()),
+            parent_record_id: Some("rec".to_string()),
+            title: Some("Title".to_string()),
+            content: Some(content.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_whitespace_only_content_is_rejected() {
+        let err = resolve_create_params(
+            flags_with_content(" \n\t "),
+            None,
+            CreateDefaults::default(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("content is empty"));
+        assert!(err.contains("--allow-empty"));
+        assert!(err.contains("people"));
+    }
+
+    #[test]
+    fn test_allow_empty_accepts_blank_content() {
+        let flags = CreateFlags {
+            allow_empty: true,
+            ..flags_with_content("   ")
+        };
+
+        let params = resolve_create_params(flags, None, CreateDefaults::default()).unwrap();
+
+        assert_eq!(params.content, "   ");
+    }
+
+    #[test]
+    fn test_allow_empty_with_blank_template_body() {
+        let template = parse_template("---\ntitle: Empty\n---\n  \n").unwrap();
+        let flags = CreateFlags {
+            parent_object: Some("people".to_string()),
+            parent_record_id: Some("rec".to_string()),
+            allow_empty: true,
+            ..Default::default()
+        };
+
+        let params =
+            resolve_create_params(flags, Some(template), CreateDefaults::default()).unwrap();
+
+        assert_eq!(params.content, "");
+        assert!(params.sources.contains(&("content", FieldSource::Default)));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::symbols::SymbolSet;
+use std::time::{Duration, Instant};
+
+/// Collects named durations for the `--timings` report printed on stderr.
+pub struct Timings {
+    enabled: bool,
+    symbols: SymbolSet,
+    start: Instant,
+    entries: Vec<(String, Duration)>,
+}
+
+impl Timings {
+    pub fn new(enabled: bool, symbols: SymbolSet) -> Self {
+        Self {
+            enabled,
+            symbols,
+            start: Instant::now(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, label: &str, duration: Duration) {
+        if self.enabled {
+            self.entries.push((label.to_string(), duration));
+        }
+    }
+
+    /// Prints all recorded timings plus the total elapsed time since creation
+    pub fn report(&self) {
+        if !self.enabled {
+            return;
+        }
+        for (label, duration) in &self.entries {
+            eprintln!(
+                "{} {:<24} {}",
+                self.symbols.timer(),
+                label,
+                format_duration(*duration)
+            );
+        }
+        eprintln!(
+            "{} {:<24} {}",
+            self.symbols.timer(),
+            "total",
+            format_duration(self.start.elapsed())
+        );
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() >= 1 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(42)), "42ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50s");
+    }
+
+    #[test]
+    fn test_disabled_timings_record_nothing() {
+        let mut timings = Timings::new(false, SymbolSet::Ascii);
+        timings.record("request", Duration::from_millis(10));
+        assert!(timings.entries.is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::fsutil;
+use reqwest::header::{self, HeaderMap};
+use reqwest::{Client, Request, RequestBuilder, Response, ResponseBuilderExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Headers whose values never reach the trace file
+const REDACTED_HEADERS: [header::HeaderName; 2] =
+    [header::AUTHORIZATION, header::PROXY_AUTHORIZATION];
+
+/// The tracer `--trace` set up for this run
+static GLOBAL: OnceLock<Tracer> = OnceLock::new();
+
+/// Starts tracing every API request of this run to `path`
+pub fn start(path: &Path) -> io::Result<()> {
+    let tracer = Tracer::create(path)?;
+    let _ = GLOBAL.set(tracer);
+    Ok(())
+}
+
+/// The tracer started with `start`, if any
+pub fn global() -> Option<Tracer> {
+    GLOBAL.get().cloned()
+}
+
+/// Appends one JSON line per request and its response (or error) to a file.
+/// Clones share the file.
+#[derive(Clone)]
+pub struct Tracer {
+    file: Arc<Mutex<File>>,
+}
+
+#[derive(Serialize)]
+struct TraceEntry {
+    /// When the request was sent, in milliseconds since the Unix epoch
+    timestamp_ms: u128,
+    request: TracedRequest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response: Option<TracedResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    duration_ms: u128,
+}
+
+#[derive(Serialize)]
+struct TracedRequest {
+    method: String,
+    url: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct TracedResponse {
+    status: u16,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+impl Tracer {
+    /// Appends to `path`, which is created readable by the current user only
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let file = fsutil::append_private(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Sends `request`, recording it, along with the `default_headers` its
+    /// client adds, and what came back. The response body is read to record
+    /// it, so the returned response holds it in memory.
+    pub async fn send(
+        &self,
+        request: RequestBuilder,
+        default_headers: &HeaderMap,
+    ) -> reqwest::Result<Response> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let traced = TracedRequest::new(&request, default_headers);
+        let timestamp_ms = timestamp_ms();
+        let started = Instant::now();
+        let (result, response) = send_and_buffer(&client, request).await;
+        self.write(&TraceEntry {
+            timestamp_ms,
+            request: traced,
+            response,
+            error: result.as_ref().err().map(|e| e.to_string()),
+            duration_ms: started.elapsed().as_millis(),
+        });
+        result
+    }
+
+    /// Sends `request` untouched, recording only its method, its URL without
+    /// the query string and the response status. For downloads, whose bodies
+    /// must stream and whose URLs may carry signatures.
+    pub async fn send_summary(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let full_url = request.url().to_string();
+        let mut url = request.url().clone();
+        url.set_query(None);
+        url.set_fragment(None);
+        let traced = TracedRequest {
+            method: request.method().to_string(),
+            url: url.to_string(),
+            headers: BTreeMap::new(),
+            body: None,
+        };
+        let timestamp_ms = timestamp_ms();
+        let started = Instant::now();
+        let result = client.execute(request).await;
+        self.write(&TraceEntry {
+            timestamp_ms,
+            request: traced,
+            response: result.as_ref().ok().map(|response| TracedResponse {
+                status: response.status().as_u16(),
+                headers: BTreeMap::new(),
+                body: None,
+            }),
+            error: result
+                .as_ref()
+                .err()
+                .map(|e| e.to_string().replace(&full_url, url.as_str())),
+            duration_ms: started.elapsed().as_millis(),
+        });
+        result
+    }
+
+    /// A failed write only loses trace lines, so it is logged, not returned
+    fn write(&self, entry: &TraceEntry) {
+        let written = serde_json::to_string(entry)
+            .map_err(io::Error::other)
+            .and_then(|line| {
+                let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+                writeln!(file, "{}", line)
+            });
+        if let Err(e) = written {
+            log::warn!("Could not write to the trace file: {}", e);
+        }
+    }
+}
+
+fn timestamp_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis())
+}
+
+/// Executes `request` and reads the whole body, returning a response rebuilt
+/// around it along with what to record of it
+async fn send_and_buffer(
+    client: &Client,
+    request: Request,
+) -> (reqwest::Result<Response>, Option<TracedResponse>) {
+    let response = match client.execute(request).await {
+        Ok(response) => response,
+        Err(e) => return (Err(e), None),
+    };
+    let status = response.status();
+    let version = response.version();
+    let url = response.url().clone();
+    let headers = response.headers().clone();
+    let body = match response.bytes().await {
+        Ok(body) => body,
+        Err(e) => return (Err(e), None),
+    };
+    let traced = TracedResponse {
+        status: status.as_u16(),
+        headers: redacted(&headers),
+        body: Some(body_value(&body)),
+    };
+    let mut response = http::Response::new(body);
+    *response.status_mut() = status;
+    *response.version_mut() = version;
+    *response.headers_mut() = headers;
+    // Keeps the URL in errors about the response; only reqwest can set it
+    if let Ok(with_url) = http::Response::builder().url(url).body(()) {
+        response
+            .extensions_mut()
+            .extend(with_url.into_parts().0.extensions);
+    }
+    (Ok(response.into()), Some(traced))
+}
+
+impl TracedRequest {
+    fn new(request: &Request, default_headers: &HeaderMap) -> Self {
+        let mut headers = redacted(default_headers);
+        headers.extend(redacted(request.headers()));
+        Self {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers,
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(body_value),
+        }
+    }
+}
+
+/// `headers` as strings, with credentials replaced
+fn redacted(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_HEADERS.contains(name) {
+                "[REDACTED]".to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// A body as JSON when it is JSON, as text when it is UTF-8, and otherwise
+/// just its length
+fn body_value(body: &[u8]) -> Value {
+    if let Ok(json) = serde_json::from_slice(body) {
+        return json;
+    }
+    match std::str::from_utf8(body) {
+        Ok(text) => Value::String(text.to_string()),
+        Err(_) => Value::String(format!("<{} bytes of binary data>", body.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted_hides_credentials() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+        headers.insert(header::ACCEPT, "application/json".parse().unwrap());
+
+        let redacted = redacted(&headers);
+        assert_eq!(redacted["authorization"], "[REDACTED]");
+        assert_eq!(redacted["accept"], "application/json");
+    }
+
+    #[test]
+    fn test_body_value() {
+        assert_eq!(body_value(br#"{"a":1}"#), serde_json::json!({ "a": 1 }));
+        assert_eq!(body_value(b"plain"), Value::String("plain".to_string()));
+        assert_eq!(
+            body_value(&[0xff, 0xfe]),
+            Value::String("<2 bytes of binary data>".to_string())
+        );
+    }
+}
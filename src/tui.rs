@@ -1,274 +1,1283 @@
 use crate::cache;
-use crate::client::AttioClient;
-use crate::models::Note;
+use crate::client::{AttioClient, DryRunRequest, PageToken};
+use crate::clipboard::ClipboardWriter;
+use crate::error::AttioError;
+use crate::filter::{self, ContentFilter, SearchScope};
+use crate::fsutil;
+use crate::logging;
+use crate::markdown;
+use crate::models::{CreateNoteData, CreateRequest, Note};
+use crate::notify::NotifyMode;
+use crate::template::CreateDefaults;
 use crossterm::{
-    event::{self, Event, KeyCode},
+    cursor,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+        MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use futures::StreamExt;
 use ratatui::{
     Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
 };
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::fs::OpenOptions;
+use std::fs;
 use std::io::{self, Write};
+use std::ops::Range;
 use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use theme::Theme;
+
+pub mod theme;
+
+/// Whether `setup_terminal` has changed terminal modes that still need restoring
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the UI is drawn on stderr, leaving stdout for the picked note ID
+static DRAW_ON_STDERR: AtomicBool = AtomicBool::new(false);
+
+type Screen = Terminal<CrosstermBackend<Box<dyn Write>>>;
+
+/// The stream the UI is drawn on
+fn screen() -> Box<dyn Write> {
+    if DRAW_ON_STDERR.load(Ordering::SeqCst) {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    }
+}
 
 #[derive(PartialEq)]
 enum InputMode {
     Normal,
     Search,
+    /// Filling in the create note form
+    Create,
 }
 
-fn log_debug(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/attio-cli.log")
-    {
-        let _ = writeln!(file, "{}", msg);
-        let _ = file.flush();
+/// How long a confirmation like "Copied!" stays in the footer
+const FLASH_DURATION: Duration = Duration::from_secs(2);
+/// Notes per request during fetch-all; Attio's API has a max limit around 50
+const FETCH_ALL_PAGE_SIZE: u32 = 50;
+/// Wait after a 429 that came without a `Retry-After` header
+const RATE_LIMIT_WAIT: Duration = Duration::from_secs(10);
+/// Rate-limit pauses in a row before fetch-all gives up
+const MAX_RATE_LIMIT_WAITS: u32 = 5;
+
+/// Progress of a Ctrl+A fetch-all, shown in the table title
+#[derive(Debug, Default)]
+struct FetchProgress {
+    pages: u32,
+    /// Notes received so far, counted against the fetch-all cap
+    notes: usize,
+    /// Total notes, when the API reports one
+    total: Option<usize>,
+    /// Seconds left in a rate-limit pause
+    rate_limited_secs: Option<u64>,
+}
+
+impl FetchProgress {
+    /// Counts a fetched page towards `max`, dropping any notes past it. True
+    /// when the cap ends the fetch while the API `has_more`.
+    fn count_page(&mut self, notes: &mut Vec<Note>, max: Option<usize>, has_more: bool) -> bool {
+        let capped = has_more && max.is_some_and(|max| self.notes + notes.len() >= max);
+        if let Some(max) = max {
+            notes.truncate(max.saturating_sub(self.notes));
+        }
+        self.notes += notes.len();
+        capped
+    }
+
+    fn label(&self, cached: usize, page_size: u32) -> String {
+        let mut label = match self.total {
+            Some(total) => format!(
+                "Fetching all... page {} of ~{} ({} of ~{} notes)",
+                self.pages,
+                total.div_ceil(page_size.max(1) as usize),
+                cached,
+                total
+            ),
+            None => format!("Fetching all... page {} ({} cached)", self.pages, cached),
+        };
+        if let Some(secs) = self.rate_limited_secs {
+            label.push_str(&format!(", rate limited, waiting {}s", secs));
+        }
+        label
+    }
+}
+
+/// Full-screen view of one note, opened with Enter
+struct DetailView {
+    note: Note,
+    /// First visible line of the body
+    scroll: u16,
+}
+
+impl DetailView {
+    fn new(note: Note) -> Self {
+        Self { note, scroll: 0 }
+    }
+
+    /// Metadata followed by the styled content, wrapped to `width` columns
+    fn lines(&self, width: u16, theme: &Theme) -> Vec<Line<'static>> {
+        let meta = Style::default().fg(theme.muted);
+        let mut lines = vec![
+            Line::styled(
+                format!(
+                    "Parent:  {} {}",
+                    self.note.parent_object, self.note.parent_record_id
+                ),
+                meta,
+            ),
+            Line::styled(format!("Created: {}", self.note.created_at), meta),
+            Line::default(),
+        ];
+        lines.extend(markdown::render(&self.note.markdown_content(), width));
+        lines
+    }
+
+    /// Scrolls by `delta` lines, stopping at the top and at the last of
+    /// `line_count` lines
+    fn scroll_by(&mut self, delta: i32, line_count: usize) {
+        let max = line_count.saturating_sub(1) as i32;
+        self.scroll = (self.scroll as i32 + delta).clamp(0, max.max(0)) as u16;
+    }
+}
+
+/// A field of the create note form, in Tab order
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum FormField {
+    #[default]
+    ParentObject,
+    ParentRecordId,
+    Title,
+    Content,
+}
+
+impl FormField {
+    const ALL: [Self; 4] = [
+        Self::ParentObject,
+        Self::ParentRecordId,
+        Self::Title,
+        Self::Content,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::ParentObject => "Parent object",
+            Self::ParentRecordId => "Parent record ID",
+            Self::Title => "Title",
+            Self::Content => "Content",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|field| *field == self)
+            .unwrap_or(0)
+    }
+
+    /// The next field, wrapping around; `back` for Shift+Tab
+    fn step(self, back: bool) -> Self {
+        let len = Self::ALL.len();
+        let next = if back {
+            self.index() + len - 1
+        } else {
+            self.index() + 1
+        };
+        Self::ALL[next % len]
+    }
+}
+
+/// What a key press in the create form asks for
+#[derive(Debug, PartialEq)]
+enum FormAction {
+    None,
+    Submit,
+    Cancel,
+}
+
+/// The note being written in the form opened with `c`
+#[derive(Debug, Default)]
+struct CreateForm {
+    /// One value per `FormField`, in the same order
+    values: [String; 4],
+    focus: FormField,
+    /// Required fields that were blank at the last submit
+    missing: Vec<FormField>,
+}
+
+impl CreateForm {
+    /// An empty form, with the parent filled in from the config defaults
+    fn new(defaults: &CreateDefaults) -> Self {
+        let mut form = Self::default();
+        if let Some(object) = &defaults.parent_object {
+            form.values[FormField::ParentObject.index()] = object.clone();
+            form.focus = FormField::ParentRecordId;
+            if let Some(record_id) = &defaults.parent_record_id {
+                form.values[FormField::ParentRecordId.index()] = record_id.clone();
+                form.focus = FormField::Title;
+            }
+        }
+        form
+    }
+
+    fn value(&self, field: FormField) -> &str {
+        &self.values[field.index()]
+    }
+
+    /// Tab and Shift+Tab move between fields, Enter starts a new line in the
+    /// content and moves to the next field elsewhere, Ctrl+S submits and Esc
+    /// cancels
+    fn handle_key(&mut self, key: event::KeyEvent) -> FormAction {
+        let value = &mut self.values[self.focus.index()];
+        match key.code {
+            KeyCode::Esc => return FormAction::Cancel,
+            KeyCode::Char('s') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                return FormAction::Submit;
+            }
+            KeyCode::Tab => self.focus = self.focus.step(false),
+            KeyCode::BackTab => self.focus = self.focus.step(true),
+            KeyCode::Enter if self.focus == FormField::Content => value.push('\n'),
+            KeyCode::Enter => self.focus = self.focus.step(false),
+            KeyCode::Backspace => {
+                value.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                value.push(c)
+            }
+            _ => {}
+        }
+        FormAction::None
+    }
+
+    /// The create request body, or `None` (remembering which fields are
+    /// missing and focusing the first) when a required field is blank
+    fn validate(&mut self) -> Option<CreateNoteData> {
+        self.missing = FormField::ALL
+            .into_iter()
+            .filter(|field| self.value(*field).trim().is_empty())
+            .collect();
+        if let Some(first) = self.missing.first() {
+            self.focus = *first;
+            return None;
+        }
+        let [parent_object, parent_record_id, title, content] =
+            self.values.clone().map(|value| value.trim().to_string());
+        Some(CreateNoteData {
+            parent_object,
+            parent_record_id,
+            title,
+            format: "plaintext".to_string(),
+            content,
+        })
+    }
+
+    /// A label and value line per field, marking the focused one with a
+    /// cursor and blank required ones in red
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for field in FormField::ALL {
+            let focused = field == self.focus;
+            let label_style = if self.missing.contains(&field) {
+                Style::default()
+                    .fg(theme.danger)
+                    .add_modifier(Modifier::BOLD)
+            } else if focused {
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.header)
+            };
+            let mut label = vec![Span::styled(field.label(), label_style)];
+            if self.missing.contains(&field) {
+                label.push(Span::styled(" (required)", label_style));
+            }
+            lines.push(Line::from(label));
+
+            let value = self.value(field);
+            let mut value_lines: Vec<String> = value.split('\n').map(str::to_string).collect();
+            if focused && let Some(last) = value_lines.last_mut() {
+                last.push('_');
+            }
+            lines.extend(
+                value_lines
+                    .into_iter()
+                    .map(|line| Line::raw(format!("  {}", line))),
+            );
+            lines.push(Line::default());
+        }
+        lines
     }
 }
 
-pub async fn run_list_tui(client: AttioClient, cache_limit_mb: u64) -> Result<(), Box<dyn Error>> {
-    log_debug("--- SESSION START ---");
+/// Column the note list is sorted by, cycled with `s`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    /// The order notes arrived from the API
+    #[default]
+    Fetched,
+    Created,
+    Title,
+    Id,
+}
+
+impl SortKey {
+    fn next(self) -> Self {
+        match self {
+            Self::Fetched => Self::Created,
+            Self::Created => Self::Title,
+            Self::Title => Self::Id,
+            Self::Id => Self::Fetched,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fetched => "fetched",
+            Self::Created => "created",
+            Self::Title => "title",
+            Self::Id => "id",
+        }
+    }
+}
+
+/// How the cached notes are ordered.
+///
+/// Notes with equal keys keep the order they arrived in, however many times
+/// the key or direction has changed since.
+#[derive(Debug, Default)]
+struct NoteOrder {
+    key: SortKey,
+    descending: bool,
+    /// Arrival sequence number of each cached note
+    arrivals: HashMap<String, usize>,
+    next_arrival: usize,
+}
+
+impl NoteOrder {
+    /// Numbers notes not seen before, in their current (API) order
+    fn record_arrivals(&mut self, notes: &[Note]) {
+        for note in notes {
+            if !self.arrivals.contains_key(&note.id.note_id) {
+                self.arrivals
+                    .insert(note.id.note_id.clone(), self.next_arrival);
+                self.next_arrival += 1;
+            }
+        }
+    }
+
+    /// Numbers `note` ahead of every note seen so far, so a note created in
+    /// the TUI stays at the top of the fetched order
+    fn record_first(&mut self, note: &Note) {
+        for arrival in self.arrivals.values_mut() {
+            *arrival += 1;
+        }
+        self.next_arrival += 1;
+        self.arrivals.insert(note.id.note_id.clone(), 0);
+    }
+
+    /// Numbers `notes` ahead of every note seen so far, keeping their order,
+    /// so notes picked up by a refresh sit at the top of the fetched order
+    fn record_newest(&mut self, notes: &[Note]) {
+        for note in notes.iter().rev() {
+            self.record_first(note);
+        }
+    }
+
+    /// Stops tracking notes that are no longer cached
+    fn forget_missing(&mut self, notes: &[Note]) {
+        if self.arrivals.len() > notes.len() {
+            let cached: HashSet<&str> = notes.iter().map(|note| note.id.note_id.as_str()).collect();
+            self.arrivals.retain(|id, _| cached.contains(id.as_str()));
+        }
+    }
+
+    /// Sorts `notes` in place by the current key and direction
+    fn apply(&self, notes: &mut [Note]) {
+        let arrival = |note: &Note| {
+            self.arrivals
+                .get(&note.id.note_id)
+                .copied()
+                .unwrap_or(usize::MAX)
+        };
+        notes.sort_by(|a, b| {
+            let by_key = match self.key {
+                SortKey::Fetched => arrival(a).cmp(&arrival(b)),
+                // Unparseable timestamps sort before all others
+                SortKey::Created => a
+                    .created_at_datetime()
+                    .ok()
+                    .cmp(&b.created_at_datetime().ok()),
+                SortKey::Title => a
+                    .title
+                    .chars()
+                    .flat_map(char::to_lowercase)
+                    .cmp(b.title.chars().flat_map(char::to_lowercase)),
+                SortKey::Id => a.id.note_id.cmp(&b.id.note_id),
+            };
+            let by_key = if self.descending {
+                by_key.reverse()
+            } else {
+                by_key
+            };
+            by_key.then_with(|| arrival(a).cmp(&arrival(b)))
+        });
+    }
+
+    /// Sort key and direction for the table title, e.g. `title ↓`
+    fn label(&self) -> String {
+        let arrow = if self.descending { "↓" } else { "↑" };
+        format!("{} {}", self.key.label(), arrow)
+    }
+}
+
+/// Where the list was left when the TUI last closed, reopened on the next
+/// launch. Kept apart from the notes cache so it works with the cache off.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedState {
+    /// Position of the first note on the page in the (sorted) list
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub search_query: String,
+    #[serde(default)]
+    pub search_scope: SearchScope,
+    #[serde(default)]
+    pub sort_key: SortKey,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+impl SavedState {
+    /// Reads the state file; a missing or unreadable one starts fresh
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fsutil::write_private(path, serde_json::to_string_pretty(self)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// The TUI state file, next to the config file
+pub fn state_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name("tui-state.json")
+}
+
+/// Paging state for the cached list and for filtered (search) results.
+///
+/// The cached list and filtered results keep separate offsets so leaving a
+/// search returns to the page the user was on.
+#[derive(Debug)]
+struct Pagination {
+    /// Index of the first visible note in the cache
+    offset: u32,
+    /// Index of the first visible note in the filtered results
+    search_offset: u32,
+    /// Notes per page, derived from the terminal height
+    limit: u32,
+    /// Whether the API may have notes beyond what is cached
+    has_more: bool,
+    /// Notes evicted from the cache, which still count towards the API offset.
+    /// With the cache off, these are all the notes before the current page.
+    evicted: usize,
+    /// Whether browsed notes accumulate; when false only the current page is held
+    cached: bool,
+}
+
+impl Pagination {
+    fn new(limit: u32) -> Self {
+        Self {
+            offset: 0,
+            search_offset: 0,
+            limit: limit.max(1),
+            has_more: true,
+            evicted: 0,
+            cached: true,
+        }
+    }
+
+    /// Switches to pages of `limit` notes after a resize. Both offsets snap
+    /// to a page boundary, and the current page becomes the one holding the
+    /// selected row of `len` available notes. Returns that note's new row.
+    fn resize(&mut self, limit: u32, selected: usize, len: usize, filtered: bool) -> usize {
+        let limit = limit.max(1);
+        let focus = (self.current_offset(filtered) as usize + selected).min(len.saturating_sub(1));
+        self.limit = limit;
+        self.offset = self.offset / limit * limit;
+        self.search_offset = self.search_offset / limit * limit;
+        let top = (focus as u32) / limit * limit;
+        if filtered {
+            self.search_offset = top;
+        } else {
+            self.offset = top;
+        }
+        focus - top as usize
+    }
+
+    fn current_offset(&self, filtered: bool) -> u32 {
+        if filtered {
+            self.search_offset
+        } else {
+            self.offset
+        }
+    }
+
+    /// Indices of the notes to show out of `len` available ones
+    fn visible_range(&self, len: usize, filtered: bool) -> Range<usize> {
+        let start = (self.current_offset(filtered) as usize).min(len);
+        let end = (start + self.limit as usize).min(len);
+        start..end
+    }
+
+    /// 1-based page number shown in the title
+    fn page_number(&self, filtered: bool) -> u32 {
+        let before = if self.cached || filtered {
+            0
+        } else {
+            self.evicted as u32
+        };
+        (before + self.current_offset(filtered)) / self.limit + 1
+    }
+
+    /// Moves to the next page if it has any of the `len` available notes
+    fn next_page(&mut self, len: usize, filtered: bool) -> bool {
+        let next = self.current_offset(filtered) + self.limit;
+        if (next as usize) >= len {
+            return false;
+        }
+        if filtered {
+            self.search_offset = next;
+        } else {
+            self.offset = next;
+        }
+        true
+    }
+
+    fn prev_page(&mut self, filtered: bool) -> bool {
+        let current = self.current_offset(filtered);
+        if current == 0 {
+            return false;
+        }
+        let prev = current.saturating_sub(self.limit);
+        if filtered {
+            self.search_offset = prev;
+        } else {
+            self.offset = prev;
+        }
+        true
+    }
+
+    /// True when the next page of the cached list isn't cached yet but the
+    /// API may still have it
+    fn needs_fetch(&self, cached_len: usize) -> bool {
+        self.has_more && (self.offset + self.limit) as usize >= cached_len
+    }
+
+    /// API offset of the first note that hasn't been fetched yet
+    fn fetch_offset(&self, cached_len: usize) -> u32 {
+        (cached_len + self.evicted) as u32
+    }
+
+    /// Records `count` notes evicted from the cache. The page stays on the
+    /// note that was at its top, now at index `anchor` (if still cached).
+    fn notes_evicted(&mut self, count: usize, anchor: Option<usize>) {
+        self.evicted += count;
+        self.offset = anchor.unwrap_or(0) as u32;
+    }
+
+    /// Records the result of fetching a page of `requested` notes
+    fn page_fetched(&mut self, requested: u32, received: usize) {
+        self.has_more = received >= requested as usize;
+    }
+
+    /// With the cache off: records that the page fetched at `api_offset`, of
+    /// `received` notes, replaced the one held before
+    fn page_replaced(&mut self, api_offset: usize, received: usize) {
+        self.evicted = api_offset;
+        self.offset = 0;
+        self.search_offset = 0;
+        self.has_more = received >= self.limit as usize;
+    }
+
+    /// With the cache off: API offset of the page before the held one, if any
+    fn prev_fetch_offset(&self) -> Option<usize> {
+        (self.evicted > 0).then(|| self.evicted.saturating_sub(self.limit as usize))
+    }
+
+    fn reset_search(&mut self) {
+        self.search_offset = 0;
+    }
+
+    /// Jumps to the first page
+    fn first_page(&mut self, filtered: bool) {
+        if filtered {
+            self.search_offset = 0;
+        } else {
+            self.offset = 0;
+        }
+    }
+
+    /// Jumps to the last page holding any of the `len` available notes
+    fn last_page(&mut self, len: usize, filtered: bool) {
+        let last = (len.saturating_sub(1) as u32 / self.limit) * self.limit;
+        if filtered {
+            self.search_offset = last;
+        } else {
+            self.offset = last;
+        }
+    }
+
+    /// Moves the selection to the next (or previous) of `len` filtered
+    /// matches, wrapping around and turning pages as needed. Returns the new
+    /// row on the current page.
+    fn step_match(&mut self, selected: usize, len: usize, forward: bool) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        let current = (self.search_offset as usize + selected).min(len - 1);
+        let next = if forward {
+            (current + 1) % len
+        } else {
+            (current + len - 1) % len
+        };
+        let limit = self.limit as usize;
+        self.search_offset = (next / limit * limit) as u32;
+        next - self.search_offset as usize
+    }
+
+    /// Steps back a page when the current one no longer has any of the `len`
+    /// available notes, e.g. after deleting the last note on the last page
+    fn step_back_if_empty(&mut self, len: usize, filtered: bool) {
+        while self.current_offset(filtered) as usize >= len && self.prev_page(filtered) {}
+    }
+}
+
+/// Maps vim-style keys to the arrow keys they stand in for. Only applies in
+/// normal mode so the letters can still be typed into a search.
+fn vim_alias(code: KeyCode, input_mode: &InputMode) -> KeyCode {
+    match (code, input_mode) {
+        (KeyCode::Char('h'), InputMode::Normal) => KeyCode::Left,
+        (KeyCode::Char('l'), InputMode::Normal) => KeyCode::Right,
+        _ => code,
+    }
+}
+
+/// Notes from a refreshed page that aren't cached yet, in page order
+fn unseen_notes(cache: &[Note], page: Vec<Note>) -> Vec<Note> {
+    let cached: HashSet<&str> = cache.iter().map(|note| note.id.note_id.as_str()).collect();
+    page.into_iter()
+        .filter(|note| !cached.contains(note.id.note_id.as_str()))
+        .collect()
+}
+
+/// Title indicator for the last refresh, e.g. `Refreshed 12s ago`
+fn refreshed_label(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("Refreshed {}s ago", secs)
+    } else {
+        format!("Refreshed {}m ago", secs / 60)
+    }
+}
+
+/// The footer confirmation, while it is still fresh
+fn flash_message(flash: &Option<(String, Instant)>) -> Option<&str> {
+    flash
+        .as_ref()
+        .filter(|(_, shown)| shown.elapsed() < FLASH_DURATION)
+        .map(|(message, _)| message.as_str())
+}
+
+/// Splits the list screen into the search box (when shown), the notes table
+/// and the help footer
+fn list_layout(area: Rect, search_shown: bool) -> (Option<Rect>, Rect, Rect) {
+    if search_shown {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Search box
+                Constraint::Min(0),    // Notes table
+                Constraint::Length(3), // Help footer
+            ])
+            .split(area);
+        (Some(chunks[0]), chunks[1], chunks[2])
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Notes table
+                Constraint::Length(3), // Help footer
+            ])
+            .split(area);
+        (None, chunks[0], chunks[1])
+    }
+}
+
+/// The page row under a click at `column`, `row` in the notes `table`,
+/// whose first row sits below the border and the header
+fn clicked_row(table: Rect, column: u16, row: u16) -> Option<usize> {
+    let first_row = table.y + 2;
+    let inside = column > table.x
+        && column + 1 < table.x + table.width
+        && row >= first_row
+        && row + 1 < table.y + table.height;
+    inside.then(|| (row - first_row) as usize)
+}
+
+/// Whether `event` is q or Esc, which cancel a fetch-all waiting on a rate limit
+fn is_cancel_key(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Key(key)
+            if key.kind == KeyEventKind::Press
+                && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+    )
+}
+
+/// The key the scroll wheel stands in for: paging the list, or scrolling an
+/// open note by a line
+fn scroll_key(kind: MouseEventKind, in_detail: bool) -> Option<KeyCode> {
+    match (kind, in_detail) {
+        (MouseEventKind::ScrollDown, false) => Some(KeyCode::Right),
+        (MouseEventKind::ScrollUp, false) => Some(KeyCode::Left),
+        (MouseEventKind::ScrollDown, true) => Some(KeyCode::Down),
+        (MouseEventKind::ScrollUp, true) => Some(KeyCode::Up),
+        _ => None,
+    }
+}
+
+/// A `width` x `height` rectangle centered in `area`, shrunk to fit
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Notes matching the search query and content filter, in cache order
+fn filter_notes<'a>(
+    notes: &'a [Note],
+    query: &str,
+    scope: SearchScope,
+    content_filter: ContentFilter,
+) -> Vec<&'a Note> {
+    notes
+        .iter()
+        .filter(|note| content_filter.matches(note) && filter::note_matches(note, query, scope))
+        .collect()
+}
+
+/// The notes on the current page, plus the total match count when a search
+/// or content filter is active
+fn visible_notes<'a>(
+    all_notes: &'a [Note],
+    search_query: &str,
+    search_scope: SearchScope,
+    content_filter: ContentFilter,
+    pagination: &Pagination,
+) -> (Vec<&'a Note>, Option<usize>) {
+    let filtered = !search_query.is_empty() || content_filter != ContentFilter::All;
+    if filtered {
+        // Search/filter mode: filter all notes and paginate through filtered results
+        let matches = filter_notes(all_notes, search_query, search_scope, content_filter);
+        let total = matches.len();
+        let range = pagination.visible_range(total, true);
+        (matches[range].to_vec(), Some(total))
+    } else {
+        // Normal mode: show slice of cached notes
+        let range = pagination.visible_range(all_notes.len(), false);
+        (all_notes[range].iter().collect(), None)
+    }
+}
+
+/// Switches the terminal into the modes the TUI needs, capturing the mouse
+/// when `mouse` is set.
+///
+/// Every mode enabled here must be undone in `restore_terminal`.
+fn setup_terminal(mouse: bool, on_stderr: bool) -> io::Result<Screen> {
+    DRAW_ON_STDERR.store(on_stderr, Ordering::SeqCst);
+    enable_raw_mode()?;
+    TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+    // Log lines on stderr would draw over the UI
+    logging::redirect_to_file(true);
+
+    let mut out = screen();
+    let entered = execute!(out, EnterAlternateScreen).and_then(|_| {
+        if mouse {
+            execute!(out, EnableMouseCapture)
+        } else {
+            Ok(())
+        }
+    });
+    if let Err(e) = entered {
+        restore_terminal();
+        return Err(e);
+    }
+    Terminal::new(CrosstermBackend::new(out)).inspect_err(|_| restore_terminal())
+}
+
+/// Restores the terminal to its normal state.
+///
+/// Idempotent and safe to call from the panic hook: it does nothing if the
+/// terminal was never set up or has already been restored.
+fn restore_terminal() {
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    // Harmless when the mouse was never captured
+    let _ = execute!(
+        screen(),
+        DisableMouseCapture,
+        LeaveAlternateScreen,
+        cursor::Show
+    );
+    let _ = disable_raw_mode();
+    logging::redirect_to_file(false);
+}
+
+/// Settings for the interactive notes list
+pub struct ListOptions {
+    pub cache_limit_mb: u64,
+    /// Keep browsed notes in memory; when false every page change refetches
+    pub cache_enabled: bool,
+    pub content_filter: ContentFilter,
+    /// Search box contents and scope to start with
+    pub initial_search: Option<(String, SearchScope)>,
+    /// Skip notes the API returns more than once
+    pub dedupe: bool,
+    pub notify_mode: NotifyMode,
+    /// Pause between pages during fetch-all
+    pub fetch_delay: Duration,
+    /// Pages fetch-all requests at once
+    pub fetch_concurrency: usize,
+    /// Notes after which fetch-all stops; `None` fetches everything
+    pub fetch_all_max: Option<usize>,
+    /// Longest fetch-all pause for a rate limit, whatever `Retry-After` asks
+    pub rate_limit_max_wait: Duration,
+    /// Show the delete and create requests instead of sending them
+    pub dry_run: bool,
+    /// Parent filled into the create note form
+    pub create_defaults: CreateDefaults,
+    /// Select rows by clicking and page with the scroll wheel; off leaves the
+    /// mouse to the terminal's own text selection
+    pub mouse: bool,
+    pub theme: Theme,
+    /// How often to re-fetch the first page for new notes; `None` only
+    /// refreshes on `R`
+    pub refresh_interval: Option<Duration>,
+    /// Where to reopen the list; `None` starts on the first page
+    pub saved_state: Option<SavedState>,
+    /// Where to save the state on quit
+    pub state_path: PathBuf,
+    /// The notes cache file fetch-all saves to
+    pub cache_path: Option<PathBuf>,
+}
+
+pub async fn run_list_tui(client: AttioClient, options: ListOptions) -> Result<(), Box<dyn Error>> {
+    run_tui(client, options, false).await.map(|_| ())
+}
+
+/// Runs the list until a note is picked with Enter, drawing on stderr so
+/// stdout stays free for the result. Returns the picked note's ID, or `None`
+/// when the user quit. The terminal is restored before this returns.
+pub async fn run_pick_tui(
+    client: AttioClient,
+    options: ListOptions,
+) -> Result<Option<String>, Box<dyn Error>> {
+    run_tui(client, options, true).await
+}
+
+async fn run_tui(
+    client: AttioClient,
+    options: ListOptions,
+    pick: bool,
+) -> Result<Option<String>, Box<dyn Error>> {
+    log::info!("--- SESSION START ---");
 
     panic::set_hook(Box::new(|info| {
         let msg = format!("CRITICAL PANIC: {}", info);
-        log_debug(&msg);
-        let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        log::error!("{}", msg);
+        restore_terminal();
         eprintln!(
             "\r\n[TUI Error] The application crashed. Terminal restored.\r\n{}\r\n",
             msg
         );
     }));
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    let res = run_app(&mut terminal, client, cache_limit_mb).await;
-
-    let _ = execute!(io::stdout(), LeaveAlternateScreen);
-    let _ = disable_raw_mode();
-    let _ = terminal.show_cursor();
+    let mut terminal = setup_terminal(options.mouse, pick)?;
+    let res = run_app(&mut terminal, client, options, pick).await;
+    restore_terminal();
 
     res
 }
 
 async fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    terminal: &mut Screen,
     client: AttioClient,
-    cache_limit_mb: u64,
-) -> Result<(), Box<dyn Error>> {
-    let mut offset = 0;
+    options: ListOptions,
+    pick: bool,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let ListOptions {
+        cache_limit_mb,
+        cache_enabled,
+        mut content_filter,
+        initial_search,
+        dedupe,
+        notify_mode,
+        fetch_delay,
+        fetch_concurrency,
+        fetch_all_max,
+        rate_limit_max_wait,
+        dry_run,
+        create_defaults,
+        mouse: _,
+        theme,
+        refresh_interval,
+        saved_state,
+        state_path,
+        cache_path,
+    } = options;
+    let saved_state = saved_state.unwrap_or_default();
     let mut all_notes: Vec<Note> = Vec::new(); // Complete cache
+    let mut note_order = NoteOrder {
+        key: saved_state.sort_key,
+        descending: saved_state.descending,
+        ..NoteOrder::default()
+    };
     let mut cache_size_bytes: usize = 0; // Track cache size in bytes
     let cache_limit_bytes = (cache_limit_mb as usize) * 1024 * 1024; // Convert MB to bytes
     let mut error_msg: Option<String> = None;
-    let mut total_fetched = 0;
     let mut input_mode = InputMode::Normal;
-    let mut search_query = String::new();
-    let mut search_offset = 0; // Separate offset for search results pagination
-    let mut is_fetching_all = false;
+    // `--contains` wins over the search saved last time
+    let (mut search_query, mut search_scope) = initial_search
+        .or_else(|| {
+            (!saved_state.search_query.is_empty())
+                .then(|| (saved_state.search_query.clone(), saved_state.search_scope))
+        })
+        .unwrap_or_default();
+    // Highlighted row on the current page, and the note opened with Enter
+    let mut selected: usize = 0;
+    let mut detail: Option<DetailView> = None;
+    // Note awaiting delete confirmation
+    let mut pending_delete: Option<Note> = None;
+    // Contents of the create note form, while `InputMode::Create`
+    let mut create_form = CreateForm::default();
+    let mut clipboard = ClipboardWriter::default();
+    // Footer confirmation and when it was shown
+    let mut flash: Option<(String, Instant)> = None;
+    // When the first page was last re-fetched; shown once refreshing starts
+    let mut last_refresh: Option<Instant> = None;
+    // Notes the last fetch-all stopped at because of `fetch_all_max`
+    let mut fetch_capped: Option<usize> = None;
+    // ID of the note chosen with Enter when picking
+    let mut picked: Option<String> = None;
 
     // Calculate initial limit based on terminal size
     // Overhead: 3 (help block) + 2 (table borders) + 1 (table header) = 6 lines
-    let calculate_limit = |terminal: &mut Terminal<CrosstermBackend<io::Stdout>>| -> u32 {
+    let calculate_limit = |terminal: &mut Screen| -> u32 {
         let size = terminal.size().unwrap_or_default();
         let height = size.height.saturating_sub(7) as u32;
         // Cap limit at 50. Attio's notes endpoint seems to have a lower limit than 100.
         let val = height.clamp(1, 50);
-        log_debug(&format!(
+        log::debug!(
             "Calculated limit: {} (Terminal height: {})",
-            val, size.height
-        ));
+            val,
+            size.height
+        );
         val
     };
 
-    let mut limit = calculate_limit(terminal);
+    let mut pagination = Pagination::new(calculate_limit(terminal));
+    pagination.cached = cache_enabled;
+
+    // Duplicates the API returned; only tracked when deduplication is off
+    let mut duplicates_seen: Option<usize> = (!dedupe).then_some(0);
 
-    // Helper to add notes to cache with deduplication and size limit
+    // Recency of cached notes; the least recently used are evicted first
+    let mut access_log = cache::AccessLog::new();
+
+    // Helper to add notes to cache with deduplication and LRU eviction
     // Returns (added_count, limit_reached)
     let add_to_cache = |cache: &mut Vec<Note>,
                         cache_size: &mut usize,
+                        duplicates_seen: &mut Option<usize>,
+                        access_log: &mut cache::AccessLog,
+                        pagination: &mut Pagination,
+                        note_order: &mut NoteOrder,
                         new_notes: Vec<Note>,
                         limit: usize|
      -> (usize, bool) {
-        let mut added = 0;
-        let mut limit_reached = false;
-        for note in new_notes {
-            // Only add if not already in cache
-            if !cache.iter().any(|n| n.id.note_id == note.id.note_id) {
-                let note_size = cache::estimate_note_size(&note);
-                // Check if adding this note would exceed the limit
-                if *cache_size + note_size <= limit {
-                    *cache_size += note_size;
-                    cache.push(note);
-                    added += 1;
-                } else {
-                    // Cache limit reached, stop adding
-                    log_debug(&format!(
-                        "Cache limit reached: {} bytes / {} bytes",
-                        *cache_size, limit
-                    ));
-                    limit_reached = true;
-                    break;
-                }
+        let anchor = cache
+            .get(pagination.offset as usize)
+            .map(|note| note.id.note_id.clone());
+        let admission = cache::admit_notes(cache, cache_size, new_notes, limit, dedupe, access_log);
+        note_order.record_arrivals(cache);
+        note_order.forget_missing(cache);
+        note_order.apply(cache);
+        if admission.evicted > 0 {
+            let anchor = anchor.and_then(|id| cache.iter().position(|n| n.id.note_id == id));
+            pagination.notes_evicted(admission.evicted, anchor);
+            log::info!(
+                "Evicted {} least recently used notes ({} total)",
+                admission.evicted,
+                pagination.evicted
+            );
+        }
+        if let Some(seen) = duplicates_seen.as_mut() {
+            *seen += admission.duplicates;
+            if admission.duplicates > 0 {
+                log::debug!(
+                    "API returned {} duplicate notes ({} total)",
+                    admission.duplicates,
+                    seen
+                );
             }
         }
-        (added, limit_reached)
+        if admission.limit_reached {
+            log::warn!(
+                "Skipped a note larger than the cache limit ({} bytes)",
+                limit
+            );
+        }
+        (admission.added, admission.limit_reached)
+    };
+
+    // With the cache off, each fetched page replaces the one held before
+    let replace_page = |cache: &mut Vec<Note>,
+                        cache_size: &mut usize,
+                        pagination: &mut Pagination,
+                        note_order: &mut NoteOrder,
+                        api_offset: usize,
+                        notes: Vec<Note>| {
+        *cache_size = notes.iter().map(cache::estimate_note_size).sum();
+        *cache = notes;
+        note_order.record_arrivals(cache);
+        note_order.forget_missing(cache);
+        note_order.apply(cache);
+        pagination.page_replaced(api_offset, cache.len());
     };
 
     // Helper for rendering
-    let draw_screen = |terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    let draw_screen = |terminal: &mut Screen,
                        all_notes: &[Note],
                        error_msg: &Option<String>,
-                       offset: u32,
-                       search_offset: u32,
-                       limit: u32,
-                       _total_fetched: usize,
+                       pagination: &Pagination,
                        loading: bool,
                        search_query: &str,
+                       search_scope: SearchScope,
+                       content_filter: ContentFilter,
+                       note_order: &NoteOrder,
                        input_mode: &InputMode,
-                       is_fetching_all: bool,
+                       fetch_progress: Option<&FetchProgress>,
+                       fetch_capped: Option<usize>,
                        cache_size_bytes: usize,
-                       cache_limit_bytes: usize|
+                       cache_limit_bytes: usize,
+                       duplicates_seen: Option<usize>,
+                       selected: usize,
+                       detail: Option<&DetailView>,
+                       confirm_delete: Option<&Note>,
+                       create_form: &CreateForm,
+                       last_refresh: Option<Instant>,
+                       flash: Option<&str>|
      -> Result<(), io::Error> {
         // Calculate cache usage
         let cache_mb = cache_size_bytes as f64 / (1024.0 * 1024.0);
         let limit_mb = cache_limit_bytes as f64 / (1024.0 * 1024.0);
-        let usage_percent = (cache_size_bytes as f64 / cache_limit_bytes as f64) * 100.0;
-
-        // Color code based on usage
-        let cache_color = if usage_percent < 70.0 {
-            Color::Green
-        } else if usage_percent < 90.0 {
-            Color::Yellow
+        let usage_percent = if pagination.cached {
+            (cache_size_bytes as f64 / cache_limit_bytes as f64) * 100.0
         } else {
-            Color::Red
+            0.0
         };
+
+        // Color code based on usage
+        let cache_color = theme.cache_color(usage_percent);
         // In search mode, filter all cached notes and paginate through results
         // In normal mode, show a slice of cached notes based on offset
-        let (display_notes, current_page, total_matches): (Vec<&Note>, u32, Option<usize>) =
-            if !search_query.is_empty() {
-                // Search mode: filter all notes and paginate through filtered results
-                let query_lower = search_query.to_lowercase();
-                let mut filtered: Vec<&Note> = all_notes
-                    .iter()
-                    .filter(|note| {
-                        note.title.to_lowercase().contains(&query_lower)
-                            || note.content_plaintext.to_lowercase().contains(&query_lower)
-                    })
-                    .collect();
-
-                let total = filtered.len();
-                let page = (search_offset / limit.max(1)) + 1;
-
-                // Paginate filtered results
-                let start = search_offset as usize;
-                let end = (start + limit as usize).min(filtered.len());
-                filtered = filtered[start..end].to_vec();
-
-                (filtered, page, Some(total))
-            } else {
-                // Normal mode: show slice of cached notes
-                let start = offset as usize;
-                let end = (start + limit as usize).min(all_notes.len());
-                let slice: Vec<&Note> = all_notes[start..end].iter().collect();
-                let page = (offset / limit.max(1)) + 1;
-
-                (slice, page, None)
-            };
+        let filtered = !search_query.is_empty() || content_filter != ContentFilter::All;
+        let current_page = pagination.page_number(filtered);
+        let (display_notes, total_matches) = visible_notes(
+            all_notes,
+            search_query,
+            search_scope,
+            content_filter,
+            pagination,
+        );
 
         terminal.draw(|f| {
-            // Dynamic layout based on search mode
-            let chunks = if input_mode == &InputMode::Search || !search_query.is_empty() {
-                Layout::default()
+            if let Some(view) = detail {
+                let chunks = Layout::default()
                     .direction(Direction::Vertical)
-                    .constraints([
-                        Constraint::Length(3), // Search box
-                        Constraint::Min(0),    // Notes table
-                        Constraint::Length(3), // Help footer
-                    ])
-                    .split(f.area())
-            } else {
-                Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([
-                        Constraint::Min(0),    // Notes table
-                        Constraint::Length(3), // Help footer
-                    ])
-                    .split(f.area())
-            };
-
-            let (table_chunk, help_chunk) =
-                if input_mode == &InputMode::Search || !search_query.is_empty() {
-                    // Render search box
-                    let search_text = if input_mode == &InputMode::Search {
-                        format!("🔍 {}_", search_query) // Show cursor
-                    } else {
-                        format!("🔍 {} (Press / to search again)", search_query)
-                    };
+                    .constraints([Constraint::Min(0), Constraint::Length(3)])
+                    .split(f.area());
+                // Already wrapped to the inner width, so no Paragraph wrapping
+                let body = Paragraph::new(view.lines(chunks[0].width.saturating_sub(2), &theme))
+                    .scroll((view.scroll, 0))
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(format!(" {} ", view.note.title))
+                            .title_style(Style::default().add_modifier(Modifier::BOLD))
+                            .border_style(Style::default().fg(theme.border)),
+                    );
+                f.render_widget(body, chunks[0]);
 
-                    let search_style = if input_mode == &InputMode::Search {
+                let footer = Line::from(vec![
+                    Span::styled(
+                        " j/k ↑/↓ ",
                         Style::default()
-                            .fg(Color::Green)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(Color::Yellow)
-                    };
-
-                    let search_widget = Paragraph::new(search_text).block(
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Scroll  "),
+                    Span::styled(
+                        " PgUp/PgDn ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Page  "),
+                    Span::styled(
+                        " [Esc] ",
+                        Style::default()
+                            .fg(theme.danger)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Back to list"),
+                ]);
+                f.render_widget(
+                    Paragraph::new(footer).block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .title(" Search ")
-                            .style(search_style),
-                    );
-                    f.render_widget(search_widget, chunks[0]);
-                    (chunks[1], chunks[2])
+                            .title(" Controls ")
+                            .border_style(Style::default().fg(theme.border)),
+                    ),
+                    chunks[1],
+                );
+                return;
+            }
+
+            let (search_chunk, table_chunk, help_chunk) = list_layout(
+                f.area(),
+                input_mode == &InputMode::Search || !search_query.is_empty(),
+            );
+            if let Some(search_chunk) = search_chunk {
+                // Render search box
+                let search_text = if input_mode == &InputMode::Search {
+                    format!("🔍 {}_", search_query) // Show cursor
+                } else {
+                    format!("🔍 {} (Press / to search again)", search_query)
+                };
+
+                let search_style = if input_mode == &InputMode::Search {
+                    Style::default()
+                        .fg(theme.search)
+                        .add_modifier(Modifier::BOLD)
                 } else {
-                    (chunks[0], chunks[1])
+                    Style::default().fg(theme.search_idle)
                 };
 
-            let cache_info = format!("{:.1}MB / {:.0}MB", cache_mb, limit_mb);
+                let search_widget = Paragraph::new(search_text).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(if pagination.cached {
+                            format!(" Search ({}) ", search_scope.label())
+                        } else {
+                            format!(" Search ({}, this page only) ", search_scope.label())
+                        })
+                        .style(search_style),
+                );
+                f.render_widget(search_widget, search_chunk);
+            }
+
+            let duplicates_info = duplicates_seen
+                .map(|n| format!(", {} duplicates", n))
+                .unwrap_or_default();
+            let (cache_info, held) = if pagination.cached {
+                (
+                    format!("{:.1}MB / {:.0}MB{}", cache_mb, limit_mb, duplicates_info),
+                    "cached",
+                )
+            } else {
+                ("off".to_string(), "on this page")
+            };
+            let filter_info = content_filter
+                .label()
+                .map(|label| format!(" [{}]", label))
+                .unwrap_or_default();
+            let refresh_info = last_refresh
+                .map(|at| format!(" | {}", refreshed_label(at.elapsed())))
+                .unwrap_or_default();
 
             let title_text = if let Some(total) = total_matches {
                 format!(
-                    " Notes - {} matches from {} cached | Cache: {} (Page {}) ",
+                    " Notes - {} matches{} from {} {} | Sort: {} | Cache: {} (Page {}){} ",
                     total,
+                    filter_info,
                     all_notes.len(),
+                    held,
+                    note_order.label(),
                     cache_info,
-                    current_page
+                    current_page,
+                    refresh_info
                 )
-            } else if is_fetching_all {
+            } else if let Some(progress) = fetch_progress {
                 format!(
-                    " Notes - Fetching all... ({} cached) | Cache: {} ",
-                    all_notes.len(),
+                    " Notes - {} | Sort: {} | Cache: {} ",
+                    progress.label(all_notes.len(), FETCH_ALL_PAGE_SIZE),
+                    note_order.label(),
                     cache_info
                 )
             } else {
+                let capped_info = fetch_capped
+                    .map(|count| format!(" | Fetched first {} of possibly more", count))
+                    .unwrap_or_default();
                 format!(
-                    " Notes - {} cached | Cache: {} (Page {}) ",
+                    " Notes - {} {}{} | Sort: {} | Cache: {} (Page {}){} ",
                     all_notes.len(),
+                    held,
+                    capped_info,
+                    note_order.label(),
                     cache_info,
-                    current_page
+                    current_page,
+                    refresh_info
                 )
             };
 
             if loading {
                 f.render_widget(
-                    Paragraph::new("Loading notes...")
-                        .block(Block::default().borders(Borders::ALL).title(" Status ")),
+                    Paragraph::new("Loading notes...").block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(" Status ")
+                            .border_style(Style::default().fg(theme.border)),
+                    ),
                     table_chunk,
                 );
             } else if let Some(msg) = error_msg {
@@ -277,7 +1286,7 @@ async fn run_app(
                         Block::default()
                             .borders(Borders::ALL)
                             .title(" Error ")
-                            .style(Style::default().fg(Color::Red)),
+                            .style(Style::default().fg(theme.danger)),
                     ),
                     table_chunk,
                 );
@@ -305,10 +1314,11 @@ async fn run_app(
                         Constraint::Fill(1), // Use remaining space
                     ],
                 )
+                .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
                 .header(
                     Row::new(vec!["ID", "Title", "Content"]).style(
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme.header)
                             .add_modifier(Modifier::BOLD),
                     ),
                 )
@@ -320,72 +1330,243 @@ async fn run_app(
                         .border_style(Style::default().fg(cache_color)),
                 );
 
-                f.render_widget(table, table_chunk);
+                let mut table_state = TableState::default().with_selected(
+                    (!display_notes.is_empty()).then(|| selected.min(display_notes.len() - 1)),
+                );
+                f.render_stateful_widget(table, table_chunk, &mut table_state);
+            }
+
+            if let Some(note) = confirm_delete {
+                let area = centered_rect(60, 5, f.area());
+                let prompt = Paragraph::new(vec![
+                    Line::from(format!("Delete \"{}\"?", note.title)),
+                    Line::from(vec![
+                        Span::styled(
+                            " [Y] ",
+                            Style::default()
+                                .fg(theme.danger)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw("Delete  "),
+                        Span::styled(
+                            " [Any other key] ",
+                            Style::default()
+                                .fg(theme.footer)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw("Cancel"),
+                    ]),
+                ])
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Confirm Delete ")
+                        .border_style(Style::default().fg(theme.danger)),
+                );
+                f.render_widget(Clear, area);
+                f.render_widget(prompt, area);
+            }
+
+            if input_mode == &InputMode::Create {
+                let lines = create_form.lines(&theme);
+                let area = centered_rect(70, lines.len() as u16 + 2, f.area());
+                let form = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" New Note ")
+                        .border_style(Style::default().fg(theme.success)),
+                );
+                f.render_widget(Clear, area);
+                f.render_widget(form, area);
             }
 
             // Footer with arrows and page info
-            let footer_content = if input_mode == &InputMode::Search {
+            let footer_content = if let Some(message) = flash {
+                Line::from(Span::styled(
+                    format!(" {} ", message),
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else if input_mode == &InputMode::Create {
+                Line::from(vec![
+                    Span::styled(
+                        " [Tab/Shift+Tab] ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Next/Prev Field  "),
+                    Span::styled(
+                        " [Enter] ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Next Field (New Line in Content)  "),
+                    Span::styled(
+                        " [Ctrl+S] ",
+                        Style::default()
+                            .fg(theme.success)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Create  "),
+                    Span::styled(
+                        " [Esc] ",
+                        Style::default()
+                            .fg(theme.danger)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Cancel"),
+                ])
+            } else if input_mode == &InputMode::Search {
                 Line::from(vec![
                     Span::styled(
                         " Type ",
                         Style::default()
-                            .fg(Color::Green)
+                            .fg(theme.success)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("to search  "),
                     Span::styled(
                         " Backspace ",
                         Style::default()
-                            .fg(Color::Yellow)
+                            .fg(theme.warning)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("to delete  "),
+                    Span::styled(
+                        " [Tab] ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Scope  "),
                     Span::styled(
                         " [Esc] ",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        Style::default()
+                            .fg(theme.danger)
+                            .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("Exit search"),
                 ])
             } else {
                 Line::from(vec![
                     Span::styled(
-                        " ← ",
+                        " ←/h ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme.footer)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("Prev  "),
                     Span::styled(
-                        " → ",
+                        " →/l ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme.footer)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("Next  "),
+                    Span::styled(
+                        " g/G ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("First/Last  "),
+                    Span::styled(
+                        " n/N ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Next/Prev Match  "),
+                    Span::styled(
+                        " ↑/↓ ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Select  "),
+                    Span::styled(
+                        " [Enter] ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(if pick { "Pick  " } else { "Open  " }),
+                    Span::styled(
+                        " [y/Y] ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Copy ID/Content  "),
+                    Span::styled(
+                        " [D] ",
+                        Style::default()
+                            .fg(theme.danger)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Delete  "),
+                    Span::styled(
+                        " [c] ",
+                        Style::default()
+                            .fg(theme.success)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("New Note  "),
+                    Span::styled(
+                        " [s/r] ",
+                        Style::default()
+                            .fg(theme.footer)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Sort/Reverse  "),
                     Span::styled(
                         " [/] ",
                         Style::default()
-                            .fg(Color::Green)
+                            .fg(theme.success)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("Search  "),
+                    Span::styled(
+                        " [E] ",
+                        Style::default()
+                            .fg(theme.warning)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Empty Filter  "),
                     Span::styled(
                         " [Ctrl+A] ",
                         Style::default()
-                            .fg(Color::Magenta)
+                            .fg(theme.highlight)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("Fetch All  "),
+                    Span::styled(
+                        " [R] ",
+                        Style::default()
+                            .fg(theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Refresh  "),
                     Span::styled(
                         " [Q] ",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        Style::default()
+                            .fg(theme.danger)
+                            .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("Quit"),
                 ])
             };
 
-            let help = Paragraph::new(footer_content)
-                .block(Block::default().borders(Borders::ALL).title(" Controls "));
+            let help = Paragraph::new(footer_content).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Controls ")
+                    .border_style(Style::default().fg(theme.border)),
+            );
             f.render_widget(help, help_chunk);
         })?;
         Ok(())
@@ -396,225 +1577,773 @@ async fn run_app(
         terminal,
         &all_notes,
         &error_msg,
-        offset,
-        search_offset,
-        limit,
-        total_fetched,
+        &pagination,
         true,
         &search_query,
+        search_scope,
+        content_filter,
+        &note_order,
         &input_mode,
-        is_fetching_all,
+        None,
+        fetch_capped,
         cache_size_bytes,
         cache_limit_bytes,
+        duplicates_seen,
+        selected,
+        detail.as_ref(),
+        pending_delete.as_ref(),
+        &create_form,
+        last_refresh,
+        flash_message(&flash),
     )?;
-    match client.list_notes(Some(limit), Some(offset)).await {
-        Ok(resp) => {
-            total_fetched = resp.data.len();
+    // Reopening past the fetch-all cap would mean paging through it all
+    // before the first frame, so that starts on the first page instead
+    let restore_offset = match fetch_all_max {
+        Some(max) if pagination.cached && saved_state.offset >= max => 0,
+        _ => saved_state.offset,
+    };
+    // With the cache off, open the saved page directly
+    let start = if pagination.cached { 0 } else { restore_offset };
+    match client
+        .list_notes(Some(pagination.limit), Some(start as u32))
+        .await
+    {
+        Ok(resp) if pagination.cached => {
+            pagination.page_fetched(pagination.limit, resp.data.len());
             let _ = add_to_cache(
                 &mut all_notes,
                 &mut cache_size_bytes,
+                &mut duplicates_seen,
+                &mut access_log,
+                &mut pagination,
+                &mut note_order,
                 resp.data,
                 cache_limit_bytes,
             );
         }
+        Ok(resp) => replace_page(
+            &mut all_notes,
+            &mut cache_size_bytes,
+            &mut pagination,
+            &mut note_order,
+            start,
+            resp.data,
+        ),
         Err(e) => error_msg = Some(e.to_string()),
     }
+    // With the cache on, fetch up to the saved page and reopen there
+    while pagination.cached
+        && error_msg.is_none()
+        && pagination.has_more
+        && all_notes.len() <= restore_offset
+    {
+        // Go easy on the API's rate limit, as fetch-all does
+        tokio::time::sleep(fetch_delay).await;
+        let api_offset = pagination.fetch_offset(all_notes.len());
+        match client
+            .list_notes(Some(FETCH_ALL_PAGE_SIZE), Some(api_offset))
+            .await
+        {
+            Ok(resp) => {
+                pagination.page_fetched(FETCH_ALL_PAGE_SIZE, resp.data.len());
+                let (added, _) = add_to_cache(
+                    &mut all_notes,
+                    &mut cache_size_bytes,
+                    &mut duplicates_seen,
+                    &mut access_log,
+                    &mut pagination,
+                    &mut note_order,
+                    resp.data,
+                    cache_limit_bytes,
+                );
+                if added == 0 {
+                    break;
+                }
+            }
+            Err(e) => error_msg = Some(e.to_string()),
+        }
+    }
+    if pagination.cached {
+        pagination.last_page(all_notes.len().min(restore_offset + 1), false);
+    }
+    if refresh_interval.is_some() {
+        last_refresh = Some(Instant::now());
+    }
 
     loop {
+        if detail.is_none() {
+            let (visible, _) = visible_notes(
+                &all_notes,
+                &search_query,
+                search_scope,
+                content_filter,
+                &pagination,
+            );
+            for note in visible {
+                access_log.touch(&note.id.note_id);
+            }
+        }
         draw_screen(
             terminal,
             &all_notes,
             &error_msg,
-            offset,
-            search_offset,
-            limit,
-            total_fetched,
+            &pagination,
             false,
             &search_query,
+            search_scope,
+            content_filter,
+            &note_order,
             &input_mode,
-            is_fetching_all,
+            None,
+            fetch_capped,
             cache_size_bytes,
             cache_limit_bytes,
+            duplicates_seen,
+            selected,
+            detail.as_ref(),
+            pending_delete.as_ref(),
+            &create_form,
+            last_refresh,
+            flash_message(&flash),
         )?;
 
+        // With the cache off: API offset of a page to fetch in place of this one
+        let mut fetch_page_at: Option<usize> = None;
+        let mut refresh_now = false;
         if event::poll(std::time::Duration::from_millis(200))? {
-            match event::read()? {
-                Event::Resize(_, _) => {
-                    limit = calculate_limit(terminal);
-                    // No need to re-fetch, just re-render with new limit
+            let event = match event::read()? {
+                // The scroll wheel acts like the arrow keys, but not while a
+                // prompt or the create form would take it as input
+                Event::Mouse(mouse)
+                    if pending_delete.is_none() && input_mode != InputMode::Create =>
+                {
+                    scroll_key(mouse.kind, detail.is_some())
+                        .map_or(Event::Mouse(mouse), |code| Event::Key(code.into()))
+                }
+                event => event,
+            };
+            match event {
+                Event::Mouse(mouse)
+                    if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                        && detail.is_none()
+                        && pending_delete.is_none()
+                        && input_mode != InputMode::Create =>
+                {
+                    let size = terminal.size()?;
+                    let (_, table, _) = list_layout(
+                        Rect::new(0, 0, size.width, size.height),
+                        input_mode == InputMode::Search || !search_query.is_empty(),
+                    );
+                    let (visible, _) = visible_notes(
+                        &all_notes,
+                        &search_query,
+                        search_scope,
+                        content_filter,
+                        &pagination,
+                    );
+                    if let Some(row) = clicked_row(table, mouse.column, mouse.row)
+                        && row < visible.len()
+                    {
+                        selected = row;
+                    }
+                }
+                Event::Resize(width, _) => {
+                    let filtered = !search_query.is_empty() || content_filter != ContentFilter::All;
+                    let len = if filtered {
+                        filter_notes(&all_notes, &search_query, search_scope, content_filter).len()
+                    } else {
+                        all_notes.len()
+                    };
+                    selected =
+                        pagination.resize(calculate_limit(terminal), selected, len, filtered);
+                    terminal.clear()?;
+                    // Rewrapping changes the line count; keep the scroll in range
+                    if let Some(view) = detail.as_mut() {
+                        let line_count = view.lines(width.saturating_sub(2), &theme).len();
+                        view.scroll_by(0, line_count);
+                    }
+                }
+                Event::Key(key) if pending_delete.is_some() => {
+                    if let Some(note) = pending_delete.take()
+                        && matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y'))
+                    {
+                        if dry_run {
+                            let request = client.delete_note_request(&note.id.note_id);
+                            match DryRunRequest::prepare(request) {
+                                Ok(prepared) => {
+                                    flash = Some((
+                                        format!("Dry run, not sent: {}", prepared),
+                                        Instant::now(),
+                                    ))
+                                }
+                                Err(e) => error_msg = Some(e.to_string()),
+                            }
+                        } else {
+                            match client.delete_note(&note.id.note_id).await {
+                                Ok(()) => {
+                                    if let Some(pos) = all_notes
+                                        .iter()
+                                        .position(|n| n.id.note_id == note.id.note_id)
+                                    {
+                                        let removed = all_notes.remove(pos);
+                                        cache_size_bytes = cache_size_bytes
+                                            .saturating_sub(cache::estimate_note_size(&removed));
+                                    }
+                                    pagination.step_back_if_empty(all_notes.len(), false);
+                                    let filtered_count = filter_notes(
+                                        &all_notes,
+                                        &search_query,
+                                        search_scope,
+                                        content_filter,
+                                    )
+                                    .len();
+                                    pagination.step_back_if_empty(filtered_count, true);
+                                    let (visible, _) = visible_notes(
+                                        &all_notes,
+                                        &search_query,
+                                        search_scope,
+                                        content_filter,
+                                        &pagination,
+                                    );
+                                    selected = selected.min(visible.len().saturating_sub(1));
+                                }
+                                Err(e) => error_msg = Some(format!("Failed to delete note: {}", e)),
+                            }
+                        }
+                    }
+                    terminal.clear()?;
+                }
+                Event::Key(key) if detail.is_some() => {
+                    let size = terminal.size()?;
+                    let page = size.height.saturating_sub(5).max(1) as i32;
+                    match key.code {
+                        KeyCode::Esc => {
+                            detail = None;
+                            terminal.clear()?;
+                        }
+                        code => {
+                            if let Some(view) = detail.as_mut() {
+                                let line_count =
+                                    view.lines(size.width.saturating_sub(2), &theme).len();
+                                let delta = match code {
+                                    KeyCode::Char('j') | KeyCode::Down => 1,
+                                    KeyCode::Char('k') | KeyCode::Up => -1,
+                                    KeyCode::PageDown => page,
+                                    KeyCode::PageUp => -page,
+                                    _ => 0,
+                                };
+                                view.scroll_by(delta, line_count);
+                            }
+                        }
+                    }
+                }
+                Event::Key(key) if input_mode == InputMode::Create => {
+                    match create_form.handle_key(key) {
+                        FormAction::None => {}
+                        FormAction::Cancel => {
+                            input_mode = InputMode::Normal;
+                            terminal.clear()?;
+                        }
+                        FormAction::Submit => {
+                            if let Some(data) = create_form.validate() {
+                                let request = CreateRequest { data };
+                                if dry_run {
+                                    match DryRunRequest::prepare(
+                                        client.create_note_request(&request),
+                                    ) {
+                                        Ok(prepared) => {
+                                            flash = Some((
+                                                format!("Dry run, not sent: {}", prepared),
+                                                Instant::now(),
+                                            ));
+                                            input_mode = InputMode::Normal;
+                                        }
+                                        Err(e) => error_msg = Some(e.to_string()),
+                                    }
+                                } else {
+                                    match client.create_note(request).await {
+                                        Ok(response) => {
+                                            let note = response.data;
+                                            cache_size_bytes += cache::estimate_note_size(&note);
+                                            note_order.record_first(&note);
+                                            all_notes.insert(0, note);
+                                            note_order.apply(&mut all_notes);
+                                            pagination.first_page(false);
+                                            selected = 0;
+                                            error_msg = None;
+                                            flash =
+                                                Some(("Note created!".to_string(), Instant::now()));
+                                            input_mode = InputMode::Normal;
+                                        }
+                                        // The form stays open so the note can be fixed and resent
+                                        Err(e) => {
+                                            error_msg =
+                                                Some(format!("Failed to create note: {}", e))
+                                        }
+                                    }
+                                }
+                            }
+                            terminal.clear()?;
+                        }
+                    }
                 }
-                Event::Key(key) => match key.code {
-                    KeyCode::Char('q') if input_mode == InputMode::Normal => return Ok(()),
+                Event::Key(key) => match vim_alias(key.code, &input_mode) {
+                    KeyCode::Char('q') if input_mode == InputMode::Normal => break,
                     KeyCode::Esc => {
                         if input_mode == InputMode::Search {
                             input_mode = InputMode::Normal;
                             search_query.clear();
-                            search_offset = 0;
+                            pagination.reset_search();
                         } else {
-                            return Ok(());
+                            break;
                         }
                     }
                     KeyCode::Char('/') if input_mode == InputMode::Normal => {
                         input_mode = InputMode::Search;
-                        search_offset = 0;
+                        pagination.reset_search();
+                    }
+                    KeyCode::Char('a')
+                        if input_mode == InputMode::Normal
+                            && key.modifiers.contains(event::KeyModifiers::CONTROL)
+                            && !pagination.cached =>
+                    {
+                        flash = Some((
+                            "Fetch all needs the cache: attio config set cache-enabled true"
+                                .to_string(),
+                            Instant::now(),
+                        ));
                     }
                     KeyCode::Char('a')
                         if input_mode == InputMode::Normal
                             && key.modifiers.contains(event::KeyModifiers::CONTROL) =>
                     {
                         // Fetch all notes
-                        is_fetching_all = true;
-                        let mut fetch_offset = 0u32;
-                        let fetch_limit = 50u32; // Attio's API has a max limit around 50
+                        let mut progress = FetchProgress::default();
+                        fetch_capped = None;
+                        let mut rate_limit_waits = 0;
+                        let mut page_token = PageToken::Offset(0);
+                        // Periodically persist progress so an interrupted fetch isn't lost.
+                        // One record's notes would replace the whole workspace's in the
+                        // cache, so narrowed listings aren't saved.
+                        let mut autosaver = cache_path
+                            .clone()
+                            .filter(|_| !client.has_note_parent())
+                            .map(|path| cache::AutoSaver::new(path, cache_limit_bytes));
 
-                        loop {
-                            draw_screen(
-                                terminal,
-                                &all_notes,
-                                &error_msg,
-                                offset,
-                                search_offset,
-                                limit,
-                                total_fetched,
-                                false,
-                                &search_query,
-                                &input_mode,
-                                is_fetching_all,
-                                cache_size_bytes,
-                                cache_limit_bytes,
-                            )?;
-
-                            match client
-                                .list_notes(Some(fetch_limit), Some(fetch_offset))
-                                .await
-                            {
-                                Ok(resp) => {
-                                    let fetched = resp.data.len();
-                                    let (_added, limit_reached) = add_to_cache(
-                                        &mut all_notes,
-                                        &mut cache_size_bytes,
-                                        resp.data,
-                                        cache_limit_bytes,
-                                    );
+                        // Recreated from the page that failed after a rate-limit wait.
+                        // Ends with the reason the fetch stopped early, if it did.
+                        let stopped = loop {
+                            let mut pages = std::pin::pin!(client.note_pages(
+                                page_token.clone(),
+                                FETCH_ALL_PAGE_SIZE,
+                                fetch_concurrency,
+                                fetch_delay,
+                            ));
+                            let failure = loop {
+                                draw_screen(
+                                    terminal,
+                                    &all_notes,
+                                    &error_msg,
+                                    &pagination,
+                                    false,
+                                    &search_query,
+                                    search_scope,
+                                    content_filter,
+                                    &note_order,
+                                    &input_mode,
+                                    Some(&progress),
+                                    fetch_capped,
+                                    cache_size_bytes,
+                                    cache_limit_bytes,
+                                    duplicates_seen,
+                                    selected,
+                                    detail.as_ref(),
+                                    pending_delete.as_ref(),
+                                    &create_form,
+                                    last_refresh,
+                                    flash_message(&flash),
+                                )?;
 
-                                    if limit_reached {
-                                        // Cache limit reached
-                                        error_msg = Some(format!(
-                                            "Cache limit reached ({:.1}MB / {:.0}MB). Stopped fetching.",
-                                            cache_size_bytes as f64 / (1024.0 * 1024.0),
-                                            cache_limit_bytes as f64 / (1024.0 * 1024.0)
-                                        ));
-                                        break;
-                                    }
-                                    if fetched < fetch_limit as usize {
+                                let page = match pages.next().await {
+                                    Some(Ok(page)) => page,
+                                    Some(Err(e)) => break Some(e),
+                                    None => break None,
+                                };
+                                rate_limit_waits = 0;
+                                progress.pages += 1;
+                                progress.total = progress.total.or(page.total_count);
+                                let mut notes = page.notes;
+                                let capped = progress.count_page(
+                                    &mut notes,
+                                    fetch_all_max,
+                                    page.next.is_some(),
+                                );
+                                let (_added, limit_reached) = add_to_cache(
+                                    &mut all_notes,
+                                    &mut cache_size_bytes,
+                                    &mut duplicates_seen,
+                                    &mut access_log,
+                                    &mut pagination,
+                                    &mut note_order,
+                                    notes,
+                                    cache_limit_bytes,
+                                );
+
+                                if let Some(saver) = autosaver.as_mut()
+                                    && let Err(e) = saver.page_fetched(&all_notes)
+                                {
+                                    log::warn!("Cache auto-save failed: {}", e);
+                                }
+
+                                if limit_reached {
+                                    error_msg = Some(format!(
+                                        "Skipped a note larger than the whole cache limit ({:.0}MB).",
+                                        cache_limit_bytes as f64 / (1024.0 * 1024.0)
+                                    ));
+                                }
+                                if capped {
+                                    fetch_capped = Some(progress.notes);
+                                    break None;
+                                }
+                                // Continue fetching even if added == 0 (all duplicates), as long as there is a next page
+                                match page.next {
+                                    Some(next) => page_token = next,
+                                    None => {
                                         // No more notes to fetch
-                                        break;
+                                        pagination.has_more = false;
+                                        break None;
                                     }
-                                    // Continue fetching even if added == 0 (all duplicates), as long as we got a full page
-                                    fetch_offset += fetch_limit;
                                 }
-                                Err(e) => {
-                                    error_msg = Some(format!("Error fetching all: {}", e));
+                            };
+
+                            let Some(e) = failure else {
+                                break None;
+                            };
+                            let wait = e
+                                .downcast_ref::<AttioError>()
+                                .filter(|e| e.is_rate_limited())
+                                .map(|e| e.retry_after().unwrap_or(RATE_LIMIT_WAIT));
+                            let Some(mut wait) =
+                                wait.filter(|_| rate_limit_waits < MAX_RATE_LIMIT_WAITS)
+                            else {
+                                error_msg = Some(format!("Error fetching all: {}", e));
+                                break error_msg.clone();
+                            };
+                            // Wait out the rate limit, then retry from the failed page
+                            wait = wait.min(rate_limit_max_wait);
+                            rate_limit_waits += 1;
+                            log::info!("Rate limited, waiting {:?}", wait);
+                            let mut cancelled = false;
+                            while !wait.is_zero() {
+                                progress.rate_limited_secs = Some(wait.as_secs_f64().ceil() as u64);
+                                draw_screen(
+                                    terminal,
+                                    &all_notes,
+                                    &error_msg,
+                                    &pagination,
+                                    false,
+                                    &search_query,
+                                    search_scope,
+                                    content_filter,
+                                    &note_order,
+                                    &input_mode,
+                                    Some(&progress),
+                                    fetch_capped,
+                                    cache_size_bytes,
+                                    cache_limit_bytes,
+                                    duplicates_seen,
+                                    selected,
+                                    detail.as_ref(),
+                                    pending_delete.as_ref(),
+                                    &create_form,
+                                    last_refresh,
+                                    flash_message(&flash),
+                                )?;
+                                // q or Esc gives up on the fetch, keeping what it has
+                                let step_started = Instant::now();
+                                if event::poll(wait.min(Duration::from_secs(1)))?
+                                    && is_cancel_key(&event::read()?)
+                                {
+                                    cancelled = true;
                                     break;
                                 }
+                                wait = wait.saturating_sub(step_started.elapsed());
                             }
+                            progress.rate_limited_secs = None;
+                            if cancelled {
+                                error_msg = Some(format!(
+                                    "Fetch all cancelled after {} notes.",
+                                    progress.notes
+                                ));
+                                break error_msg.clone();
+                            }
+                        };
+
+                        if let Some(saver) = autosaver.as_mut()
+                            && let Err(e) = saver.flush(&all_notes)
+                        {
+                            log::warn!("Cache save failed: {}", e);
                         }
 
-                        is_fetching_all = false;
+                        let (title, body) = match stopped {
+                            Some(reason) => ("Attio fetch-all stopped", reason),
+                            None => (
+                                "Attio fetch-all finished",
+                                format!("{} notes cached.", all_notes.len()),
+                            ),
+                        };
+                        tokio::task::spawn_blocking(move || notify_mode.notify(title, &body));
+                    }
+                    KeyCode::Up => selected = selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        let (visible, _) = visible_notes(
+                            &all_notes,
+                            &search_query,
+                            search_scope,
+                            content_filter,
+                            &pagination,
+                        );
+                        if selected + 1 < visible.len() {
+                            selected += 1;
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let (visible, _) = visible_notes(
+                            &all_notes,
+                            &search_query,
+                            search_scope,
+                            content_filter,
+                            &pagination,
+                        );
+                        if let Some(note) = visible.get(selected) {
+                            if pick {
+                                picked = Some(note.id.note_id.clone());
+                                break;
+                            }
+                            detail = Some(DetailView::new((*note).clone()));
+                            terminal.clear()?;
+                        }
+                    }
+                    KeyCode::Char(c @ ('y' | 'Y')) if input_mode == InputMode::Normal => {
+                        let (visible, _) = visible_notes(
+                            &all_notes,
+                            &search_query,
+                            search_scope,
+                            content_filter,
+                            &pagination,
+                        );
+                        if let Some(note) = visible.get(selected) {
+                            let (text, what) = if c == 'y' {
+                                (&note.id.note_id, "note ID")
+                            } else {
+                                (&note.content_markdown, "note content")
+                            };
+                            match clipboard.copy(text) {
+                                Ok(()) => {
+                                    flash = Some((format!("Copied {}!", what), Instant::now()))
+                                }
+                                Err(e) => error_msg = Some(e.to_string()),
+                            }
+                        }
+                    }
+                    KeyCode::Char('R') if input_mode == InputMode::Normal => refresh_now = true,
+                    KeyCode::Char('c') if input_mode == InputMode::Normal => {
+                        create_form = CreateForm::new(&create_defaults);
+                        input_mode = InputMode::Create;
+                    }
+                    KeyCode::Char('d') if input_mode == InputMode::Normal => {
+                        let (visible, _) = visible_notes(
+                            &all_notes,
+                            &search_query,
+                            search_scope,
+                            content_filter,
+                            &pagination,
+                        );
+                        pending_delete = visible.get(selected).map(|note| (*note).clone());
+                    }
+                    KeyCode::Char(c @ ('s' | 'r')) if input_mode == InputMode::Normal => {
+                        if c == 's' {
+                            note_order.key = note_order.key.next();
+                        } else {
+                            note_order.descending = !note_order.descending;
+                        }
+                        note_order.apply(&mut all_notes);
+                        // The old page position means nothing in the new order
+                        pagination.first_page(false);
+                        pagination.first_page(true);
+                        selected = 0;
+                        terminal.clear()?;
+                    }
+                    KeyCode::Char(c @ ('g' | 'G'))
+                        if input_mode == InputMode::Normal
+                            && !pagination.cached
+                            && search_query.is_empty()
+                            && content_filter == ContentFilter::All =>
+                    {
+                        if c == 'G' {
+                            flash = Some((
+                                "The last page isn't known with the cache off".to_string(),
+                                Instant::now(),
+                            ));
+                        } else if pagination.evicted > 0 {
+                            fetch_page_at = Some(0);
+                        } else {
+                            pagination.first_page(false);
+                            selected = 0;
+                        }
+                    }
+                    KeyCode::Char('g') if input_mode == InputMode::Normal => {
+                        let filtered =
+                            !search_query.is_empty() || content_filter != ContentFilter::All;
+                        pagination.first_page(filtered);
+                        selected = 0;
+                        terminal.clear()?;
+                    }
+                    KeyCode::Char('G') if input_mode == InputMode::Normal => {
+                        let filtered =
+                            !search_query.is_empty() || content_filter != ContentFilter::All;
+                        let len = if filtered {
+                            filter_notes(&all_notes, &search_query, search_scope, content_filter)
+                                .len()
+                        } else {
+                            all_notes.len()
+                        };
+                        pagination.last_page(len, filtered);
+                        selected = 0;
+                        terminal.clear()?;
+                    }
+                    KeyCode::Char(c @ ('n' | 'N'))
+                        if input_mode == InputMode::Normal
+                            && (!search_query.is_empty()
+                                || content_filter != ContentFilter::All) =>
+                    {
+                        let matches =
+                            filter_notes(&all_notes, &search_query, search_scope, content_filter)
+                                .len();
+                        let before = pagination.search_offset;
+                        selected = pagination.step_match(selected, matches, c == 'n');
+                        if pagination.search_offset != before {
+                            terminal.clear()?; // Clear artifacts when changing pages
+                        }
+                    }
+                    KeyCode::Char('e') if input_mode == InputMode::Normal => {
+                        selected = 0;
+                        content_filter = content_filter.next();
+                        pagination.reset_search();
+                        terminal.clear()?;
+                    }
+                    KeyCode::Tab if input_mode == InputMode::Search => {
+                        search_scope = search_scope.next();
+                        selected = 0;
+                        pagination.reset_search();
                     }
                     KeyCode::Char(c) if input_mode == InputMode::Search => {
                         search_query.push(c);
-                        search_offset = 0; // Reset to first page of results
+                        selected = 0;
+                        pagination.reset_search(); // Reset to first page of results
                     }
                     KeyCode::Backspace if input_mode == InputMode::Search => {
                         search_query.pop();
-                        search_offset = 0; // Reset to first page of results
+                        selected = 0;
+                        pagination.reset_search(); // Reset to first page of results
                     }
                     KeyCode::Right => {
-                        if !search_query.is_empty() {
+                        if !search_query.is_empty() || content_filter != ContentFilter::All {
                             // In search mode: paginate through filtered results
-                            let query_lower = search_query.to_lowercase();
-                            let filtered_count = all_notes
-                                .iter()
-                                .filter(|note| {
-                                    note.title.to_lowercase().contains(&query_lower)
-                                        || note
-                                            .content_plaintext
-                                            .to_lowercase()
-                                            .contains(&query_lower)
-                                })
-                                .count();
-
-                            if search_offset + limit < filtered_count as u32 {
-                                search_offset += limit;
+                            let filtered_count = filter_notes(
+                                &all_notes,
+                                &search_query,
+                                search_scope,
+                                content_filter,
+                            )
+                            .len();
+                            if pagination.next_page(filtered_count, true) {
+                                selected = 0;
                                 terminal.clear()?; // Clear artifacts when changing pages
                             }
+                        } else if input_mode == InputMode::Normal && !pagination.cached {
+                            if pagination.next_page(all_notes.len(), false) {
+                                selected = 0;
+                                terminal.clear()?;
+                            } else if pagination.has_more {
+                                fetch_page_at =
+                                    Some(pagination.fetch_offset(all_notes.len()) as usize);
+                            }
                         } else if input_mode == InputMode::Normal {
-                            // In normal mode: check if we can go forward
-                            let next_offset = offset + limit;
-
-                            if next_offset < all_notes.len() as u32 {
-                                // Already have data in cache, safe to move forward
-                                offset = next_offset;
-                                terminal.clear()?; // Clear artifacts when changing pages
-                            } else if total_fetched == limit as usize {
-                                // Try to fetch more from API
+                            if pagination.needs_fetch(all_notes.len()) {
+                                // Fetch the page after the last cached note
                                 terminal.clear()?;
                                 draw_screen(
                                     terminal,
                                     &all_notes,
                                     &error_msg,
-                                    offset, // Keep current offset during fetch
-                                    search_offset,
-                                    limit,
-                                    total_fetched,
+                                    &pagination,
                                     true,
                                     &search_query,
+                                    search_scope,
+                                    content_filter,
+                                    &note_order,
                                     &input_mode,
-                                    is_fetching_all,
+                                    None,
+                                    fetch_capped,
                                     cache_size_bytes,
                                     cache_limit_bytes,
+                                    duplicates_seen,
+                                    selected,
+                                    detail.as_ref(),
+                                    pending_delete.as_ref(),
+                                    &create_form,
+                                    last_refresh,
+                                    flash_message(&flash),
                                 )?;
-                                match client.list_notes(Some(limit), Some(next_offset)).await {
+                                let fetch_offset = pagination.fetch_offset(all_notes.len());
+                                match client
+                                    .list_notes(Some(pagination.limit), Some(fetch_offset))
+                                    .await
+                                {
                                     Ok(resp) => {
-                                        total_fetched = resp.data.len();
+                                        pagination.page_fetched(pagination.limit, resp.data.len());
                                         let (_added, limit_reached) = add_to_cache(
                                             &mut all_notes,
                                             &mut cache_size_bytes,
+                                            &mut duplicates_seen,
+                                            &mut access_log,
+                                            &mut pagination,
+                                            &mut note_order,
                                             resp.data,
                                             cache_limit_bytes,
                                         );
-
-                                        // Only move forward if we have data at the next offset
-                                        if next_offset < all_notes.len() as u32 {
-                                            offset = next_offset;
-                                            error_msg = None;
-                                            terminal.clear()?;
-                                        } else if limit_reached {
+                                        if limit_reached {
                                             error_msg = Some(
-                                                "Cache limit reached. Not caching new notes."
+                                                "Skipped a note larger than the whole cache limit."
                                                     .to_string(),
                                             );
+                                        } else {
+                                            error_msg = None;
                                         }
-                                        // If total_fetched == 0, we're at the end, don't move
                                     }
                                     Err(e) => error_msg = Some(e.to_string()),
                                 }
                             }
-                            // If neither condition is true, we're at the end - don't move
+
+                            // Only move forward if the next page has data; at the end, stay put
+                            if pagination.next_page(all_notes.len(), false) {
+                                selected = 0;
+                                terminal.clear()?; // Clear artifacts when changing pages
+                            }
                         }
                     }
                     KeyCode::Left => {
-                        if !search_query.is_empty() {
-                            // In search mode: paginate through filtered results
-                            if search_offset > 0 {
-                                search_offset = search_offset.saturating_sub(limit);
-                                terminal.clear()?; // Clear artifacts when changing pages
-                            }
-                        } else if input_mode == InputMode::Normal && offset > 0 {
-                            // In normal mode: just move offset (already in cache)
-                            offset = offset.saturating_sub(limit);
+                        let filtered =
+                            !search_query.is_empty() || content_filter != ContentFilter::All;
+                        if (filtered || input_mode == InputMode::Normal)
+                            && pagination.prev_page(filtered)
+                        {
+                            selected = 0;
                             terminal.clear()?; // Clear artifacts when changing pages
+                        } else if !filtered && input_mode == InputMode::Normal && !pagination.cached
+                        {
+                            fetch_page_at = pagination.prev_fetch_offset();
                         }
                     }
                     _ => {}
@@ -622,5 +2351,800 @@ async fn run_app(
                 _ => {}
             }
         }
+
+        // Pick up notes added since the last fetch, keeping the page,
+        // selection and search as they are
+        let refresh_due = refresh_interval
+            .zip(last_refresh)
+            .is_some_and(|(interval, at)| at.elapsed() >= interval);
+        if (refresh_now || refresh_due) && fetch_page_at.is_none() {
+            let selected_id = visible_notes(
+                &all_notes,
+                &search_query,
+                search_scope,
+                content_filter,
+                &pagination,
+            )
+            .0
+            .get(selected)
+            .map(|note| note.id.note_id.clone());
+            // With the cache off there is only the held page to refresh
+            let api_offset = if pagination.cached {
+                0
+            } else {
+                pagination.evicted
+            };
+            match client
+                .list_notes(Some(pagination.limit), Some(api_offset as u32))
+                .await
+            {
+                Ok(resp) if pagination.cached => {
+                    let fresh = unseen_notes(&all_notes, resp.data);
+                    if !fresh.is_empty() {
+                        log::info!("Refresh found {} new notes", fresh.len());
+                        // Past the first page, stay on the note at the top
+                        let anchor = all_notes
+                            .get(pagination.offset as usize)
+                            .filter(|_| pagination.offset > 0)
+                            .map(|note| note.id.note_id.clone());
+                        note_order.record_newest(&fresh);
+                        let _ = add_to_cache(
+                            &mut all_notes,
+                            &mut cache_size_bytes,
+                            &mut duplicates_seen,
+                            &mut access_log,
+                            &mut pagination,
+                            &mut note_order,
+                            fresh,
+                            cache_limit_bytes,
+                        );
+                        if let Some(pos) =
+                            anchor.and_then(|id| all_notes.iter().position(|n| n.id.note_id == id))
+                        {
+                            pagination.offset = pos as u32;
+                        }
+                    }
+                }
+                Ok(resp) => replace_page(
+                    &mut all_notes,
+                    &mut cache_size_bytes,
+                    &mut pagination,
+                    &mut note_order,
+                    api_offset,
+                    resp.data,
+                ),
+                Err(e) => {
+                    log::warn!("Refresh failed: {}", e);
+                    flash = Some((format!("Refresh failed: {}", e), Instant::now()));
+                }
+            }
+            let (visible, _) = visible_notes(
+                &all_notes,
+                &search_query,
+                search_scope,
+                content_filter,
+                &pagination,
+            );
+            selected = selected_id
+                .and_then(|id| visible.iter().position(|note| note.id.note_id == id))
+                .unwrap_or(selected)
+                .min(visible.len().saturating_sub(1));
+            last_refresh = Some(Instant::now());
+        }
+
+        if let Some(api_offset) = fetch_page_at {
+            terminal.clear()?;
+            draw_screen(
+                terminal,
+                &all_notes,
+                &error_msg,
+                &pagination,
+                true,
+                &search_query,
+                search_scope,
+                content_filter,
+                &note_order,
+                &input_mode,
+                None,
+                fetch_capped,
+                cache_size_bytes,
+                cache_limit_bytes,
+                duplicates_seen,
+                selected,
+                detail.as_ref(),
+                pending_delete.as_ref(),
+                &create_form,
+                last_refresh,
+                flash_message(&flash),
+            )?;
+            match client
+                .list_notes(Some(pagination.limit), Some(api_offset as u32))
+                .await
+            {
+                // Past the last note: stay on the current page
+                Ok(resp) if resp.data.is_empty() && api_offset > pagination.evicted => {
+                    pagination.has_more = false
+                }
+                Ok(resp) => {
+                    replace_page(
+                        &mut all_notes,
+                        &mut cache_size_bytes,
+                        &mut pagination,
+                        &mut note_order,
+                        api_offset,
+                        resp.data,
+                    );
+                    selected = 0;
+                    error_msg = None;
+                }
+                Err(e) => error_msg = Some(e.to_string()),
+            }
+        }
+    }
+
+    // With the cache off the held page starts at `evicted`
+    let before = if pagination.cached {
+        0
+    } else {
+        pagination.evicted
+    };
+    let state = SavedState {
+        offset: before + pagination.offset as usize,
+        search_query,
+        search_scope,
+        sort_key: note_order.key,
+        descending: note_order.descending,
+    };
+    // The picker starts fresh and leaves the list's saved place alone
+    if !pick && let Err(e) = state.save(&state_path) {
+        log::warn!(
+            "Couldn't save the TUI state to {}: {}",
+            state_path.display(),
+            e
+        );
+    }
+    Ok(picked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_terminal_without_setup_is_noop() {
+        // Must not panic or touch terminal modes when setup never ran
+        restore_terminal();
+        restore_terminal();
+        assert!(!TERMINAL_ACTIVE.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_visible_range_clamps_to_available_notes() {
+        let mut pagination = Pagination::new(10);
+        assert_eq!(pagination.visible_range(25, false), 0..10);
+        assert_eq!(pagination.visible_range(4, false), 0..4);
+
+        pagination.offset = 20;
+        assert_eq!(pagination.visible_range(25, false), 20..25);
+        // An offset past the end yields an empty page instead of panicking
+        assert_eq!(pagination.visible_range(15, false), 15..15);
+    }
+
+    #[test]
+    fn test_next_page_stops_at_last_page() {
+        let mut pagination = Pagination::new(10);
+        assert!(pagination.next_page(25, false));
+        assert!(pagination.next_page(25, false));
+        assert_eq!(pagination.offset, 20);
+        assert!(!pagination.next_page(25, false));
+        assert_eq!(pagination.offset, 20);
+
+        // Exactly one full page: there is no second page
+        let mut pagination = Pagination::new(10);
+        assert!(!pagination.next_page(10, false));
+    }
+
+    #[test]
+    fn test_prev_page_stops_at_first_page() {
+        let mut pagination = Pagination::new(10);
+        assert!(!pagination.prev_page(false));
+
+        pagination.offset = 15;
+        assert!(pagination.prev_page(false));
+        assert_eq!(pagination.offset, 5);
+        assert!(pagination.prev_page(false));
+        assert_eq!(pagination.offset, 0);
+    }
+
+    #[test]
+    fn test_search_offset_is_independent() {
+        let mut pagination = Pagination::new(10);
+        pagination.next_page(30, false);
+        assert!(pagination.next_page(30, true));
+        assert_eq!(pagination.offset, 10);
+        assert_eq!(pagination.search_offset, 10);
+        assert_eq!(pagination.page_number(true), 2);
+
+        pagination.reset_search();
+        assert_eq!(pagination.search_offset, 0);
+        assert_eq!(pagination.offset, 10);
+    }
+
+    #[test]
+    fn test_needs_fetch_tracks_last_page_size() {
+        let mut pagination = Pagination::new(10);
+        pagination.page_fetched(10, 10);
+        assert!(pagination.needs_fetch(10));
+        assert!(!pagination.needs_fetch(25));
+
+        // A short page means the API has nothing more
+        pagination.page_fetched(10, 4);
+        assert!(!pagination.needs_fetch(14));
+    }
+
+    #[test]
+    fn test_needs_fetch_after_resize() {
+        // 20 notes cached with a 20-row page, then the terminal grows
+        let mut pagination = Pagination::new(20);
+        pagination.page_fetched(20, 20);
+        pagination.resize(30, 0, 20, false);
+
+        assert!(pagination.needs_fetch(20));
+        assert_eq!(pagination.visible_range(20, false), 0..20);
+    }
+
+    #[test]
+    fn test_resize_keeps_the_selected_note_in_view() {
+        // Row 5 of the third 20-row page is note 45
+        let mut pagination = Pagination::new(20);
+        pagination.offset = 40;
+        pagination.search_offset = 25;
+        let selected = pagination.resize(15, 5, 100, false);
+        assert_eq!(pagination.offset, 45);
+        assert_eq!(selected, 0);
+        // The other list only snaps to a page boundary
+        assert_eq!(pagination.search_offset, 15);
+
+        // Growing again lands on the page that holds note 45
+        let selected = pagination.resize(40, selected, 100, false);
+        assert_eq!(pagination.offset, 40);
+        assert_eq!(selected, 5);
+    }
+
+    #[test]
+    fn test_resize_clamps_to_available_notes() {
+        // Selection past the last of 12 search matches
+        let mut pagination = Pagination::new(10);
+        pagination.search_offset = 10;
+        let selected = pagination.resize(4, 9, 12, true);
+        assert_eq!(pagination.search_offset, 8);
+        assert_eq!(selected, 3);
+        assert_eq!(pagination.visible_range(12, true), 8..12);
+
+        let mut empty = Pagination::new(10);
+        assert_eq!(empty.resize(3, 2, 0, false), 0);
+        assert_eq!(empty.offset, 0);
+    }
+
+    fn note(id: &str, content: &str) -> Note {
+        Note::test(id)
+            .with_parent("people", "rec_1")
+            .with_title(&format!("Note {}", id))
+            .with_content(content)
+    }
+
+    fn ids(notes: &[Note]) -> Vec<&str> {
+        notes.iter().map(|n| n.id.note_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_key_cycles_back_to_fetch_order() {
+        let mut key = SortKey::default();
+        for _ in 0..4 {
+            key = key.next();
+        }
+        assert_eq!(key, SortKey::Fetched);
+    }
+
+    #[test]
+    fn test_sort_by_title_and_reverse() {
+        let mut notes = vec![note("1", ""), note("2", ""), note("3", "")];
+        notes[0].title = "beta".to_string();
+        notes[1].title = "Alpha".to_string();
+        notes[2].title = "gamma".to_string();
+        let mut order = NoteOrder::default();
+        order.record_arrivals(&notes);
+
+        order.key = SortKey::Title;
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["2", "1", "3"]);
+
+        order.descending = true;
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["3", "1", "2"]);
+        assert_eq!(order.label(), "title ↓");
+    }
+
+    #[test]
+    fn test_sort_ties_keep_fetch_order() {
+        // All share a created_at, so only arrival order can separate them
+        let mut notes = vec![note("c", ""), note("a", ""), note("b", "")];
+        let mut order = NoteOrder::default();
+        order.record_arrivals(&notes);
+
+        order.key = SortKey::Id;
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["a", "b", "c"]);
+
+        order.key = SortKey::Created;
+        order.descending = true;
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["c", "a", "b"]);
+
+        order.key = SortKey::Fetched;
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_sorted_cache_drives_both_paginations() {
+        let mut notes: Vec<Note> = (0..6).map(|i| note(&i.to_string(), "")).collect();
+        for (i, n) in notes.iter_mut().enumerate() {
+            n.title = if i % 2 == 0 { "match" } else { "other" }.to_string();
+        }
+        let mut order = NoteOrder::default();
+        order.record_arrivals(&notes);
+        order.key = SortKey::Id;
+        order.descending = true;
+        order.apply(&mut notes);
+
+        let mut pagination = Pagination::new(2);
+        let (page, _) = visible_notes(
+            &notes,
+            "",
+            SearchScope::All,
+            ContentFilter::All,
+            &pagination,
+        );
+        assert_eq!(
+            page.iter()
+                .map(|n| n.id.note_id.as_str())
+                .collect::<Vec<_>>(),
+            ["5", "4"]
+        );
+
+        pagination.next_page(3, true);
+        let (page, total) = visible_notes(
+            &notes,
+            "match",
+            SearchScope::Title,
+            ContentFilter::All,
+            &pagination,
+        );
+        assert_eq!(total, Some(3));
+        assert_eq!(
+            page.iter()
+                .map(|n| n.id.note_id.as_str())
+                .collect::<Vec<_>>(),
+            ["0"]
+        );
+    }
+
+    #[test]
+    fn test_new_arrivals_sort_into_place_after_eviction() {
+        let mut notes = vec![note("b", ""), note("d", "")];
+        let mut order = NoteOrder {
+            key: SortKey::Id,
+            ..Default::default()
+        };
+        order.record_arrivals(&notes);
+
+        // "b" is evicted, then "a" and "c" arrive in a later page
+        notes.remove(0);
+        notes.extend([note("c", ""), note("a", "")]);
+        order.record_arrivals(&notes);
+        order.forget_missing(&notes);
+        order.apply(&mut notes);
+
+        assert_eq!(ids(&notes), ["a", "c", "d"]);
+        assert_eq!(order.arrivals.len(), 3);
+        assert!(order.arrivals["a"] > order.arrivals["d"]);
+    }
+
+    #[test]
+    fn test_detail_lines_include_metadata_and_rendered_content() {
+        let view = DetailView::new(note("1", "# Heading\nbody"));
+        let text: Vec<String> = view
+            .lines(40, &Theme::default())
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            [
+                "Parent:  people rec_1",
+                "Created: 2024-01-01T00:00:00Z",
+                "",
+                "Heading",
+                "body"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detail_lines_rewrap_with_width() {
+        let view = DetailView::new(note("1", "one two three four"));
+        assert_eq!(view.lines(80, &Theme::default()).len(), 4);
+        assert_eq!(view.lines(8, &Theme::default()).len(), 6);
+    }
+
+    #[test]
+    fn test_detail_scroll_is_clamped() {
+        let mut view = DetailView::new(note("1", "a\nb\nc"));
+        let line_count = view.lines(80, &Theme::default()).len();
+        view.scroll_by(-3, line_count);
+        assert_eq!(view.scroll, 0);
+        // 3 metadata lines + 3 content lines: the last line is index 5
+        view.scroll_by(100, line_count);
+        assert_eq!(view.scroll, 5);
+        view.scroll_by(-2, line_count);
+        assert_eq!(view.scroll, 3);
+        // A narrower terminal can leave fewer lines than the current scroll
+        view.scroll_by(0, 2);
+        assert_eq!(view.scroll, 1);
+    }
+
+    #[test]
+    fn test_step_back_if_empty_after_last_note_removed() {
+        let mut pagination = Pagination::new(10);
+        pagination.offset = 20;
+        // Page 3 still has a note
+        pagination.step_back_if_empty(21, false);
+        assert_eq!(pagination.offset, 20);
+        // Its only note was deleted
+        pagination.step_back_if_empty(20, false);
+        assert_eq!(pagination.offset, 10);
+        // Everything was deleted
+        pagination.step_back_if_empty(0, false);
+        assert_eq!(pagination.offset, 0);
+    }
+
+    #[test]
+    fn test_fetch_progress_label() {
+        let mut progress = FetchProgress {
+            pages: 2,
+            ..FetchProgress::default()
+        };
+        assert_eq!(
+            progress.label(100, 50),
+            "Fetching all... page 2 (100 cached)"
+        );
+
+        progress.total = Some(420);
+        progress.rate_limited_secs = Some(7);
+        assert_eq!(
+            progress.label(100, 50),
+            "Fetching all... page 2 of ~9 (100 of ~420 notes), rate limited, waiting 7s"
+        );
+    }
+
+    #[test]
+    fn test_fetch_all_stops_at_cap() {
+        let page = |n: usize| (0..n).map(|i| note(&i.to_string(), "")).collect::<Vec<_>>();
+        let mut progress = FetchProgress::default();
+
+        let mut first = page(50);
+        assert!(!progress.count_page(&mut first, Some(120), true));
+        assert!(!progress.count_page(&mut page(50), Some(120), true));
+
+        // Only the notes up to the cap are kept
+        let mut third = page(50);
+        assert!(progress.count_page(&mut third, Some(120), true));
+        assert_eq!(third.len(), 20);
+        assert_eq!(progress.notes, 120);
+
+        // Reaching the cap on the last page isn't reported as capped
+        let mut progress = FetchProgress::default();
+        assert!(!progress.count_page(&mut page(50), Some(50), false));
+        assert!(!progress.count_page(&mut page(50), None, true));
+    }
+
+    #[test]
+    fn test_evicted_notes_still_count_towards_fetch_offset() {
+        let mut pagination = Pagination::new(10);
+        pagination.offset = 30;
+        pagination.notes_evicted(15, Some(15));
+        assert_eq!(pagination.offset, 15);
+        assert_eq!(pagination.fetch_offset(25), 40);
+
+        // The anchor itself was evicted
+        pagination.notes_evicted(5, None);
+        assert_eq!(pagination.offset, 0);
+        assert_eq!(pagination.fetch_offset(20), 40);
+    }
+
+    #[test]
+    fn test_first_and_last_page() {
+        let mut pagination = Pagination::new(10);
+        pagination.last_page(25, false);
+        assert_eq!(pagination.offset, 20);
+        pagination.last_page(30, true);
+        assert_eq!(pagination.search_offset, 20);
+        pagination.last_page(0, false);
+        assert_eq!(pagination.offset, 0);
+
+        pagination.offset = 20;
+        pagination.first_page(false);
+        assert_eq!(pagination.offset, 0);
+        assert_eq!(pagination.search_offset, 20);
+    }
+
+    #[test]
+    fn test_step_match_turns_pages_and_wraps() {
+        let mut pagination = Pagination::new(10);
+        // Last row of page 1 moves onto page 2
+        assert_eq!(pagination.step_match(9, 15, true), 0);
+        assert_eq!(pagination.search_offset, 10);
+        // Last match wraps to the first
+        assert_eq!(pagination.step_match(4, 15, true), 0);
+        assert_eq!(pagination.search_offset, 0);
+        // And backwards from the first wraps to the last
+        assert_eq!(pagination.step_match(0, 15, false), 4);
+        assert_eq!(pagination.search_offset, 10);
+        assert_eq!(pagination.step_match(0, 0, true), 0);
+    }
+
+    #[test]
+    fn test_vim_alias_only_in_normal_mode() {
+        assert_eq!(
+            vim_alias(KeyCode::Char('h'), &InputMode::Normal),
+            KeyCode::Left
+        );
+        assert_eq!(
+            vim_alias(KeyCode::Char('l'), &InputMode::Normal),
+            KeyCode::Right
+        );
+        assert_eq!(
+            vim_alias(KeyCode::Char('l'), &InputMode::Search),
+            KeyCode::Char('l')
+        );
+        assert_eq!(
+            vim_alias(KeyCode::Right, &InputMode::Normal),
+            KeyCode::Right
+        );
+    }
+
+    #[test]
+    fn test_flash_message_expires() {
+        let fresh = Some(("Copied!".to_string(), Instant::now()));
+        assert_eq!(flash_message(&fresh), Some("Copied!"));
+
+        let stale = Instant::now().checked_sub(FLASH_DURATION);
+        if let Some(shown) = stale {
+            assert_eq!(flash_message(&Some(("Copied!".to_string(), shown))), None);
+        }
+        assert_eq!(flash_message(&None), None);
+    }
+
+    #[test]
+    fn test_saved_state_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = state_path(&dir.path().join("attio").join("config.json"));
+        assert_eq!(path, dir.path().join("attio").join("tui-state.json"));
+
+        let state = SavedState {
+            offset: 40,
+            search_query: "renewal".to_string(),
+            search_scope: SearchScope::Title,
+            sort_key: SortKey::Created,
+            descending: true,
+        };
+        state.save(&path).unwrap();
+        assert_eq!(SavedState::load(&path), state);
+    }
+
+    #[test]
+    fn test_unreadable_saved_state_starts_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tui-state.json");
+        assert_eq!(SavedState::load(&path), SavedState::default());
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(SavedState::load(&path), SavedState::default());
+
+        fs::write(&path, r#"{"offset": 20}"#).unwrap();
+        assert_eq!(SavedState::load(&path).offset, 20);
+    }
+
+    #[test]
+    fn test_refreshed_notes_go_to_the_top_of_fetch_order() {
+        let mut cache = vec![note("a", ""), note("b", "")];
+        let mut order = NoteOrder::default();
+        order.record_arrivals(&cache);
+
+        let page = vec![note("c", ""), note("d", ""), note("a", "")];
+        let fresh = unseen_notes(&cache, page);
+        assert_eq!(ids(&fresh), ["c", "d"]);
+
+        order.record_newest(&fresh);
+        cache.extend(fresh);
+        order.record_arrivals(&cache);
+        order.apply(&mut cache);
+        assert_eq!(ids(&cache), ["c", "d", "a", "b"]);
+    }
+
+    #[test]
+    fn test_refreshed_label() {
+        assert_eq!(refreshed_label(Duration::from_secs(5)), "Refreshed 5s ago");
+        assert_eq!(
+            refreshed_label(Duration::from_secs(150)),
+            "Refreshed 2m ago"
+        );
+    }
+
+    #[test]
+    fn test_clicked_row_skips_border_and_header() {
+        let (_, table, _) = list_layout(Rect::new(0, 0, 80, 24), false);
+        assert_eq!(table, Rect::new(0, 0, 80, 21));
+        assert_eq!(clicked_row(table, 10, 2), Some(0));
+        assert_eq!(clicked_row(table, 10, 5), Some(3));
+        // Header, borders and the footer below the table
+        assert_eq!(clicked_row(table, 10, 1), None);
+        assert_eq!(clicked_row(table, 0, 5), None);
+        assert_eq!(clicked_row(table, 10, 20), None);
+        assert_eq!(clicked_row(table, 10, 22), None);
+
+        // The search box pushes the table down
+        let (search, table, _) = list_layout(Rect::new(0, 0, 80, 24), true);
+        assert_eq!(search, Some(Rect::new(0, 0, 80, 3)));
+        assert_eq!(clicked_row(table, 10, 5), Some(0));
+    }
+
+    #[test]
+    fn test_scroll_wheel_maps_to_keys() {
+        assert_eq!(
+            scroll_key(MouseEventKind::ScrollDown, false),
+            Some(KeyCode::Right)
+        );
+        assert_eq!(
+            scroll_key(MouseEventKind::ScrollUp, true),
+            Some(KeyCode::Up)
+        );
+        assert_eq!(scroll_key(MouseEventKind::Moved, false), None);
+    }
+
+    #[test]
+    fn test_cancel_keys() {
+        assert!(is_cancel_key(&Event::Key(KeyCode::Char('q').into())));
+        assert!(is_cancel_key(&Event::Key(KeyCode::Esc.into())));
+        assert!(!is_cancel_key(&Event::Key(KeyCode::Char('a').into())));
+        assert!(!is_cancel_key(&Event::FocusGained));
+    }
+
+    #[test]
+    fn test_centered_rect_fits_inside_area() {
+        let area = Rect::new(0, 0, 100, 40);
+        assert_eq!(centered_rect(60, 5, area), Rect::new(20, 17, 60, 5));
+        let small = Rect::new(0, 0, 30, 3);
+        assert_eq!(centered_rect(60, 5, small), small);
+    }
+
+    #[test]
+    fn test_page_number_with_zero_limit() {
+        let pagination = Pagination::new(0);
+        assert_eq!(pagination.limit, 1);
+        assert_eq!(pagination.page_number(false), 1);
+    }
+
+    #[test]
+    fn test_uncached_pages_replace_each_other() {
+        let mut pagination = Pagination::new(10);
+        pagination.cached = false;
+        pagination.page_replaced(0, 10);
+        assert_eq!(pagination.prev_fetch_offset(), None);
+        assert_eq!(pagination.fetch_offset(10), 10);
+
+        pagination.page_replaced(10, 10);
+        assert_eq!(pagination.page_number(false), 2);
+        assert_eq!(pagination.fetch_offset(10), 20);
+        assert_eq!(pagination.prev_fetch_offset(), Some(0));
+
+        // A short page is the last one
+        pagination.page_replaced(20, 4);
+        assert!(!pagination.has_more);
+        assert_eq!(pagination.page_number(false), 3);
+        // Search only pages through the held notes
+        assert_eq!(pagination.page_number(true), 1);
+    }
+
+    fn press(form: &mut CreateForm, code: KeyCode) -> FormAction {
+        form.handle_key(event::KeyEvent::new(code, event::KeyModifiers::NONE))
+    }
+
+    fn type_text(form: &mut CreateForm, text: &str) {
+        for c in text.chars() {
+            press(form, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_create_form_moves_between_fields() {
+        let mut form = CreateForm::default();
+        type_text(&mut form, "people");
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, "rec_1");
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "Kickoff");
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, "line one");
+        // Enter in the content starts a new line instead of moving on
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "line twoo");
+        press(&mut form, KeyCode::Backspace);
+        assert_eq!(form.focus, FormField::Content);
+
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, FormField::ParentObject);
+        press(&mut form, KeyCode::BackTab);
+        assert_eq!(form.focus, FormField::Content);
+
+        let submit = form.handle_key(event::KeyEvent::new(
+            KeyCode::Char('s'),
+            event::KeyModifiers::CONTROL,
+        ));
+        assert_eq!(submit, FormAction::Submit);
+        let data = form.validate().unwrap();
+        assert_eq!(data.parent_object, "people");
+        assert_eq!(data.parent_record_id, "rec_1");
+        assert_eq!(data.title, "Kickoff");
+        assert_eq!(data.content, "line one\nline two");
+        assert_eq!(press(&mut form, KeyCode::Esc), FormAction::Cancel);
+    }
+
+    #[test]
+    fn test_create_form_reports_missing_fields() {
+        let mut form = CreateForm::new(&CreateDefaults {
+            parent_object: Some("companies".to_string()),
+            parent_record_id: None,
+        });
+        assert_eq!(form.focus, FormField::ParentRecordId);
+        type_text(&mut form, "  ");
+
+        assert!(form.validate().is_none());
+        assert_eq!(
+            form.missing,
+            [
+                FormField::ParentRecordId,
+                FormField::Title,
+                FormField::Content
+            ]
+        );
+        assert_eq!(form.focus, FormField::ParentRecordId);
+        let text: Vec<String> = form
+            .lines(&Theme::default())
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert!(text.contains(&"Title (required)".to_string()));
+        assert!(text.contains(&"  companies".to_string()));
+    }
+
+    #[test]
+    fn test_created_note_stays_first_in_fetch_order() {
+        let mut notes = vec![note("a", ""), note("b", "")];
+        let mut order = NoteOrder::default();
+        order.record_arrivals(&notes);
+
+        let created = note("new", "");
+        order.record_first(&created);
+        notes.push(created);
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["new", "a", "b"]);
+
+        // Notes fetched later still go after it
+        notes.push(note("c", ""));
+        order.record_arrivals(&notes);
+        order.apply(&mut notes);
+        assert_eq!(ids(&notes), ["new", "a", "b", "c"]);
     }
 }
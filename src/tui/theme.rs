@@ -0,0 +1,257 @@
+use crate::models::ThemeName;
+use ratatui::style::Color;
+use std::collections::BTreeMap;
+
+/// Colors the TUI draws with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Table column headers and create form labels
+    pub header: Color,
+    /// Borders of the detail view and controls blocks
+    pub border: Color,
+    /// Search box while typing
+    pub search: Color,
+    /// Search box holding a finished search
+    pub search_idle: Color,
+    /// Key hints in the footer
+    pub footer: Color,
+    /// Create, confirmations and the focused form field
+    pub success: Color,
+    pub warning: Color,
+    /// Delete, quit and errors
+    pub danger: Color,
+    /// Fetch-all and refresh hints
+    pub highlight: Color,
+    /// Metadata above a note's content
+    pub muted: Color,
+    /// Table border by cache usage: below `cache_warn_percent`, below
+    /// `cache_full_percent`, then above
+    pub cache_low: Color,
+    pub cache_mid: Color,
+    pub cache_high: Color,
+    pub cache_warn_percent: f64,
+    pub cache_full_percent: f64,
+}
+
+/// Names of the colors `theme-colors` can override
+pub const COLOR_KEYS: &[&str] = &[
+    "header",
+    "border",
+    "search",
+    "search-idle",
+    "footer",
+    "success",
+    "warning",
+    "danger",
+    "highlight",
+    "muted",
+    "cache-low",
+    "cache-mid",
+    "cache-high",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            header: Color::Cyan,
+            border: Color::Reset,
+            search: Color::Green,
+            search_idle: Color::Yellow,
+            footer: Color::Cyan,
+            success: Color::Green,
+            warning: Color::Yellow,
+            danger: Color::Red,
+            highlight: Color::Magenta,
+            muted: Color::DarkGray,
+            cache_low: Color::Green,
+            cache_mid: Color::Yellow,
+            cache_high: Color::Red,
+            cache_warn_percent: 70.0,
+            cache_full_percent: 90.0,
+        }
+    }
+}
+
+impl Theme {
+    /// The built-in palette `name`
+    pub fn named(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Default => Self::default(),
+            ThemeName::Dark => Self {
+                header: Color::LightCyan,
+                search: Color::LightGreen,
+                search_idle: Color::LightYellow,
+                footer: Color::LightCyan,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                danger: Color::LightRed,
+                highlight: Color::LightMagenta,
+                muted: Color::Gray,
+                cache_low: Color::LightGreen,
+                cache_mid: Color::LightYellow,
+                cache_high: Color::LightRed,
+                ..Self::default()
+            },
+            ThemeName::Light => Self {
+                header: Color::Blue,
+                search_idle: Color::Rgb(0x9a, 0x67, 0x00),
+                footer: Color::Blue,
+                warning: Color::Rgb(0x9a, 0x67, 0x00),
+                cache_mid: Color::Rgb(0x9a, 0x67, 0x00),
+                ..Self::default()
+            },
+            ThemeName::Monochrome => Self {
+                header: Color::Reset,
+                search: Color::Reset,
+                search_idle: Color::Reset,
+                footer: Color::Reset,
+                success: Color::Reset,
+                warning: Color::Reset,
+                danger: Color::Reset,
+                highlight: Color::Reset,
+                muted: Color::Reset,
+                cache_low: Color::Reset,
+                cache_mid: Color::Reset,
+                cache_high: Color::Reset,
+                ..Self::default()
+            },
+        }
+    }
+
+    /// The palette `name` with `overrides` (color key to `#rrggbb`) applied
+    pub fn with_overrides(
+        name: ThemeName,
+        overrides: &BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        let mut theme = Self::named(name);
+        for (key, value) in overrides {
+            let color = parse_hex(value).ok_or_else(|| {
+                format!(
+                    "Invalid color for {}: {}. Colors must look like #rrggbb.",
+                    key, value
+                )
+            })?;
+            *theme.slot_mut(key).ok_or_else(|| {
+                format!(
+                    "Unknown theme color: {}. Available colors: {}",
+                    key,
+                    COLOR_KEYS.join(", ")
+                )
+            })? = color;
+        }
+        Ok(theme)
+    }
+
+    fn slot_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "header" => &mut self.header,
+            "border" => &mut self.border,
+            "search" => &mut self.search,
+            "search-idle" => &mut self.search_idle,
+            "footer" => &mut self.footer,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "danger" => &mut self.danger,
+            "highlight" => &mut self.highlight,
+            "muted" => &mut self.muted,
+            "cache-low" => &mut self.cache_low,
+            "cache-mid" => &mut self.cache_mid,
+            "cache-high" => &mut self.cache_high,
+            _ => return None,
+        })
+    }
+
+    /// Table border color for a cache `usage_percent` full
+    pub fn cache_color(&self, usage_percent: f64) -> Color {
+        if usage_percent < self.cache_warn_percent {
+            self.cache_low
+        } else if usage_percent < self.cache_full_percent {
+            self.cache_mid
+        } else {
+            self.cache_high
+        }
+    }
+}
+
+/// `#rrggbb` as an RGB color
+pub fn parse_hex(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Parses `theme-colors` as given to `config set`: comma-separated
+/// `key=#rrggbb` pairs, or `none` for no overrides
+pub fn parse_overrides(value: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut overrides = BTreeMap::new();
+    if value == "none" {
+        return Ok(overrides);
+    }
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, color) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid theme color: {}. Expected key=#rrggbb.", pair))?;
+        overrides.insert(key.trim().to_string(), color.trim().to_string());
+    }
+    // Validates every key and color
+    Theme::with_overrides(ThemeName::Default, &overrides)?;
+    Ok(overrides)
+}
+
+/// `overrides` in the form `parse_overrides` reads
+pub fn format_overrides(overrides: &BTreeMap<String, String>) -> String {
+    if overrides.is_empty() {
+        return "none".to_string();
+    }
+    overrides
+        .iter()
+        .map(|(key, color)| format!("{}={}", key, color))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("#ff8800"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex("#FF8800"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex("ff8800"), None);
+        assert_eq!(parse_hex("#ff880"), None);
+        assert_eq!(parse_hex("#gg8800"), None);
+    }
+
+    #[test]
+    fn test_overrides_replace_named_colors() {
+        let overrides = parse_overrides("header=#ff8800, cache-high=#010203").unwrap();
+        let theme = Theme::with_overrides(ThemeName::Monochrome, &overrides).unwrap();
+        assert_eq!(theme.header, Color::Rgb(255, 136, 0));
+        assert_eq!(theme.cache_high, Color::Rgb(1, 2, 3));
+        assert_eq!(theme.footer, Color::Reset);
+        assert_eq!(
+            format_overrides(&overrides),
+            "cache-high=#010203,header=#ff8800"
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_rejected() {
+        assert!(parse_overrides("header").is_err());
+        assert!(parse_overrides("headers=#ff8800").is_err());
+        assert!(parse_overrides("header=orange").is_err());
+        assert!(parse_overrides("none").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cache_color_follows_thresholds() {
+        let theme = Theme::default();
+        assert_eq!(theme.cache_color(10.0), Color::Green);
+        assert_eq!(theme.cache_color(70.0), Color::Yellow);
+        assert_eq!(theme.cache_color(95.0), Color::Red);
+    }
+}
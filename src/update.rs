@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// GitHub API entry for this repo's newest published release
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/zlahham/attio-cli/releases/latest";
+
+/// Where release binaries are downloaded from
+pub const RELEASES_PAGE_URL: &str = "https://github.com/zlahham/attio-cli/releases";
+
+/// The version check is a courtesy, so it never holds a command up for long
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize)]
+struct Release {
+    /// e.g. `v0.3.0`
+    tag_name: String,
+}
+
+/// What `version` prints with `--output json`
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    /// Latest released version; `None` when not checked or the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<String>,
+    pub update_available: bool,
+}
+
+/// The version of the release at `url`, or `None` when it can't be fetched
+/// for any reason. GitHub's latest release is never a draft or pre-release.
+pub async fn latest_version(url: &str) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        // GitHub rejects requests without a user agent
+        .user_agent(concat!("attio-cli/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .ok()?;
+    let response = client
+        .get(url)
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?;
+    let release = response.json::<Release>().await.ok()?;
+    Some(release.tag_name.trim_start_matches('v').to_string())
+}
+
+/// Whether `latest` is a higher version than `current`. Pre-release and
+/// build suffixes are ignored; anything unparseable never counts as newer.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse(latest), parse(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn parse(version: &str) -> Option<Vec<u64>> {
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_is_newer_compares_numerically() {
+        assert!(is_newer("0.10.0", "0.9.1"));
+        assert!(is_newer("1.0.0", "0.2.0"));
+        assert!(!is_newer("0.2.0", "0.2.0"));
+        assert!(!is_newer("0.1.9", "0.2.0"));
+        assert!(!is_newer("0.3.0-beta.1", "0.3.0"));
+        assert!(!is_newer("garbage", "0.2.0"));
+    }
+
+    #[tokio::test]
+    async fn test_latest_version_strips_the_tag_prefix() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/zlahham/attio-cli/releases/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tag_name": "v0.3.0",
+                "html_url": "https://github.com/zlahham/attio-cli/releases/tag/v0.3.0"
+            })))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/repos/zlahham/attio-cli/releases/latest", server.uri());
+        assert_eq!(latest_version(&url).await.as_deref(), Some("0.3.0"));
+    }
+
+    #[tokio::test]
+    async fn test_latest_version_is_none_on_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        assert_eq!(latest_version(&server.uri()).await, None);
+        assert_eq!(latest_version("http://127.0.0.1:9").await, None);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::client::AttioClient;
+use crate::filter::ContentFilter;
+use crate::models::Note;
+use crate::symbols::SymbolSet;
+use crossterm::style::Stylize;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+/// Notes fetched per poll
+const WATCH_PAGE_SIZE: u32 = 50;
+/// Longest wait between polls while the API keeps failing
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Remembers which notes have already been shown
+#[derive(Default)]
+pub struct NoteWatcher {
+    seen: HashSet<String>,
+}
+
+impl NoteWatcher {
+    /// Returns the notes not seen before, oldest first, and marks them as seen
+    pub fn new_notes<'a>(&mut self, notes: &'a [Note]) -> Vec<&'a Note> {
+        let mut fresh: Vec<&Note> = notes
+            .iter()
+            .filter(|note| self.seen.insert(note.id.note_id.clone()))
+            .collect();
+        fresh.sort_by_key(|note| note.created_at_datetime().ok());
+        fresh
+    }
+}
+
+/// Wait before the next poll: the base interval after a success, doubling
+/// (up to `MAX_BACKOFF`) after each consecutive failure
+pub fn next_interval(base: Duration, current: Duration, failed: bool) -> Duration {
+    if failed {
+        current.saturating_mul(2).clamp(base, MAX_BACKOFF.max(base))
+    } else {
+        base
+    }
+}
+
+fn print_note(note: &Note, highlight: bool) {
+    let line = format!(
+        "{}  {}  {}",
+        note.created_at,
+        note.id.note_id,
+        if note.title.is_empty() {
+            "(untitled)"
+        } else {
+            &note.title
+        }
+    );
+    if highlight {
+        println!("{}", format!("NEW {}", line).green().bold());
+    } else {
+        println!("    {}", line);
+    }
+}
+
+/// Polls the notes of one record until Ctrl+C, printing notes as they arrive
+pub async fn watch_record(
+    client: &AttioClient,
+    object: &str,
+    record_id: &str,
+    interval: Duration,
+    content_filter: ContentFilter,
+    symbols: SymbolSet,
+) -> Result<(), Box<dyn Error>> {
+    let client = client.clone().with_note_parent(object, record_id);
+    let highlight = io::stdout().is_terminal();
+    let mut watcher = NoteWatcher::default();
+    let mut first_poll = true;
+    let mut wait = interval;
+
+    println!(
+        "{} Watching notes on {}/{} every {}s. Press Ctrl+C to stop.",
+        symbols.link(),
+        object,
+        record_id,
+        interval.as_secs()
+    );
+
+    loop {
+        match client.list_notes(Some(WATCH_PAGE_SIZE), None).await {
+            Ok(response) => {
+                let notes: Vec<Note> = response
+                    .data
+                    .into_iter()
+                    .filter(|note| content_filter.matches(note))
+                    .collect();
+                for note in watcher.new_notes(&notes) {
+                    print_note(note, highlight && !first_poll);
+                }
+                if first_poll && notes.is_empty() {
+                    println!("    No notes yet.");
+                }
+                first_poll = false;
+                wait = next_interval(interval, wait, false);
+            }
+            Err(e) => {
+                wait = next_interval(interval, wait, true);
+                eprintln!(
+                    "{} Poll failed: {}. Retrying in {}s.",
+                    symbols.warning(),
+                    e,
+                    wait.as_secs()
+                );
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(wait) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, created_at: &str) -> Note {
+        Note::test(id)
+            .with_parent("deals", "rec")
+            .with_created_at(created_at)
+    }
+
+    #[test]
+    fn test_new_notes_only_returns_unseen() {
+        let mut watcher = NoteWatcher::default();
+        let first = vec![note("a", "2024-01-01"), note("b", "2024-01-02")];
+        assert_eq!(watcher.new_notes(&first).len(), 2);
+
+        let second = vec![
+            note("c", "2024-01-03"),
+            note("a", "2024-01-01"),
+            note("b", "2024-01-02"),
+        ];
+        let fresh = watcher.new_notes(&second);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].id.note_id, "c");
+
+        assert!(watcher.new_notes(&second).is_empty());
+    }
+
+    #[test]
+    fn test_new_notes_are_oldest_first() {
+        let mut watcher = NoteWatcher::default();
+        // "c" is the earliest once its offset is taken into account
+        let notes = vec![
+            note("b", "2024-01-02T00:00:00Z"),
+            note("a", "2024-01-01T00:00:00Z"),
+            note("c", "2024-01-01T01:00:00+05:00"),
+        ];
+
+        let fresh = watcher.new_notes(&notes);
+
+        assert_eq!(fresh[0].id.note_id, "c");
+        assert_eq!(fresh[1].id.note_id, "a");
+        assert_eq!(fresh[2].id.note_id, "b");
+    }
+
+    #[test]
+    fn test_backoff_doubles_on_failure_and_resets() {
+        let base = Duration::from_secs(10);
+        let wait = next_interval(base, base, true);
+        assert_eq!(wait, Duration::from_secs(20));
+        let wait = next_interval(base, wait, true);
+        assert_eq!(wait, Duration::from_secs(40));
+        assert_eq!(next_interval(base, wait, false), base);
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let base = Duration::from_secs(10);
+        assert_eq!(
+            next_interval(base, Duration::from_secs(250), true),
+            MAX_BACKOFF
+        );
+        // A base interval above the cap is still honored
+        let slow = Duration::from_secs(600);
+        assert_eq!(next_interval(slow, slow, true), slow);
+    }
+}